@@ -1,7 +1,14 @@
 fn main() {
     let sdk_path = std::env::var("R3DSDK_DIR").unwrap_or_else(|_| "sdk".to_string());
 
-    let include_path = std::path::PathBuf::from(format!("{sdk_path}/Include"));
+    // The mock backend replaces the SDK headers and libraries with the implementation in mock/
+    let mock = cfg!(feature = "mock");
+
+    let include_path = if mock {
+        std::path::PathBuf::from("mock/include")
+    } else {
+        std::path::PathBuf::from(format!("{sdk_path}/Include"))
+    };
 
     let mut config = cpp_build::Config::new();
 
@@ -10,8 +17,11 @@ fn main() {
         .flag_if_supported("-fno-rtti")
         .flag_if_supported("-xobjective-c++")
         .include(&include_path)
-        .include("headers/")
-        .build("src/lib.rs");
+        .include("headers/");
+    if mock {
+        config.file("mock/r3dsdk_mock.cpp");
+    }
+    config.build("src/lib.rs");
 
     println!("cargo:rerun-if-env-changed=R3DSDK_DIR");
     println!("cargo:rerun-if-changed=src/asyncdecoder.rs");
@@ -24,13 +34,15 @@ fn main() {
     println!("cargo:rerun-if-changed=src/image_processing_limits.rs");
     println!("cargo:rerun-if-changed=src/lib.rs");
     println!("cargo:rerun-if-changed=src/metadata.rs");
+    println!("cargo:rerun-if-changed=src/mock.rs");
     println!("cargo:rerun-if-changed=src/r3ddecoder.rs");
     println!("cargo:rerun-if-changed=src/sdk.rs");
+    println!("cargo:rerun-if-changed=mock");
 
-    println!("cargo:rustc-include-search={sdk_path}/Include");
+    println!("cargo:rustc-include-search={}", include_path.display());
     println!("cargo:rustc-include-search=headers/");
 
-    if cfg!(feature = "link") {
+    if cfg!(feature = "link") && !mock {
         if cfg!(target_os = "windows") {
             println!("cargo:rustc-link-search={sdk_path}/Lib/win64");
             println!("cargo:rustc-link-lib=R3DSDK-2017MD");
@@ -0,0 +1,297 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#pragma once
+
+#include "R3DSDKDefinitions.h"
+#include "R3DSDKMetadata.h"
+
+#include <memory>
+
+namespace R3DSDK {
+
+InitializeStatus InitializeSdk(const char * libraryPath, unsigned int optionalComponents);
+void FinalizeSdk();
+const char * GetSdkVersion();
+FileId IdentifyFile(const char * path);
+
+// 3D LUTs can't be loaded by the mock, so this always returns NULL
+const char * Get3DLutPath(Handle3DLut handle);
+
+struct LiftGammaGain { float Lift; float Gamma; float Gain; };
+struct SlopeOffsetPower { float Slope; float Offset; float Power; };
+
+class ImageProcessingSettings {
+public:
+	ImageProcessingSettings();
+
+	ColorVersion Version;
+	float Kelvin;
+	float Tint;
+	float ExposureCompensation;
+	float GainRed;
+	float GainGreen;
+	float GainBlue;
+	float Saturation;
+	float Contrast;
+	float Brightness;
+	float DRX;
+	size_t ISO;
+	ImageGammaCurve GammaCurve;
+	ImageColorSpace ColorSpace;
+	size_t CustomPDLogBlackPoint;
+	size_t CustomPDLogWhitePoint;
+	float CustomPDLogGamma;
+	float UserCurve[10];
+	float UserCurveRed[10];
+	float UserCurveGreen[10];
+	float UserCurveBlue[10];
+	ImageDetail Detail;
+	ImageOLPFCompensation OLPFCompensation;
+	ImageDenoise Denoise;
+	union { bool DEB; bool ChromaNoiseReduction; };
+	FlashingPixelAdjust FlashingPixelAdjustment;
+	float Shadow;
+	float FLUT;
+	LiftGammaGain LggRed;
+	LiftGammaGain LggGreen;
+	LiftGammaGain LggBlue;
+	ImagePipeline ImagePipelineMode;
+	float ExposureAdjust;
+	SlopeOffsetPower CdlRed;
+	SlopeOffsetPower CdlGreen;
+	SlopeOffsetPower CdlBlue;
+	float CdlSaturation;
+	bool CdlEnabled;
+	Handle3DLut Lut3D;
+	bool Lut3DEnabled;
+	ToneMap OutputToneMap;
+	RollOff HighlightRollOff;
+	unsigned int HdrPeakNits;
+	bool MasklineAdjust;
+
+	void CheckBounds();
+};
+
+class HdrProcessingSettings {
+public:
+	HdrProcessingSettings();
+
+	HdrBlendAlgorithm BlendAlgorithm;
+	float Bias;
+
+	void CheckBounds();
+};
+
+class ImageProcessingLimits {
+public:
+	static const float KelvinMin, KelvinMax, KelvinDefault;
+	static const float TintMin, TintMax, TintDefault;
+	static const float ExposureMin, ExposureMax, ExposureDefault;
+	static const float GainsMin, GainsMax, GainsDefault;
+	static const float SaturationMin, SaturationMax, SaturationDefault;
+	static const float ContrastMin, ContrastMax, ContrastDefault;
+	static const float BrightnessMin, BrightnessMax, BrightnessDefault;
+	static const float DRXMin, DRXMax, DRXDefault;
+	static const float ShadowMin, ShadowMax, ShadowDefault;
+	static const float FLUTMin, FLUTMax, FLUTDefault;
+	static const float LggLiftMin, LggLiftMax, LggLiftDefault;
+	static const float LggGammaMin, LggGammaMax, LggGammaDefault;
+	static const float LggGainMin, LggGainMax, LggGainDefault;
+
+	static const size_t ISOCount;
+	static const size_t ISOList[];
+	static const size_t ISODefault;
+
+	static const size_t GammaCurveCount;
+	static const ImageGammaCurve GammaCurveMap[];
+	static const char * GammaCurveLabels[];
+	static const ImageGammaCurve GammaCurveDefault;
+
+	static const size_t ColorSpaceCount;
+	static const ImageColorSpace ColorSpaceMap[];
+	static const char * ColorSpaceLabels[];
+	static const ImageColorSpace ColorSpaceDefault;
+
+	static const size_t CustomPDLogBlackPointMin, CustomPDLogBlackPointMax, CustomPDLogBlackPointDefault;
+	static const size_t CustomPDLogWhitePointMin, CustomPDLogWhitePointMax, CustomPDLogWhitePointDefault;
+	static const float CustomPDLogGammaMin, CustomPDLogGammaMax, CustomPDLogGammaDefault;
+
+	static const size_t ImagePipelineModeCount;
+	static const ImagePipeline ImagePipelineModeMap[];
+	static const char * ImagePipelineModeLabels[];
+	static const ImagePipeline ImagePipelineModeDefault;
+
+	static const size_t HighlightRollOffCount;
+	static const RollOff HighlightRollOffMap[];
+	static const char * HighlightRollOffLabels[];
+	static const RollOff HighlightRollOffDefault;
+
+	static const size_t OutputToneMapCount;
+	static const ToneMap OutputToneMapMap[];
+	static const char * OutputToneMapLabels[];
+	static const ToneMap OutputToneMapDefault;
+
+	static const float ExposureAdjustMin, ExposureAdjustMax, ExposureAdjustDefault;
+	static const unsigned int HdrPeakNitsMin, HdrPeakNitsMax, HdrPeakNitsDefault;
+	static const float CdlSlopeMin, CdlSlopeMax, CdlSlopeDefault;
+	static const float CdlOffsetMin, CdlOffsetMax, CdlOffsetDefault;
+	static const float CdlPowerMin, CdlPowerMax, CdlPowerDefault;
+	static const float CdlSaturationMin, CdlSaturationMax, CdlSaturationDefault;
+};
+
+class VideoDecodeJob {
+public:
+	VideoDecodeJob();
+
+	VideoDecodeMode Mode;
+	VideoPixelType PixelType;
+	void * OutputBuffer;
+	size_t OutputBufferSize;
+	const ImageProcessingSettings * ImageProcessing;
+	const HdrProcessingSettings * HdrProcessing;
+	Metadata * OutputFrameMetadata;
+};
+
+struct IMUSample {
+	unsigned long long timestamp;
+	float ax, ay, az;
+	float rx, ry, rz;
+};
+
+namespace Mock { struct ClipDescription; }
+
+class Clip {
+public:
+	Clip();
+	Clip(const char * pathToFile);
+	~Clip();
+
+	LoadStatus LoadFrom(const char * pathToFile);
+	LoadStatus Status() const;
+	void Close();
+	void CloseFileHandles();
+
+	bool RmdSidecarPresent() const;
+	const char * GetRmdPath();
+
+	DecodeStatus CheckFrame(size_t videoFrameNo) const;
+	bool ExtendedHighlightsEnabled() const;
+	bool SetExtendedHighlights(bool enabled);
+
+	size_t VideoTrackCount() const;
+	DecodeStatus DecodeVideoFrame(size_t videoFrameNo, const VideoDecodeJob & decodeJob) const;
+	DecodeStatus VideoTrackDecodeFrame(size_t videoTrackNo, size_t videoFrameNo, const VideoDecodeJob & decodeJob) const;
+
+	bool Uuid(uint8_t * uuid) const;
+	size_t Width() const;
+	size_t Height() const;
+	size_t VideoFrameCount() const;
+	size_t AudioChannelCount() const;
+	size_t AudioBlockCountAndSize(size_t * maximumSize) const;
+	unsigned long long AudioSampleCount() const;
+	float VideoAudioFramerate() const;
+	float TimecodeFramerate() const;
+	const char * Timecode(size_t videoFrameNo);
+	const char * AbsoluteTimecode(size_t videoFrameNo);
+	const char * EdgeTimecode(size_t videoFrameNo);
+
+	void GetDefaultImageProcessingSettings(ImageProcessingSettings & settingsToFill) const;
+	void GetClipImageProcessingSettings(ImageProcessingSettings & settingsToFill) const;
+	HdrMode GetRmdHdrProcessingSettings(HdrProcessingSettings & settingsToFill, size_t & trackNoToFill) const;
+	ColorVersion MinimumColorVersion() const;
+	ColorVersion DefaultColorVersion() const;
+
+	DecodeStatus GetFrameMetadata(Metadata & metadataToFill, size_t videoFrameNo) const;
+	size_t MetadataCount() const;
+	bool MetadataExists(const char * key) const;
+	MetadataType MetadataItemType(const char * key) const;
+	unsigned int MetadataItemAsInt(const char * key) const;
+	std::string MetadataItemAsString(const char * key) const;
+	float MetadataItemAsFloat(const char * key) const;
+	std::string MetadataItemKey(size_t index) const;
+
+	size_t IMUSampleCount() const;
+	bool DecodeIMUSamples(IMUSample * samples, size_t start, size_t count) const;
+
+	enum FileType
+	{
+		FileType_Invalid  = 0,
+		FileType_Clip     = 1,
+		FileType_Metadata = 2,
+		FileType_LUT      = 3,
+		FileType_Audio    = 5
+	};
+
+	// Mock: the clip path followed by the sidecar files with the clip name which exist on disk
+	size_t FileListCount() const;
+	FileType FileList(size_t fileIdx, std::string & pathOutput) const;
+
+	// Mock only: description of the loaded clip, NULL if no clip is loaded
+	const Mock::ClipDescription * Description() const;
+
+private:
+	Clip(const Clip &) = delete;
+	Clip & operator=(const Clip &) = delete;
+
+	LoadStatus status;
+	bool extendedHighlights;
+	std::shared_ptr<const Mock::ClipDescription> clip;
+	std::string path;
+	std::string rmdPath;
+	std::string timecode;
+};
+
+class AsyncDecompressJob {
+public:
+	AsyncDecompressJob();
+
+	::R3DSDK::Clip * Clip;
+	VideoDecodeMode Mode;
+	void * OutputBuffer;
+	size_t OutputBufferSize;
+	size_t VideoFrameNo;
+	size_t VideoTrackNo;
+	bool AbortDecode;
+
+	typedef void (*AsyncDecompressCallback)(AsyncDecompressJob * item, DecodeStatus decodeStatus);
+	AsyncDecompressCallback Callback;
+	void * PrivateData;
+	Metadata * OutputFrameMetadata;
+};
+
+namespace Mock { class WorkQueue; }
+
+class AsyncDecoder {
+public:
+	AsyncDecoder();
+	~AsyncDecoder();
+
+	DecodeStatus Open(size_t noOfThreads = 0);
+	void Close();
+
+	static size_t ThreadsAvailable();
+	static size_t GetSizeBufferNeeded(const AsyncDecompressJob & job);
+	DecodeStatus DecodeForGpuSdk(AsyncDecompressJob & job);
+
+private:
+	Mock::WorkQueue * queue;
+};
+
+class GpuDecoder {
+public:
+	GpuDecoder();
+	~GpuDecoder();
+
+	DecodeStatus Open();
+	void Close();
+
+	static DecodeStatus DecodeSupportedForClip(const Clip & clip);
+	static size_t GetSizeBufferNeeded(const AsyncDecompressJob & job);
+	DecodeStatus DecodeForGpuSdk(AsyncDecompressJob & job);
+
+private:
+	Mock::WorkQueue * queue;
+};
+
+}
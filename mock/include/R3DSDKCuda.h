@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#pragma once
+
+#include "R3DSDK.h"
+#include "cuda_runtime.h"
+
+namespace R3DSDK {
+
+struct EXT_CUDA_API {
+	void * cudaFree;
+	void * cudaFreeArray;
+	void * cudaFreeHost;
+	void * cudaFreeMipmappedArray;
+	void * cudaHostAlloc;
+	void * cudaMalloc;
+	void * cudaMalloc3D;
+	void * cudaMalloc3DArray;
+	void * cudaMallocArray;
+	void * cudaMallocHost;
+	void * cudaMallocMipmappedArray;
+	void * cudaMallocPitch;
+};
+
+class DebayerCudaJob {
+public:
+	void * raw_host_mem;
+	void * raw_device_mem;
+	size_t output_device_mem_size;
+	void * output_device_mem;
+	VideoDecodeMode mode;
+	const ImageProcessingSettings * imageProcessingSettings;
+	VideoPixelType pixelType;
+
+	void completeAsync();
+	static size_t ResultFrameSize(const DebayerCudaJob * job);
+	static size_t ResultFrameSize(const DebayerCudaJob & job);
+};
+
+class REDCuda {
+public:
+	enum Status {
+		Status_Ok = 0,
+		Status_ErrorProcessing = 1,
+		Status_InvalidJobParameter = 2,
+		Status_InvalidJobParameter_mode = 3,
+		Status_InvalidJobParameter_raw_host_mem = 4,
+		Status_InvalidJobParameter_raw_device_mem = 5,
+		Status_InvalidJobParameter_pixelType = 6,
+		Status_InvalidJobParameter_output_device_mem_size = 7,
+		Status_InvalidJobParameter_output_device_mem = 8,
+		Status_InvalidJobParameter_ColorVersion1 = 9,
+		Status_UnableToUseGPUDevice = 10,
+		Status_UnableToLoadLibrary = 11,
+		Status_ParameterUnsupported = 12,
+		Status_InvalidJobParameter_deviceId = 13
+	};
+
+	REDCuda(EXT_CUDA_API & api);
+	~REDCuda();
+
+	DebayerCudaJob * createDebayerJob();
+	void releaseDebayerJob(DebayerCudaJob * job);
+	Status checkCompatibility(int deviceId, cudaStream_t stream, cudaError_t & err);
+	Status process(int deviceId, cudaStream_t stream, DebayerCudaJob * job, cudaError_t & err);
+	Status processAsync(int deviceId, cudaStream_t stream, DebayerCudaJob * job, cudaError_t & err);
+};
+
+}
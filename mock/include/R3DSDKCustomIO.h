@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#pragma once
+
+#include "R3DSDKDefinitions.h"
+
+namespace R3DSDK {
+
+class IOInterface {
+public:
+	typedef void * Handle;
+	enum FileAccess { IO_READ = 1, IO_WRITE = 2 };
+
+	IOInterface();
+	virtual ~IOInterface();
+
+	virtual Handle Open(const char * utf8Path, FileAccess access) = 0;
+	virtual unsigned long long Filesize(Handle handle) = 0;
+	virtual void Close(Handle handle) = 0;
+	virtual bool Read(void * outputBuffer, size_t bytes, unsigned long long offset, Handle handle) = 0;
+	virtual bool Write(const void * inputBuffer, size_t bytes, Handle handle) = 0;
+	virtual bool CreatePath(const char * utf8Path) = 0;
+};
+
+void SetIoInterface(IOInterface * io);
+void ResetIoInterface();
+
+}
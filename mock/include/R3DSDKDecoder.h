@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#pragma once
+
+#include "R3DSDK.h"
+
+namespace R3DSDK {
+
+struct OpenCLDeviceInfo {
+	void * platform_id;
+	void * device_id;
+	char name[256];
+	char platform_name[256];
+};
+
+struct CudaDeviceInfo {
+	int pci_bus_id;
+	char name[256];
+};
+
+class R3DDecoderOptions {
+public:
+	static R3DStatus CreateOptions(R3DDecoderOptions ** options);
+	static void ReleaseOptions(R3DDecoderOptions * options);
+
+	R3DStatus setScratchFolder(const std::string & folder);
+	R3DStatus setDecompressionThreadCount(size_t count);
+	R3DStatus setConcurrentImageCount(size_t count);
+	R3DStatus setMemoryPoolSize(size_t sizeMBs);
+	R3DStatus setGPUMemoryPoolSize(size_t sizeMBs);
+	R3DStatus setGPUConcurrentFrameCount(size_t count);
+
+	static R3DStatus GetOpenCLDeviceList(std::vector<OpenCLDeviceInfo> & list);
+	static R3DStatus GetCudaDeviceList(std::vector<CudaDeviceInfo> & list);
+	R3DStatus useDevice(const OpenCLDeviceInfo & device);
+	R3DStatus useDevice(const CudaDeviceInfo & device);
+
+private:
+	R3DDecoderOptions();
+	friend class R3DDecoder;
+
+	size_t threads;
+	size_t deviceCount;
+};
+
+class R3DDecodeJob {
+public:
+	const Clip * clip;
+	VideoDecodeMode mode;
+	size_t videoTrackNo;
+	size_t videoFrameNo;
+	void * outputBuffer;
+	size_t outputBufferSize;
+	VideoPixelType pixelType;
+	ImageProcessingSettings * imageProcessingSettings;
+	HdrProcessingSettings * hdrProcessingSettings;
+
+	typedef void (*DecodeCallback)(R3DDecodeJob * item, R3DStatus decodeStatus);
+	DecodeCallback callback;
+	void * privateData;
+	Metadata * outputFrameMetadata;
+
+private:
+	R3DDecodeJob();
+	friend class R3DDecoder;
+};
+
+class R3DDecoder {
+public:
+	static R3DStatus CreateDecoder(R3DDecoderOptions * options, R3DDecoder ** decoder);
+	static void ReleaseDecoder(R3DDecoder * decoder);
+	static R3DStatus CreateDecodeJob(R3DDecodeJob ** job);
+	static void ReleaseDecodeJob(R3DDecodeJob * job);
+
+	R3DStatus decode(R3DDecodeJob * job);
+
+private:
+	R3DDecoder();
+	~R3DDecoder();
+
+	Mock::WorkQueue * queue;
+};
+
+}
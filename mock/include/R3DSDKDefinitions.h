@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Mock R3D SDK, used with the `mock` feature instead of the RED R3D SDK headers.
+// Only the parts of the API used by r3d-rs are declared. Values match R3D SDK 9.1.0.
+
+#pragma once
+
+#include <cstddef>
+#include <cstdint>
+#include <string>
+#include <vector>
+
+namespace R3DSDK {
+
+enum InitializeStatus {
+	ISInitializeOK = 0,
+	ISLibraryNotLoaded = 1,
+	ISR3DSDKLibraryNotFound = 2,
+	ISRedCudaLibraryNotFound = 3,
+	ISRedOpenCLLibraryNotFound = 4,
+	ISR3DDecoderLibraryNotFound = 5,
+	ISLibraryVersionMismatch = 6,
+	ISInvalidR3DSDKLibrary = 7,
+	ISInvalidRedCudaLibrary = 8,
+	ISInvalidRedOpenCLLibrary = 9,
+	ISInvalidR3DDecoderLibrary = 10,
+	ISRedCudaLibraryInitializeFailed = 11,
+	ISRedOpenCLLibraryInitializeFailed = 12,
+	ISR3DDecoderLibraryInitializeFailed = 13,
+	ISR3DSDKLibraryInitializeFailed = 14,
+	ISInvalidPath = 15,
+	ISInternalError = 16,
+	ISRedMetalLibraryNotFound = 17,
+	ISInvalidRedMetalLibrary = 18,
+	ISRedMetalLibraryInitializeFailed = 19,
+	ISMetalNotAvailable = 20
+};
+
+enum LoadStatus {
+	LSClipLoaded = 0,
+	LSPathNotFound = 1,
+	LSFailedToOpenFile = 2,
+	LSNotAnR3DFile = 3,
+	LSClipIsEmpty = 4,
+	LSOutOfMemory = 5,
+	LSUnknownError = 6,
+	LSNoClipOpen = 7,
+	LSNotInitialized = 8
+};
+
+enum CreateStatus {
+	CSStarted = 0,
+	CSFrameAdded = 1,
+	CSDone = 2,
+	CSOutOfMemory = 3,
+	CSRequestOutOfRange = 4,
+	CSInvalidParameter = 5,
+	CSFailedToGetSourceFrame = 6,
+	CSFailedToCreateDestination = 7,
+	CSFailedToWriteToDestination = 8,
+	CSUnknownError = 9,
+	CSInvalidSourceClip = 10,
+	CSInvalidPath = 11,
+	CSFailedToGetSourceAudio = 12,
+	CSOutOfOrder = 13,
+	CSInvalidStream = 14
+};
+
+enum DecodeStatus {
+	DSDecodeOK = 0,
+	DSOutputBufferInvalid = 1,
+	DSRequestOutOfRange = 3,
+	DSInvalidParameter = 4,
+	DSIsDroppedFrame = 5,
+	DSDecodeFailed = 6,
+	DSOutOfMemory = 7,
+	DSUnknownError = 8,
+	DSNoClipOpen = 9,
+	DSCannotReadFromFile = 10,
+	DSInvalidPixelType = 11,
+	DSNotAnHDRxClip = 12,
+	DSCancelled = 13,
+	DSUnsupportedClipFormat = 14,
+	DSParameterUnsupported = 15,
+	DSDecoderNotOpened = 16
+};
+
+enum R3DStatus {
+	R3DStatus_Ok = 0,
+	R3DStatus_ErrorProcessing = 1,
+	R3DStatus_InvalidJobParameter = 2,
+	R3DStatus_InvalidJobParameter_mode = 3,
+	R3DStatus_InvalidJobParameter_raw_host_mem = 4,
+	R3DStatus_InvalidJobParameter_raw_device_mem = 5,
+	R3DStatus_InvalidJobParameter_pixelType = 6,
+	R3DStatus_InvalidJobParameter_output_device_mem_size = 7,
+	R3DStatus_InvalidJobParameter_output_device_mem = 8,
+	R3DStatus_InvalidJobParameter_ColorVersion1 = 9,
+	R3DStatus_InvalidJobParameter_clip = 10,
+	R3DStatus_UnableToUseGPUDevice = 11,
+	R3DStatus_NoGPUDeviceSpecified = 12,
+	R3DStatus_UnableToLoadLibrary = 13,
+	R3DStatus_ParameterUnsupported = 14
+};
+
+enum VideoDecodeMode {
+	DECODE_FULL_RES_PREMIUM    = 0x44465250,
+	DECODE_HALF_RES_PREMIUM    = 0x44485250,
+	DECODE_HALF_RES_GOOD       = 0x44485247,
+	DECODE_QUARTER_RES_GOOD    = 0x44515247,
+	DECODE_EIGHT_RES_GOOD      = 0x44455247,
+	DECODE_SIXTEENTH_RES_GOOD  = 0x44535247
+};
+
+enum VideoPixelType {
+	PixelType_16Bit_RGB_Interleaved       = 0x52423649,
+	PixelType_HalfFloat_RGB_Interleaved   = 0x52424846,
+	PixelType_HalfFloat_RGB_ACES_Int      = 0x52424841,
+	PixelType_16Bit_RGB_Planar            = 0x52423650,
+	PixelType_8Bit_BGR_Interleaved        = 0x42475238,
+	PixelType_8Bit_BGRA_Interleaved       = 0x42524138,
+	PixelType_10Bit_DPX_MethodB           = 0x44503042
+};
+
+enum ImageGammaCurve {
+	ImageGammaLinear = 0,
+	ImageGammaRec709 = 1,
+	ImageGammaREDspace = 2,
+	ImageGammaREDlog = 3,
+	ImageGammaPDlog685 = 4,
+	ImageGammaPDlog985 = 5,
+	ImageGammaCustomPDlog = 6,
+	ImageGammaREDgamma = 7,
+	ImageGammaSRGB = 8,
+	ImageGammaREDlogFilm = 9,
+	ImageGammaREDgamma2 = 10,
+	ImageGammaREDgamma3 = 11,
+	ImageGammaREDgamma4 = 12,
+	ImageGammaHDR2084 = 14,
+	ImageGammaBT1886 = 15,
+	ImageGammaLog3G12 = 16,
+	ImageGammaLog3G10 = 18,
+	ImageGammaHybridLogGamma = 19,
+	ImageGamma2_2 = 20,
+	ImageGamma2_6 = 21
+};
+
+enum ImageColorSpace {
+	ImageColorCameraRGB = 0,
+	ImageColorRec709 = 1,
+	ImageColorREDspace = 2,
+	ImageColorREDcolor = 3,
+	ImageColorSRGB = 4,
+	ImageColorAdobe1998 = 5,
+	ImageColorREDcolor2 = 6,
+	ImageColorREDcolor3 = 8,
+	ImageColorDRAGONcolor = 9,
+	ImageColorREDcolor4 = 11,
+	ImageColorDRAGONcolor2 = 12,
+	ImageColorRec2020 = 13,
+	ImageColorREDWideGamutRGB = 15,
+	ImageColorDCIP3 = 16,
+	ImageColorProPhotoRGB = 17,
+	ImageColorDCIP3D65 = 18
+};
+
+enum ImageDetail { ImageDetailLow = 0, ImageDetailMedium = 1, ImageDetailHigh = 2 };
+enum ImageOLPFCompensation { ImageOLPFCompOff = 0, ImageOLPFCompLow = 1, ImageOLPFCompMedium = 2, ImageOLPFCompHigh = 3 };
+enum ImageDenoise { ImageDenoiseOff = 0, ImageDenoiseMinimum = 1, ImageDenoiseMilder = 2, ImageDenoiseMild = 3, ImageDenoiseMedium = 4, ImageDenoiseStrong = 5, ImageDenoiseMaximum = 6 };
+enum FlashingPixelAdjust { FlashingPixelAdjustOff = 0, FlashingPixelAdjustMild = 1179667780, FlashingPixelAdjustMedium = 1179667789, FlashingPixelAdjustStrong = 1179669319 };
+enum ColorVersion { ColorVersion1 = 1, ColorVersion2 = 2, ColorVersion3 = 3, ColorVersionBC = 4 };
+enum ImagePipeline { Primary_Development_Only = 0, Full_Graded = 1 };
+enum RollOff { ROLLOFF_NONE = 0, ROLLOFF_HARD = 1, ROLLOFF_MEDIUM = 2, ROLLOFF_SOFT = 3, ROLLOFF_VERY_SOFT = 4 };
+enum ToneMap { ToneMap_Low = 0, ToneMap_Medium = 1, ToneMap_High = 2, ToneMap_None = 3 };
+enum HdrBlendAlgorithm { HDRxSimpleBlend = 0x48445253, HDRxMagicMotion = 0x4844524D };
+enum HdrMode { HDR_USE_TRACKNO = 0, HDR_DO_BLEND = 1 };
+enum MetadataType { MetadataTypeInvalid = 0, MetadataTypeInt = 1, MetadataTypeString = 2, MetadataTypeFloat = 3 };
+enum FileId { FileId_Unknown = 0, FileId_R3D = 1, FileId_NEV_NRAW = 3, FileId_R3D_NE = 4 };
+
+typedef void * Handle3DLut;
+
+}
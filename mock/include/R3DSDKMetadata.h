@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#pragma once
+
+#include "R3DSDKDefinitions.h"
+
+namespace R3DSDK {
+
+namespace Mock { struct MetadataItems; }
+
+class Metadata {
+public:
+	Metadata();
+	Metadata(const Metadata & other);
+	Metadata & operator=(const Metadata & other);
+	~Metadata();
+
+	size_t MetadataCount() const;
+	bool MetadataExists(const char * key) const;
+	MetadataType MetadataItemType(const char * key) const;
+	unsigned int MetadataItemAsInt(const char * key) const;
+	std::string MetadataItemAsString(const char * key) const;
+	float MetadataItemAsFloat(const char * key) const;
+	std::string MetadataItemKey(size_t index) const;
+
+	Mock::MetadataItems * items;
+};
+
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#pragma once
+
+#include "R3DSDK.h"
+
+#ifdef __OBJC__
+#import <Metal/Metal.h>
+
+namespace R3DSDK {
+
+struct EXT_METAL_API { };
+
+class DebayerMetalJob {
+public:
+	void * raw_host_mem;
+	id<MTLBuffer> raw_device_mem;
+	size_t output_device_mem_size;
+	id<MTLBuffer> output_device_mem;
+	id<MTLTexture> output_device_image;
+	VideoDecodeMode mode;
+	const ImageProcessingSettings * imageProcessingSettings;
+	VideoPixelType pixelType;
+	bool batchMode;
+
+	void completeAsync();
+	static size_t ResultFrameSize(const DebayerMetalJob & job);
+};
+
+class REDMetal {
+public:
+	enum Status {
+		Status_Ok = 0,
+		Status_ErrorProcessing = 1,
+		Status_InvalidJobParameter = 2,
+		Status_InvalidJobParameter_mode = 3,
+		Status_InvalidJobParameter_raw_host_mem = 4,
+		Status_InvalidJobParameter_raw_device_mem = 5,
+		Status_InvalidJobParameter_pixelType = 6,
+		Status_InvalidJobParameter_output_device_mem_size = 7,
+		Status_InvalidJobParameter_output_device_mem = 8,
+		Status_InvalidJobParameter_ColorVersion1 = 9,
+		Status_UnableToUseGPUDevice = 10,
+		Status_UnableToLoadLibrary = 11,
+		Status_ParameterUnsupported = 12,
+		Status_InvalidAPIObject = 13
+	};
+
+	REDMetal(EXT_METAL_API & api);
+	~REDMetal();
+
+	DebayerMetalJob * createDebayerJob();
+	void releaseDebayerJob(DebayerMetalJob * job);
+	Status checkCompatibility(id<MTLCommandQueue> queue, int & err);
+	Status process(id<MTLCommandQueue> queue, DebayerMetalJob * job, int & err);
+	Status processAsync(id<MTLCommandQueue> queue, DebayerMetalJob * job, int & err);
+	Status flush(id<MTLCommandQueue> queue, int & err);
+};
+
+}
+#endif
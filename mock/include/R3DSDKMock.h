@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Configuration of the synthetic clips served by the mock SDK. Not part of the R3D SDK API.
+
+#pragma once
+
+#include "R3DSDK.h"
+
+namespace R3DSDK {
+namespace Mock {
+
+struct MetadataItem {
+	std::string key;
+	MetadataType type;
+	unsigned int intValue;
+	float floatValue;
+	std::string stringValue;
+};
+
+struct MetadataItems {
+	std::vector<MetadataItem> items;
+
+	const MetadataItem * Find(const char * key) const;
+	void Set(const MetadataItem & item);
+};
+
+enum FramePattern {
+	// R = horizontal ramp, G = vertical ramp, B = frame number / (frame count - 1)
+	PatternGradient = 0,
+	// Constant color
+	PatternSolid = 1,
+	// Black & white squares moving one pixel to the right every frame
+	PatternCheckerboard = 2
+};
+
+struct ClipDescription {
+	ClipDescription();
+
+	size_t width;
+	size_t height;
+	size_t frameCount;
+	size_t trackCount;
+	float framerate;
+	uint8_t uuid[16];
+	MetadataItems metadata;
+	MetadataItems frameMetadata;
+	std::vector<IMUSample> imuSamples;
+	std::vector<size_t> droppedFrames;
+	FramePattern pattern;
+	float color[3];
+	size_t checkerSize;
+	// Added to every decode to simulate slow decodes
+	unsigned int decodeDelayMs;
+};
+
+// Clips are looked up by the exact path passed to Clip::LoadFrom().
+// Registering a path again replaces the previous description, clips which are already open keep the old one.
+void RegisterClip(const char * path, const ClipDescription & clip);
+bool UnregisterClip(const char * path);
+
+}
+}
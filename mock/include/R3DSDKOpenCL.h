@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#pragma once
+
+#include "R3DSDK.h"
+#include "CL/opencl.h"
+
+namespace R3DSDK {
+
+struct EXT_OCLAPI_1_1 {
+	void * clSetKernelArg;
+	void * clFlush;
+	void * clFinish;
+	void * clEnqueueCopyImage;
+	void * clCreateContext;
+	void * clCreateCommandQueue;
+	void * clCreateSampler;
+	void * clCreateKernel;
+	void * clCreateBuffer;
+	void * clCreateProgramWithSource;
+	void * clCreateProgramWithBinary;
+	void * clReleaseEvent;
+	void * clReleaseSampler;
+	void * clReleaseKernel;
+	void * clReleaseMemObject;
+	void * clReleaseProgram;
+	void * clReleaseContext;
+	void * clReleaseCommandQueue;
+	void * clGetPlatformInfo;
+	void * clGetDeviceIDs;
+	void * clGetPlatformIDs;
+	void * clGetDeviceInfo;
+	void * clGetContextInfo;
+	void * clGetImageInfo;
+	void * clGetProgramBuildInfo;
+	void * clGetProgramInfo;
+	void * clGetKernelWorkGroupInfo;
+	void * clBuildProgram;
+	void * clEnqueueWriteBuffer;
+	void * clEnqueueReadBuffer;
+	void * clEnqueueCopyBuffer;
+	void * clEnqueueCopyBufferToImage;
+	void * clEnqueueWriteImage;
+	void * clEnqueueNDRangeKernel;
+	void * clEnqueueMapBuffer;
+	void * clEnqueueUnmapMemObject;
+	void * clWaitForEvents;
+	void * clEnqueueBarrier;
+	void * clEnqueueMarker;
+	void * clCreateImage2D;
+	void * clSetMemObjectDestructorCallback;
+	void * clCreateSubBuffer;
+	void * clGetMemObjectInfo;
+	void * clCreateImage3D;
+};
+
+class DebayerOpenCLJob {
+public:
+	void * raw_host_mem;
+	cl_mem raw_device_mem;
+	size_t output_device_mem_size;
+	cl_mem output_device_mem;
+	VideoDecodeMode mode;
+	const ImageProcessingSettings * imageProcessingSettings;
+	VideoPixelType pixelType;
+
+	void completeAsync();
+	static size_t ResultFrameSize(const DebayerOpenCLJob & job);
+};
+
+class REDCL {
+public:
+	enum Status {
+		Status_Ok = 0,
+		Status_ErrorProcessing = 1,
+		Status_InvalidJobParameter = 2,
+		Status_InvalidJobParameter_mode = 3,
+		Status_InvalidJobParameter_raw_host_mem = 4,
+		Status_InvalidJobParameter_raw_device_mem = 5,
+		Status_InvalidJobParameter_pixelType = 6,
+		Status_InvalidJobParameter_output_device_mem_size = 7,
+		Status_InvalidJobParameter_output_device_mem = 8,
+		Status_InvalidJobParameter_ColorVersion1 = 9,
+		Status_UnableToUseGPUDevice = 10,
+		Status_UnableToLoadLibrary = 11,
+		Status_ParameterUnsupported = 12,
+		Status_InvalidAPIObject = 13
+	};
+
+	REDCL(EXT_OCLAPI_1_1 & api, const char * folder);
+	~REDCL();
+
+	DebayerOpenCLJob * createDebayerJob();
+	void releaseDebayerJob(DebayerOpenCLJob * job);
+	Status checkCompatibility(cl_context ctx, cl_command_queue queue, cl_int & err);
+	Status process(cl_context ctx, cl_command_queue queue, DebayerOpenCLJob * job, cl_int & err);
+	Status processAsync(cl_context ctx, cl_command_queue queue, DebayerOpenCLJob * job, cl_int & err);
+};
+
+}
@@ -0,0 +1,1231 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Mock implementation of the R3D SDK, compiled instead of linking the RED libraries when the `mock` feature is enabled.
+//
+// Clips are never read from disk, Clip::LoadFrom() looks the path up in the registry filled by Mock::RegisterClip()
+// and every decode renders a test pattern. Image processing only applies exposure, RGB gains and saturation,
+// so tests can check that settings reach the decoder. There is no GPU: R3DDecoder decodes on CPU worker threads
+// and the REDCuda / REDCL / REDMetal debayer classes validate their jobs and then report Status_UnableToUseGPUDevice.
+
+#include "R3DSDK.h"
+#include "R3DSDKDecoder.h"
+#include "R3DSDKCustomIO.h"
+#include "R3DSDKMock.h"
+#ifndef __APPLE__
+#include "R3DSDKCuda.h"
+#endif
+#include "R3DSDKOpenCL.h"
+#if defined(__APPLE__) && defined(__OBJC__)
+#include "R3DSDKMetal.h"
+#endif
+
+#include <algorithm>
+#include <atomic>
+#include <chrono>
+#include <cmath>
+#include <condition_variable>
+#include <cstdio>
+#include <cstdlib>
+#include <cstring>
+#include <deque>
+#include <functional>
+#include <map>
+#include <mutex>
+#include <thread>
+
+namespace R3DSDK {
+
+// ******************************
+// SDK STATE
+// ******************************
+
+static std::atomic<bool> g_initialized(false);
+static std::atomic<unsigned int> g_components(0);
+static std::atomic<IOInterface *> g_io(nullptr);
+
+static const unsigned int OPTION_RED_DECODER = 0x04;
+
+// Set while a decode callback runs on this thread. Releasing a job inside its own callback or finalizing the SDK
+// while a callback still runs is not allowed by the SDK, the mock aborts instead so the tests catch it.
+static thread_local bool t_inCallback = false;
+static std::atomic<int> g_runningCallbacks(0);
+
+struct CallbackScope {
+	CallbackScope() { t_inCallback = true; g_runningCallbacks++; }
+	~CallbackScope() { g_runningCallbacks--; t_inCallback = false; }
+};
+
+static void CheckNotInCallback(const char * function) {
+	if (t_inCallback) {
+		fprintf(stderr, "R3D SDK mock: %s called inside a decode callback\n", function);
+		abort();
+	}
+}
+
+static std::mutex & RegistryMutex() {
+	static std::mutex mutex;
+	return mutex;
+}
+static std::map<std::string, std::shared_ptr<const Mock::ClipDescription>> & Registry() {
+	static std::map<std::string, std::shared_ptr<const Mock::ClipDescription>> registry;
+	return registry;
+}
+
+InitializeStatus InitializeSdk(const char * libraryPath, unsigned int optionalComponents) {
+	if (libraryPath == nullptr || libraryPath[0] == '\0')
+		return ISInvalidPath;
+	g_components = optionalComponents;
+	g_initialized = true;
+	return ISInitializeOK;
+}
+
+void FinalizeSdk() {
+	if (g_runningCallbacks > 0) {
+		fprintf(stderr, "R3D SDK mock: FinalizeSdk called while a decode callback is running\n");
+		abort();
+	}
+	g_initialized = false;
+	g_components = 0;
+}
+
+const char * GetSdkVersion() {
+	// Mock "libraries" are reported for the loaded components, so the version parsing can be exercised
+	if (!g_initialized)
+		return "R3D SDK v9.1.0 (mock backend, no RED libraries loaded)";
+	if (g_components & OPTION_RED_DECODER)
+		return "R3D SDK v9.1.0 (mock backend), REDR3D v9.1.0 (mock), R3DDecoder v9.1.0 (mock)";
+	return "R3D SDK v9.1.0 (mock backend), REDR3D v9.1.0 (mock)";
+}
+
+const char * Get3DLutPath(Handle3DLut handle) {
+	(void)handle;
+	return nullptr;
+}
+
+FileId IdentifyFile(const char * path) {
+	if (path == nullptr)
+		return FileId_Unknown;
+	std::lock_guard<std::mutex> lock(RegistryMutex());
+	return Registry().count(path) ? FileId_R3D : FileId_Unknown;
+}
+
+// ******************************
+// IMAGE PROCESSING SETTINGS
+// ******************************
+
+const float ImageProcessingLimits::KelvinMin = 1700.0f, ImageProcessingLimits::KelvinMax = 50000.0f, ImageProcessingLimits::KelvinDefault = 5600.0f;
+const float ImageProcessingLimits::TintMin = -100.0f, ImageProcessingLimits::TintMax = 100.0f, ImageProcessingLimits::TintDefault = 0.0f;
+const float ImageProcessingLimits::ExposureMin = -7.0f, ImageProcessingLimits::ExposureMax = 7.0f, ImageProcessingLimits::ExposureDefault = 0.0f;
+const float ImageProcessingLimits::GainsMin = 0.0f, ImageProcessingLimits::GainsMax = 10.0f, ImageProcessingLimits::GainsDefault = 1.0f;
+const float ImageProcessingLimits::SaturationMin = 0.0f, ImageProcessingLimits::SaturationMax = 4.0f, ImageProcessingLimits::SaturationDefault = 1.0f;
+const float ImageProcessingLimits::ContrastMin = -1.0f, ImageProcessingLimits::ContrastMax = 1.0f, ImageProcessingLimits::ContrastDefault = 0.0f;
+const float ImageProcessingLimits::BrightnessMin = -10.0f, ImageProcessingLimits::BrightnessMax = 10.0f, ImageProcessingLimits::BrightnessDefault = 0.0f;
+const float ImageProcessingLimits::DRXMin = 0.0f, ImageProcessingLimits::DRXMax = 1.0f, ImageProcessingLimits::DRXDefault = 0.0f;
+const float ImageProcessingLimits::ShadowMin = -2.0f, ImageProcessingLimits::ShadowMax = 2.0f, ImageProcessingLimits::ShadowDefault = 0.0f;
+const float ImageProcessingLimits::FLUTMin = -8.0f, ImageProcessingLimits::FLUTMax = 8.0f, ImageProcessingLimits::FLUTDefault = 0.0f;
+const float ImageProcessingLimits::LggLiftMin = -1.0f, ImageProcessingLimits::LggLiftMax = 1.0f, ImageProcessingLimits::LggLiftDefault = 0.0f;
+const float ImageProcessingLimits::LggGammaMin = 0.0f, ImageProcessingLimits::LggGammaMax = 4.0f, ImageProcessingLimits::LggGammaDefault = 1.0f;
+const float ImageProcessingLimits::LggGainMin = 0.0f, ImageProcessingLimits::LggGainMax = 2.0f, ImageProcessingLimits::LggGainDefault = 1.0f;
+
+const size_t ImageProcessingLimits::ISOList[] = { 250, 320, 400, 500, 640, 800, 1000, 1280, 1600, 2000, 2560, 3200, 4000, 5120, 6400, 8000, 10240, 12800 };
+const size_t ImageProcessingLimits::ISOCount = sizeof(ISOList) / sizeof(ISOList[0]);
+const size_t ImageProcessingLimits::ISODefault = 800;
+
+const ImageGammaCurve ImageProcessingLimits::GammaCurveMap[] = {
+	ImageGammaLinear, ImageGammaSRGB, ImageGammaHDR2084, ImageGammaBT1886, ImageGammaLog3G12, ImageGammaLog3G10,
+	ImageGammaREDlogFilm, ImageGammaHybridLogGamma, ImageGamma2_2, ImageGamma2_6, ImageGammaRec709, ImageGammaREDgamma4,
+	ImageGammaPDlog685, ImageGammaPDlog985, ImageGammaCustomPDlog, ImageGammaREDspace, ImageGammaREDlog,
+	ImageGammaREDgamma, ImageGammaREDgamma2, ImageGammaREDgamma3
+};
+const char * ImageProcessingLimits::GammaCurveLabels[] = {
+	"Linear Light", "sRGB", "ST 2084", "BT.1886", "Log3G12", "Log3G10",
+	"REDlogFilm", "Hybrid Log-Gamma", "Gamma 2.2", "Gamma 2.6", "Rec.709", "REDgamma4",
+	"PDlog 685", "PDlog 985", "Custom PDlog", "REDspace", "REDlog",
+	"REDgamma", "REDgamma2", "REDgamma3"
+};
+const size_t ImageProcessingLimits::GammaCurveCount = sizeof(GammaCurveMap) / sizeof(GammaCurveMap[0]);
+const ImageGammaCurve ImageProcessingLimits::GammaCurveDefault = ImageGammaBT1886;
+
+const ImageColorSpace ImageProcessingLimits::ColorSpaceMap[] = {
+	ImageColorREDWideGamutRGB, ImageColorRec2020, ImageColorRec709, ImageColorSRGB, ImageColorAdobe1998, ImageColorDCIP3,
+	ImageColorProPhotoRGB, ImageColorDCIP3D65, ImageColorDRAGONcolor2, ImageColorREDcolor4, ImageColorCameraRGB,
+	ImageColorREDspace, ImageColorREDcolor, ImageColorREDcolor2, ImageColorREDcolor3, ImageColorDRAGONcolor
+};
+const char * ImageProcessingLimits::ColorSpaceLabels[] = {
+	"REDWideGamutRGB", "Rec.2020", "Rec.709", "sRGB", "Adobe1998", "DCI-P3",
+	"ProPhoto RGB", "DCI-P3 D65", "DRAGONcolor2", "REDcolor4", "Camera RGB",
+	"REDspace", "REDcolor", "REDcolor2", "REDcolor3", "DRAGONcolor"
+};
+const size_t ImageProcessingLimits::ColorSpaceCount = sizeof(ColorSpaceMap) / sizeof(ColorSpaceMap[0]);
+const ImageColorSpace ImageProcessingLimits::ColorSpaceDefault = ImageColorRec709;
+
+const size_t ImageProcessingLimits::CustomPDLogBlackPointMin = 0, ImageProcessingLimits::CustomPDLogBlackPointMax = 511, ImageProcessingLimits::CustomPDLogBlackPointDefault = 95;
+const size_t ImageProcessingLimits::CustomPDLogWhitePointMin = 512, ImageProcessingLimits::CustomPDLogWhitePointMax = 1023, ImageProcessingLimits::CustomPDLogWhitePointDefault = 685;
+const float ImageProcessingLimits::CustomPDLogGammaMin = 0.2f, ImageProcessingLimits::CustomPDLogGammaMax = 1.0f, ImageProcessingLimits::CustomPDLogGammaDefault = 0.6f;
+
+const ImagePipeline ImageProcessingLimits::ImagePipelineModeMap[] = { Full_Graded, Primary_Development_Only };
+const char * ImageProcessingLimits::ImagePipelineModeLabels[] = { "Full Graded Pipeline", "Primary Development Only" };
+const size_t ImageProcessingLimits::ImagePipelineModeCount = sizeof(ImagePipelineModeMap) / sizeof(ImagePipelineModeMap[0]);
+const ImagePipeline ImageProcessingLimits::ImagePipelineModeDefault = Full_Graded;
+
+const RollOff ImageProcessingLimits::HighlightRollOffMap[] = { ROLLOFF_NONE, ROLLOFF_HARD, ROLLOFF_MEDIUM, ROLLOFF_SOFT, ROLLOFF_VERY_SOFT };
+const char * ImageProcessingLimits::HighlightRollOffLabels[] = { "None", "Hard", "Medium", "Soft", "Very Soft" };
+const size_t ImageProcessingLimits::HighlightRollOffCount = sizeof(HighlightRollOffMap) / sizeof(HighlightRollOffMap[0]);
+const RollOff ImageProcessingLimits::HighlightRollOffDefault = ROLLOFF_MEDIUM;
+
+const ToneMap ImageProcessingLimits::OutputToneMapMap[] = { ToneMap_Low, ToneMap_Medium, ToneMap_High, ToneMap_None };
+const char * ImageProcessingLimits::OutputToneMapLabels[] = { "Low", "Medium", "High", "None" };
+const size_t ImageProcessingLimits::OutputToneMapCount = sizeof(OutputToneMapMap) / sizeof(OutputToneMapMap[0]);
+const ToneMap ImageProcessingLimits::OutputToneMapDefault = ToneMap_Medium;
+
+const float ImageProcessingLimits::ExposureAdjustMin = -4.0f, ImageProcessingLimits::ExposureAdjustMax = 4.0f, ImageProcessingLimits::ExposureAdjustDefault = 0.0f;
+const unsigned int ImageProcessingLimits::HdrPeakNitsMin = 1000, ImageProcessingLimits::HdrPeakNitsMax = 10000, ImageProcessingLimits::HdrPeakNitsDefault = 1000;
+const float ImageProcessingLimits::CdlSlopeMin = 0.0f, ImageProcessingLimits::CdlSlopeMax = 10.0f, ImageProcessingLimits::CdlSlopeDefault = 1.0f;
+const float ImageProcessingLimits::CdlOffsetMin = -10.0f, ImageProcessingLimits::CdlOffsetMax = 10.0f, ImageProcessingLimits::CdlOffsetDefault = 0.0f;
+const float ImageProcessingLimits::CdlPowerMin = 0.0f, ImageProcessingLimits::CdlPowerMax = 10.0f, ImageProcessingLimits::CdlPowerDefault = 1.0f;
+const float ImageProcessingLimits::CdlSaturationMin = 0.0f, ImageProcessingLimits::CdlSaturationMax = 10.0f, ImageProcessingLimits::CdlSaturationDefault = 1.0f;
+
+static void DefaultUserCurve(float * curve) {
+	static const float points[10] = { 0.0f, 0.0f, 0.25f, 0.25f, 0.5f, 0.5f, 0.75f, 0.75f, 1.0f, 1.0f };
+	memcpy(curve, points, sizeof(points));
+}
+
+ImageProcessingSettings::ImageProcessingSettings() {
+	typedef ImageProcessingLimits L;
+	Version = ColorVersion3;
+	Kelvin = L::KelvinDefault;
+	Tint = L::TintDefault;
+	ExposureCompensation = L::ExposureDefault;
+	GainRed = GainGreen = GainBlue = L::GainsDefault;
+	Saturation = L::SaturationDefault;
+	Contrast = L::ContrastDefault;
+	Brightness = L::BrightnessDefault;
+	DRX = L::DRXDefault;
+	ISO = L::ISODefault;
+	GammaCurve = L::GammaCurveDefault;
+	ColorSpace = L::ColorSpaceDefault;
+	CustomPDLogBlackPoint = L::CustomPDLogBlackPointDefault;
+	CustomPDLogWhitePoint = L::CustomPDLogWhitePointDefault;
+	CustomPDLogGamma = L::CustomPDLogGammaDefault;
+	DefaultUserCurve(UserCurve);
+	DefaultUserCurve(UserCurveRed);
+	DefaultUserCurve(UserCurveGreen);
+	DefaultUserCurve(UserCurveBlue);
+	Detail = ImageDetailHigh;
+	OLPFCompensation = ImageOLPFCompOff;
+	Denoise = ImageDenoiseOff;
+	DEB = false;
+	FlashingPixelAdjustment = FlashingPixelAdjustOff;
+	Shadow = L::ShadowDefault;
+	FLUT = L::FLUTDefault;
+	LggRed = LggGreen = LggBlue = LiftGammaGain { L::LggLiftDefault, L::LggGammaDefault, L::LggGainDefault };
+	ImagePipelineMode = L::ImagePipelineModeDefault;
+	ExposureAdjust = L::ExposureAdjustDefault;
+	CdlRed = CdlGreen = CdlBlue = SlopeOffsetPower { L::CdlSlopeDefault, L::CdlOffsetDefault, L::CdlPowerDefault };
+	CdlSaturation = L::CdlSaturationDefault;
+	CdlEnabled = false;
+	Lut3D = nullptr;
+	Lut3DEnabled = false;
+	OutputToneMap = L::OutputToneMapDefault;
+	HighlightRollOff = L::HighlightRollOffDefault;
+	HdrPeakNits = L::HdrPeakNitsDefault;
+	MasklineAdjust = false;
+}
+
+template <typename T> static void Clamp(T & v, T min, T max) { v = std::min(std::max(v, min), max); }
+template <typename T> static void CheckEnum(T & v, const T * map, size_t count, T def) {
+	if (std::find(map, map + count, v) == map + count)
+		v = def;
+}
+
+void ImageProcessingSettings::CheckBounds() {
+	typedef ImageProcessingLimits L;
+	Clamp(Kelvin, L::KelvinMin, L::KelvinMax);
+	Clamp(Tint, L::TintMin, L::TintMax);
+	Clamp(ExposureCompensation, L::ExposureMin, L::ExposureMax);
+	Clamp(GainRed, L::GainsMin, L::GainsMax);
+	Clamp(GainGreen, L::GainsMin, L::GainsMax);
+	Clamp(GainBlue, L::GainsMin, L::GainsMax);
+	Clamp(Saturation, L::SaturationMin, L::SaturationMax);
+	Clamp(Contrast, L::ContrastMin, L::ContrastMax);
+	Clamp(Brightness, L::BrightnessMin, L::BrightnessMax);
+	Clamp(DRX, L::DRXMin, L::DRXMax);
+	Clamp(ISO, L::ISOList[0], L::ISOList[L::ISOCount - 1]);
+	CheckEnum(GammaCurve, L::GammaCurveMap, L::GammaCurveCount, L::GammaCurveDefault);
+	CheckEnum(ColorSpace, L::ColorSpaceMap, L::ColorSpaceCount, L::ColorSpaceDefault);
+	Clamp(CustomPDLogBlackPoint, L::CustomPDLogBlackPointMin, L::CustomPDLogBlackPointMax);
+	Clamp(CustomPDLogWhitePoint, L::CustomPDLogWhitePointMin, L::CustomPDLogWhitePointMax);
+	Clamp(CustomPDLogGamma, L::CustomPDLogGammaMin, L::CustomPDLogGammaMax);
+	float * curves[4] = { UserCurve, UserCurveRed, UserCurveGreen, UserCurveBlue };
+	for (float * curve : curves)
+		for (size_t i = 0; i < 10; i++)
+			Clamp(curve[i], 0.0f, 1.0f);
+	Clamp(Shadow, L::ShadowMin, L::ShadowMax);
+	Clamp(FLUT, L::FLUTMin, L::FLUTMax);
+	LiftGammaGain * lggs[3] = { &LggRed, &LggGreen, &LggBlue };
+	for (LiftGammaGain * lgg : lggs) {
+		Clamp(lgg->Lift, L::LggLiftMin, L::LggLiftMax);
+		Clamp(lgg->Gamma, L::LggGammaMin, L::LggGammaMax);
+		Clamp(lgg->Gain, L::LggGainMin, L::LggGainMax);
+	}
+	CheckEnum(ImagePipelineMode, L::ImagePipelineModeMap, L::ImagePipelineModeCount, L::ImagePipelineModeDefault);
+	Clamp(ExposureAdjust, L::ExposureAdjustMin, L::ExposureAdjustMax);
+	SlopeOffsetPower * cdls[3] = { &CdlRed, &CdlGreen, &CdlBlue };
+	for (SlopeOffsetPower * cdl : cdls) {
+		Clamp(cdl->Slope, L::CdlSlopeMin, L::CdlSlopeMax);
+		Clamp(cdl->Offset, L::CdlOffsetMin, L::CdlOffsetMax);
+		Clamp(cdl->Power, L::CdlPowerMin, L::CdlPowerMax);
+	}
+	Clamp(CdlSaturation, L::CdlSaturationMin, L::CdlSaturationMax);
+	CheckEnum(OutputToneMap, L::OutputToneMapMap, L::OutputToneMapCount, L::OutputToneMapDefault);
+	CheckEnum(HighlightRollOff, L::HighlightRollOffMap, L::HighlightRollOffCount, L::HighlightRollOffDefault);
+	Clamp(HdrPeakNits, L::HdrPeakNitsMin, L::HdrPeakNitsMax);
+}
+
+HdrProcessingSettings::HdrProcessingSettings() : BlendAlgorithm(HDRxSimpleBlend), Bias(0.0f) { }
+
+void HdrProcessingSettings::CheckBounds() {
+	if (BlendAlgorithm != HDRxSimpleBlend && BlendAlgorithm != HDRxMagicMotion)
+		BlendAlgorithm = HDRxSimpleBlend;
+	Clamp(Bias, -1.0f, 1.0f);
+}
+
+// ******************************
+// METADATA
+// ******************************
+
+namespace Mock {
+
+const MetadataItem * MetadataItems::Find(const char * key) const {
+	if (key == nullptr)
+		return nullptr;
+	for (const MetadataItem & item : items)
+		if (item.key == key)
+			return &item;
+	return nullptr;
+}
+
+void MetadataItems::Set(const MetadataItem & item) {
+	for (MetadataItem & existing : items) {
+		if (existing.key == item.key) {
+			existing = item;
+			return;
+		}
+	}
+	items.push_back(item);
+}
+
+}
+
+Metadata::Metadata() : items(new Mock::MetadataItems()) { }
+Metadata::Metadata(const Metadata & other) : items(new Mock::MetadataItems(*other.items)) { }
+Metadata & Metadata::operator=(const Metadata & other) {
+	*items = *other.items;
+	return *this;
+}
+Metadata::~Metadata() {
+	CheckNotInCallback("Metadata::~Metadata");
+	delete items;
+}
+
+static MetadataType ItemType(const Mock::MetadataItem * item) { return item ? item->type : MetadataTypeInvalid; }
+static unsigned int ItemAsInt(const Mock::MetadataItem * item) { return item && item->type == MetadataTypeInt ? item->intValue : 0; }
+static float ItemAsFloat(const Mock::MetadataItem * item) { return item && item->type == MetadataTypeFloat ? item->floatValue : 0.0f; }
+static std::string ItemAsString(const Mock::MetadataItem * item) {
+	if (item == nullptr)
+		return std::string();
+	switch (item->type) {
+		case MetadataTypeString: return item->stringValue;
+		case MetadataTypeInt:    return std::to_string(item->intValue);
+		case MetadataTypeFloat:  return std::to_string(item->floatValue);
+		default:                 return std::string();
+	}
+}
+static std::string ItemKey(const Mock::MetadataItems & items, size_t index) {
+	return index < items.items.size() ? items.items[index].key : std::string();
+}
+
+size_t Metadata::MetadataCount() const { return items->items.size(); }
+bool Metadata::MetadataExists(const char * key) const { return items->Find(key) != nullptr; }
+MetadataType Metadata::MetadataItemType(const char * key) const { return ItemType(items->Find(key)); }
+unsigned int Metadata::MetadataItemAsInt(const char * key) const { return ItemAsInt(items->Find(key)); }
+std::string Metadata::MetadataItemAsString(const char * key) const { return ItemAsString(items->Find(key)); }
+float Metadata::MetadataItemAsFloat(const char * key) const { return ItemAsFloat(items->Find(key)); }
+std::string Metadata::MetadataItemKey(size_t index) const { return ItemKey(*items, index); }
+
+// ******************************
+// CLIP REGISTRY
+// ******************************
+
+namespace Mock {
+
+ClipDescription::ClipDescription()
+	: width(1920), height(1080), frameCount(24), trackCount(1), framerate(24.0f),
+	  pattern(PatternGradient), checkerSize(64), decodeDelayMs(0) {
+	memset(uuid, 0, sizeof(uuid));
+	color[0] = color[1] = color[2] = 0.18f;
+}
+
+void RegisterClip(const char * path, const ClipDescription & clip) {
+	std::lock_guard<std::mutex> lock(RegistryMutex());
+	Registry()[path] = std::make_shared<const ClipDescription>(clip);
+}
+
+bool UnregisterClip(const char * path) {
+	std::lock_guard<std::mutex> lock(RegistryMutex());
+	return Registry().erase(path) > 0;
+}
+
+}
+
+// ******************************
+// FRAME RENDERING
+// ******************************
+
+static size_t ModeDivisor(VideoDecodeMode mode) {
+	switch (mode) {
+		case DECODE_FULL_RES_PREMIUM:   return 1;
+		case DECODE_HALF_RES_PREMIUM:   return 2;
+		case DECODE_HALF_RES_GOOD:      return 2;
+		case DECODE_QUARTER_RES_GOOD:   return 4;
+		case DECODE_EIGHT_RES_GOOD:     return 8;
+		case DECODE_SIXTEENTH_RES_GOOD: return 16;
+		default:                        return 0;
+	}
+}
+
+// Bytes per pixel over all channels, 0 for unknown pixel types
+static size_t BytesPerPixel(VideoPixelType pixelType) {
+	switch (pixelType) {
+		case PixelType_16Bit_RGB_Interleaved:     return 6;
+		case PixelType_HalfFloat_RGB_Interleaved: return 6;
+		case PixelType_HalfFloat_RGB_ACES_Int:    return 6;
+		case PixelType_16Bit_RGB_Planar:          return 6;
+		case PixelType_8Bit_BGR_Interleaved:      return 3;
+		case PixelType_8Bit_BGRA_Interleaved:     return 4;
+		case PixelType_10Bit_DPX_MethodB:         return 4;
+		default:                                  return 0;
+	}
+}
+
+static uint16_t FloatToHalf(float f) {
+	uint32_t x;
+	memcpy(&x, &f, sizeof(x));
+	const uint32_t sign = (x >> 16) & 0x8000;
+	const uint32_t biased = (x >> 23) & 0xff;
+	uint32_t mant = x & 0x7fffff;
+	if (biased == 0xff)
+		return (uint16_t)(sign | 0x7c00 | (mant ? 0x200 : 0));
+	const int32_t exp = (int32_t)biased - 127 + 15;
+	if (exp >= 31)
+		return (uint16_t)(sign | 0x7c00);
+	if (exp <= 0) {
+		if (exp < -10)
+			return (uint16_t)sign;
+		mant |= 0x800000;
+		const uint32_t shift = (uint32_t)(14 - exp);
+		uint32_t half = mant >> shift;
+		const uint32_t rem = mant & ((1u << shift) - 1);
+		const uint32_t mid = 1u << (shift - 1);
+		if (rem > mid || (rem == mid && (half & 1)))
+			half++;
+		return (uint16_t)(sign | half);
+	}
+	uint32_t half = sign | ((uint32_t)exp << 10) | (mant >> 13);
+	const uint32_t rem = mant & 0x1fff;
+	if (rem > 0x1000 || (rem == 0x1000 && (half & 1)))
+		half++;
+	return (uint16_t)half;
+}
+
+static float Saturate(float v) { return std::min(std::max(v, 0.0f), 1.0f); }
+
+struct Grade {
+	float gain[3];
+	float saturation;
+};
+
+static Grade MakeGrade(const ImageProcessingSettings * settings, size_t track) {
+	const ImageProcessingSettings defaults;
+	const ImageProcessingSettings & s = settings ? *settings : defaults;
+	// The X track of HDRx clips is exposed 3 stops lower than the A track
+	const float stops = s.ExposureCompensation + s.ExposureAdjust - (track > 0 ? 3.0f : 0.0f);
+	const float exposure = std::pow(2.0f, stops);
+	Grade grade;
+	grade.gain[0] = exposure * s.GainRed;
+	grade.gain[1] = exposure * s.GainGreen;
+	grade.gain[2] = exposure * s.GainBlue;
+	grade.saturation = s.Saturation;
+	return grade;
+}
+
+// Scene value of the pixel at full resolution coordinates (x, y), before image processing
+static void SamplePattern(const Mock::ClipDescription & clip, size_t x, size_t y, size_t frame, float rgb[3]) {
+	switch (clip.pattern) {
+		case Mock::PatternSolid:
+			rgb[0] = clip.color[0];
+			rgb[1] = clip.color[1];
+			rgb[2] = clip.color[2];
+			break;
+		case Mock::PatternCheckerboard: {
+			const size_t size = std::max<size_t>(clip.checkerSize, 1);
+			const float v = (((x + frame) / size + y / size) & 1) ? 1.0f : 0.0f;
+			rgb[0] = rgb[1] = rgb[2] = v;
+			break;
+		}
+		default:
+			rgb[0] = clip.width  > 1 ? (float)x / (float)(clip.width  - 1) : 0.0f;
+			rgb[1] = clip.height > 1 ? (float)y / (float)(clip.height - 1) : 0.0f;
+			rgb[2] = clip.frameCount > 1 ? (float)frame / (float)(clip.frameCount - 1) : 0.0f;
+			break;
+	}
+}
+
+static void ApplyGrade(const Grade & grade, float rgb[3]) {
+	for (int c = 0; c < 3; c++)
+		rgb[c] *= grade.gain[c];
+	const float luma = 0.2126f * rgb[0] + 0.7152f * rgb[1] + 0.0722f * rgb[2];
+	for (int c = 0; c < 3; c++)
+		rgb[c] = luma + (rgb[c] - luma) * grade.saturation;
+}
+
+static void WritePixel(VideoPixelType pixelType, uint8_t * out, size_t index, size_t pixelCount, const float rgb[3]) {
+	switch (pixelType) {
+		case PixelType_16Bit_RGB_Interleaved: {
+			uint16_t * p = (uint16_t *)out + index * 3;
+			for (int c = 0; c < 3; c++)
+				p[c] = (uint16_t)std::lround(Saturate(rgb[c]) * 65535.0f);
+			break;
+		}
+		case PixelType_16Bit_RGB_Planar: {
+			uint16_t * p = (uint16_t *)out;
+			for (int c = 0; c < 3; c++)
+				p[c * pixelCount + index] = (uint16_t)std::lround(Saturate(rgb[c]) * 65535.0f);
+			break;
+		}
+		case PixelType_HalfFloat_RGB_Interleaved:
+		case PixelType_HalfFloat_RGB_ACES_Int: {
+			uint16_t * p = (uint16_t *)out + index * 3;
+			for (int c = 0; c < 3; c++)
+				p[c] = FloatToHalf(rgb[c]);
+			break;
+		}
+		case PixelType_8Bit_BGR_Interleaved:
+		case PixelType_8Bit_BGRA_Interleaved: {
+			const size_t bpp = pixelType == PixelType_8Bit_BGRA_Interleaved ? 4 : 3;
+			uint8_t * p = out + index * bpp;
+			for (int c = 0; c < 3; c++)
+				p[2 - c] = (uint8_t)std::lround(Saturate(rgb[c]) * 255.0f);
+			if (bpp == 4)
+				p[3] = 0xFF;
+			break;
+		}
+		case PixelType_10Bit_DPX_MethodB: {
+			// R << 22 | G << 12 | B << 2, big endian like in DPX files
+			uint32_t word = 0;
+			for (int c = 0; c < 3; c++)
+				word |= (uint32_t)std::lround(Saturate(rgb[c]) * 1023.0f) << (22 - c * 10);
+			uint8_t * p = out + index * 4;
+			p[0] = (uint8_t)(word >> 24);
+			p[1] = (uint8_t)(word >> 16);
+			p[2] = (uint8_t)(word >> 8);
+			p[3] = (uint8_t)word;
+			break;
+		}
+		default:
+			break;
+	}
+}
+
+static bool IsDroppedFrame(const Mock::ClipDescription & clip, size_t frame) {
+	return std::find(clip.droppedFrames.begin(), clip.droppedFrames.end(), frame) != clip.droppedFrames.end();
+}
+
+static void SimulateDelay(const Mock::ClipDescription & clip) {
+	if (clip.decodeDelayMs > 0)
+		std::this_thread::sleep_for(std::chrono::milliseconds(clip.decodeDelayMs));
+}
+
+// Renders a frame of the given track, or the HDRx blend of both tracks if hdr is set.
+// Parameters must have been validated by the caller.
+static void RenderFrame(const Mock::ClipDescription & clip, size_t track, const HdrProcessingSettings * hdr, size_t frame,
+                        VideoDecodeMode mode, VideoPixelType pixelType, const ImageProcessingSettings * settings, void * output) {
+	const size_t div = ModeDivisor(mode);
+	const size_t width = clip.width / div, height = clip.height / div;
+	const Grade gradeA = MakeGrade(settings, hdr ? 0 : track);
+	const Grade gradeX = MakeGrade(settings, 1);
+	// Bias -1 = A track only, 1 = X track only
+	const float blend = hdr ? (std::min(std::max(hdr->Bias, -1.0f), 1.0f) + 1.0f) * 0.5f : 0.0f;
+	for (size_t y = 0; y < height; y++) {
+		for (size_t x = 0; x < width; x++) {
+			float rgb[3];
+			SamplePattern(clip, x * div + div / 2, y * div + div / 2, frame, rgb);
+			if (hdr) {
+				float a[3] = { rgb[0], rgb[1], rgb[2] };
+				ApplyGrade(gradeA, a);
+				ApplyGrade(gradeX, rgb);
+				for (int c = 0; c < 3; c++)
+					rgb[c] = a[c] + (rgb[c] - a[c]) * blend;
+			} else {
+				ApplyGrade(gradeA, rgb);
+			}
+			WritePixel(pixelType, (uint8_t *)output, y * width + x, width * height, rgb);
+		}
+	}
+}
+
+static void FillFrameMetadata(const Mock::ClipDescription & clip, Metadata * metadata) {
+	if (metadata)
+		*metadata->items = clip.frameMetadata;
+}
+
+// ******************************
+// CLIP
+// ******************************
+
+Clip::Clip() : status(LSNoClipOpen), extendedHighlights(false) { }
+
+Clip::Clip(const char * pathToFile) : status(LSNoClipOpen), extendedHighlights(false) {
+	LoadFrom(pathToFile);
+}
+
+Clip::~Clip() { Close(); }
+
+static LoadStatus ProbeFile(const char * path) {
+	IOInterface * io = g_io;
+	if (io) {
+		IOInterface::Handle handle = io->Open(path, IOInterface::IO_READ);
+		if (handle == nullptr)
+			return LSPathNotFound;
+		if (handle != (IOInterface::Handle)-1) {
+			io->Close(handle);
+			return LSNotAnR3DFile;
+		}
+	}
+	FILE * file = fopen(path, "rb");
+	if (file == nullptr)
+		return LSPathNotFound;
+	fclose(file);
+	return LSNotAnR3DFile;
+}
+
+LoadStatus Clip::LoadFrom(const char * pathToFile) {
+	Close();
+	if (!g_initialized) {
+		status = LSNotInitialized;
+		return status;
+	}
+	if (pathToFile == nullptr || pathToFile[0] == '\0') {
+		status = LSPathNotFound;
+		return status;
+	}
+	{
+		std::lock_guard<std::mutex> lock(RegistryMutex());
+		auto it = Registry().find(pathToFile);
+		if (it != Registry().end())
+			clip = it->second;
+	}
+	if (!clip) {
+		status = ProbeFile(pathToFile);
+		return status;
+	}
+	if (clip->frameCount == 0 || clip->width == 0 || clip->height == 0) {
+		clip.reset();
+		status = LSClipIsEmpty;
+		return status;
+	}
+	path = pathToFile;
+	const size_t dot = path.find_last_of('.');
+	const size_t slash = path.find_last_of("/\\");
+	rmdPath = (dot != std::string::npos && (slash == std::string::npos || dot > slash) ? path.substr(0, dot) : path) + ".RMD";
+	status = LSClipLoaded;
+	return status;
+}
+
+LoadStatus Clip::Status() const { return status; }
+
+void Clip::Close() {
+	clip.reset();
+	path.clear();
+	rmdPath.clear();
+	extendedHighlights = false;
+	status = LSNoClipOpen;
+}
+
+void Clip::CloseFileHandles() { }
+
+const Mock::ClipDescription * Clip::Description() const { return status == LSClipLoaded ? clip.get() : nullptr; }
+
+bool Clip::RmdSidecarPresent() const { return false; }
+const char * Clip::GetRmdPath() { return status == LSClipLoaded ? rmdPath.c_str() : nullptr; }
+
+static std::vector<std::pair<Clip::FileType, std::string>> SidecarFiles(const std::string & clipPath) {
+	static const std::pair<Clip::FileType, const char *> extensions[] = {
+		{ Clip::FileType_Metadata, ".RMD" }, { Clip::FileType_Metadata, ".cdl" }, { Clip::FileType_LUT, ".cube" }, { Clip::FileType_Audio, ".wav" }
+	};
+	const size_t dot = clipPath.find_last_of('.');
+	const size_t slash = clipPath.find_last_of("/\\");
+	const std::string stem = dot != std::string::npos && (slash == std::string::npos || dot > slash) ? clipPath.substr(0, dot) : clipPath;
+	std::vector<std::pair<Clip::FileType, std::string>> files;
+	for (const auto & ext : extensions) {
+		const std::string path = stem + ext.second;
+		if (FILE * file = fopen(path.c_str(), "rb")) {
+			fclose(file);
+			files.emplace_back(ext.first, path);
+		}
+	}
+	return files;
+}
+
+size_t Clip::FileListCount() const { return status == LSClipLoaded ? SidecarFiles(path).size() + 1 : 0; }
+
+Clip::FileType Clip::FileList(size_t fileIdx, std::string & pathOutput) const {
+	if (status != LSClipLoaded)
+		return FileType_Invalid;
+	if (fileIdx == 0) {
+		pathOutput = path;
+		return FileType_Clip;
+	}
+	const auto files = SidecarFiles(path);
+	if (fileIdx > files.size())
+		return FileType_Invalid;
+	pathOutput = files[fileIdx - 1].second;
+	return files[fileIdx - 1].first;
+}
+
+DecodeStatus Clip::CheckFrame(size_t videoFrameNo) const {
+	const Mock::ClipDescription * c = Description();
+	if (!c)
+		return DSNoClipOpen;
+	if (videoFrameNo >= c->frameCount)
+		return DSRequestOutOfRange;
+	if (IsDroppedFrame(*c, videoFrameNo))
+		return DSIsDroppedFrame;
+	return DSDecodeOK;
+}
+
+bool Clip::ExtendedHighlightsEnabled() const { return extendedHighlights; }
+bool Clip::SetExtendedHighlights(bool enabled) {
+	if (status != LSClipLoaded)
+		return false;
+	extendedHighlights = enabled;
+	return true;
+}
+
+size_t Clip::VideoTrackCount() const { const Mock::ClipDescription * c = Description(); return c ? c->trackCount : 0; }
+
+static DecodeStatus DecodeSync(const Clip & clip, size_t track, size_t frame, const VideoDecodeJob & job) {
+	const Mock::ClipDescription * c = clip.Description();
+	if (!c)
+		return DSNoClipOpen;
+	const size_t div = ModeDivisor(job.Mode);
+	if (div == 0)
+		return DSInvalidParameter;
+	const size_t bpp = BytesPerPixel(job.PixelType);
+	if (bpp == 0)
+		return DSInvalidPixelType;
+	if (frame >= c->frameCount || track >= c->trackCount)
+		return DSRequestOutOfRange;
+	if (job.HdrProcessing && c->trackCount < 2)
+		return DSNotAnHDRxClip;
+	if (job.ImageProcessing && job.ImageProcessing->Version == ColorVersion1)
+		return DSParameterUnsupported;
+	const size_t needed = (c->width / div) * (c->height / div) * bpp;
+	if (job.OutputBuffer == nullptr || job.OutputBufferSize < needed || ((uintptr_t)job.OutputBuffer & 15) != 0)
+		return DSOutputBufferInvalid;
+	SimulateDelay(*c);
+	if (IsDroppedFrame(*c, frame))
+		return DSIsDroppedFrame;
+	RenderFrame(*c, track, job.HdrProcessing, frame, job.Mode, job.PixelType, job.ImageProcessing, job.OutputBuffer);
+	FillFrameMetadata(*c, job.OutputFrameMetadata);
+	return DSDecodeOK;
+}
+
+DecodeStatus Clip::DecodeVideoFrame(size_t videoFrameNo, const VideoDecodeJob & decodeJob) const {
+	return DecodeSync(*this, 0, videoFrameNo, decodeJob);
+}
+
+DecodeStatus Clip::VideoTrackDecodeFrame(size_t videoTrackNo, size_t videoFrameNo, const VideoDecodeJob & decodeJob) const {
+	return DecodeSync(*this, videoTrackNo, videoFrameNo, decodeJob);
+}
+
+bool Clip::Uuid(uint8_t * uuid) const {
+	const Mock::ClipDescription * c = Description();
+	if (!c || uuid == nullptr)
+		return false;
+	memcpy(uuid, c->uuid, sizeof(c->uuid));
+	return true;
+}
+
+size_t Clip::Width() const { const Mock::ClipDescription * c = Description(); return c ? c->width : 0; }
+size_t Clip::Height() const { const Mock::ClipDescription * c = Description(); return c ? c->height : 0; }
+size_t Clip::VideoFrameCount() const { const Mock::ClipDescription * c = Description(); return c ? c->frameCount : 0; }
+
+size_t Clip::AudioChannelCount() const { return 0; }
+size_t Clip::AudioBlockCountAndSize(size_t * maximumSize) const {
+	if (maximumSize)
+		*maximumSize = 0;
+	return 0;
+}
+unsigned long long Clip::AudioSampleCount() const { return 0; }
+
+float Clip::VideoAudioFramerate() const { const Mock::ClipDescription * c = Description(); return c ? c->framerate : 0.0f; }
+float Clip::TimecodeFramerate() const {
+	const float fps = VideoAudioFramerate();
+	return fps > 30.0f ? fps / 2.0f : fps;
+}
+
+static std::string FormatTimecode(size_t frames, float fps) {
+	const size_t rate = std::max<size_t>((size_t)std::lround(fps), 1);
+	const size_t seconds = frames / rate;
+	char buf[32];
+	snprintf(buf, sizeof(buf), "%02zu:%02zu:%02zu:%02zu", (seconds / 3600) % 24, (seconds / 60) % 60, seconds % 60, frames % rate);
+	return buf;
+}
+
+// Timecode starts at 01:00:00:00, edge timecode at 00:00:00:00
+static const char * ClipTimecode(const Clip & clip, std::string & storage, size_t videoFrameNo, bool edge) {
+	const Mock::ClipDescription * c = clip.Description();
+	if (!c || videoFrameNo >= c->frameCount)
+		return nullptr;
+	const float tcFps = clip.TimecodeFramerate();
+	const size_t start = edge ? 0 : 3600 * std::max<size_t>((size_t)std::lround(tcFps), 1);
+	storage = FormatTimecode(start + (size_t)((double)videoFrameNo * tcFps / c->framerate), tcFps);
+	return storage.c_str();
+}
+
+const char * Clip::Timecode(size_t videoFrameNo) { return ClipTimecode(*this, timecode, videoFrameNo, false); }
+const char * Clip::AbsoluteTimecode(size_t videoFrameNo) { return ClipTimecode(*this, timecode, videoFrameNo, false); }
+const char * Clip::EdgeTimecode(size_t videoFrameNo) { return ClipTimecode(*this, timecode, videoFrameNo, true); }
+
+void Clip::GetDefaultImageProcessingSettings(ImageProcessingSettings & settingsToFill) const {
+	settingsToFill = ImageProcessingSettings();
+	settingsToFill.Version = DefaultColorVersion();
+}
+
+void Clip::GetClipImageProcessingSettings(ImageProcessingSettings & settingsToFill) const {
+	GetDefaultImageProcessingSettings(settingsToFill);
+}
+
+HdrMode Clip::GetRmdHdrProcessingSettings(HdrProcessingSettings & settingsToFill, size_t & trackNoToFill) const {
+	settingsToFill = HdrProcessingSettings();
+	trackNoToFill = 0;
+	return HDR_USE_TRACKNO;
+}
+
+ColorVersion Clip::MinimumColorVersion() const { return ColorVersion2; }
+ColorVersion Clip::DefaultColorVersion() const { return ColorVersion3; }
+
+DecodeStatus Clip::GetFrameMetadata(Metadata & metadataToFill, size_t videoFrameNo) const {
+	const Mock::ClipDescription * c = Description();
+	if (!c)
+		return DSNoClipOpen;
+	if (videoFrameNo >= c->frameCount)
+		return DSRequestOutOfRange;
+	FillFrameMetadata(*c, &metadataToFill);
+	return DSDecodeOK;
+}
+
+static const Mock::MetadataItem * ClipItem(const Clip & clip, const char * key) {
+	const Mock::ClipDescription * c = clip.Description();
+	return c ? c->metadata.Find(key) : nullptr;
+}
+
+size_t Clip::MetadataCount() const { const Mock::ClipDescription * c = Description(); return c ? c->metadata.items.size() : 0; }
+bool Clip::MetadataExists(const char * key) const { return ClipItem(*this, key) != nullptr; }
+MetadataType Clip::MetadataItemType(const char * key) const { return ItemType(ClipItem(*this, key)); }
+unsigned int Clip::MetadataItemAsInt(const char * key) const { return ItemAsInt(ClipItem(*this, key)); }
+std::string Clip::MetadataItemAsString(const char * key) const { return ItemAsString(ClipItem(*this, key)); }
+float Clip::MetadataItemAsFloat(const char * key) const { return ItemAsFloat(ClipItem(*this, key)); }
+std::string Clip::MetadataItemKey(size_t index) const {
+	const Mock::ClipDescription * c = Description();
+	return c ? ItemKey(c->metadata, index) : std::string();
+}
+
+size_t Clip::IMUSampleCount() const { const Mock::ClipDescription * c = Description(); return c ? c->imuSamples.size() : 0; }
+
+bool Clip::DecodeIMUSamples(IMUSample * samples, size_t start, size_t count) const {
+	const Mock::ClipDescription * c = Description();
+	if (!c || samples == nullptr || start >= c->imuSamples.size() || count > c->imuSamples.size() - start)
+		return false;
+	std::copy(c->imuSamples.begin() + start, c->imuSamples.begin() + start + count, samples);
+	return true;
+}
+
+VideoDecodeJob::VideoDecodeJob()
+	: Mode(DECODE_FULL_RES_PREMIUM), PixelType(PixelType_16Bit_RGB_Interleaved), OutputBuffer(nullptr), OutputBufferSize(0),
+	  ImageProcessing(nullptr), HdrProcessing(nullptr), OutputFrameMetadata(nullptr) { }
+
+// ******************************
+// WORKER THREADS
+// ******************************
+
+namespace Mock {
+
+class WorkQueue {
+public:
+	explicit WorkQueue(size_t threads) {
+		for (size_t i = 0; i < std::max<size_t>(threads, 1); i++)
+			workers.emplace_back([this] { Run(); });
+	}
+	// Finishes all queued work before returning
+	~WorkQueue() {
+		{
+			std::lock_guard<std::mutex> lock(mutex);
+			stopping = true;
+		}
+		cond.notify_all();
+		for (std::thread & worker : workers)
+			worker.join();
+	}
+	void Push(std::function<void()> work) {
+		{
+			std::lock_guard<std::mutex> lock(mutex);
+			queue.push_back(std::move(work));
+		}
+		cond.notify_one();
+	}
+
+private:
+	void Run() {
+		for (;;) {
+			std::function<void()> work;
+			{
+				std::unique_lock<std::mutex> lock(mutex);
+				cond.wait(lock, [this] { return stopping || !queue.empty(); });
+				if (queue.empty())
+					return;
+				work = std::move(queue.front());
+				queue.pop_front();
+			}
+			work();
+		}
+	}
+
+	std::mutex mutex;
+	std::condition_variable cond;
+	std::deque<std::function<void()>> queue;
+	std::vector<std::thread> workers;
+	bool stopping = false;
+};
+
+}
+
+static size_t HardwareThreads() {
+	return std::max<unsigned int>(std::thread::hardware_concurrency(), 1);
+}
+
+// ******************************
+// ASYNC DECODING FOR THE GPU SDK
+// ******************************
+
+// Layout of the raw frames produced by DecodeForGpuSdk: this header followed by 16-bit interleaved RGB
+// at the decode resolution, without any image processing applied.
+struct MockRawHeader {
+	uint32_t magic;
+	uint32_t width;
+	uint32_t height;
+	uint32_t frame;
+	uint32_t track;
+	uint32_t mode;
+	uint64_t reserved;
+};
+static const uint32_t MOCK_RAW_MAGIC = 0x4D52414B; // "MRAK"
+
+AsyncDecompressJob::AsyncDecompressJob()
+	: Clip(nullptr), Mode(DECODE_FULL_RES_PREMIUM), OutputBuffer(nullptr), OutputBufferSize(0), VideoFrameNo(0), VideoTrackNo(0),
+	  AbortDecode(false), Callback(nullptr), PrivateData(nullptr), OutputFrameMetadata(nullptr) { }
+
+static size_t RawBufferSize(const AsyncDecompressJob & job) {
+	const Mock::ClipDescription * c = job.Clip ? job.Clip->Description() : nullptr;
+	const size_t div = ModeDivisor(job.Mode);
+	if (!c || div == 0)
+		return 0;
+	return sizeof(MockRawHeader) + (c->width / div) * (c->height / div) * 6;
+}
+
+static DecodeStatus SubmitRaw(Mock::WorkQueue * queue, AsyncDecompressJob & job) {
+	if (!queue)
+		return DSDecoderNotOpened;
+	const Mock::ClipDescription * c = job.Clip ? job.Clip->Description() : nullptr;
+	if (!c)
+		return DSNoClipOpen;
+	if (ModeDivisor(job.Mode) == 0 || job.Callback == nullptr)
+		return DSInvalidParameter;
+	if (job.VideoFrameNo >= c->frameCount || job.VideoTrackNo >= c->trackCount)
+		return DSRequestOutOfRange;
+	if (job.OutputBuffer == nullptr || job.OutputBufferSize < RawBufferSize(job))
+		return DSOutputBufferInvalid;
+
+	AsyncDecompressJob * item = &job;
+	queue->Push([item] {
+		const Mock::ClipDescription & clip = *item->Clip->Description();
+		DecodeStatus status = DSDecodeOK;
+		SimulateDelay(clip);
+		if (item->AbortDecode) {
+			status = DSCancelled;
+		} else if (IsDroppedFrame(clip, item->VideoFrameNo)) {
+			status = DSIsDroppedFrame;
+		} else {
+			const size_t div = ModeDivisor(item->Mode);
+			MockRawHeader header = { MOCK_RAW_MAGIC, (uint32_t)(clip.width / div), (uint32_t)(clip.height / div),
+			                         (uint32_t)item->VideoFrameNo, (uint32_t)item->VideoTrackNo, (uint32_t)item->Mode, 0 };
+			memcpy(item->OutputBuffer, &header, sizeof(header));
+			const ImageProcessingSettings unprocessed;
+			RenderFrame(clip, item->VideoTrackNo, nullptr, item->VideoFrameNo, item->Mode, PixelType_16Bit_RGB_Interleaved, &unprocessed,
+			            (uint8_t *)item->OutputBuffer + sizeof(header));
+			FillFrameMetadata(clip, item->OutputFrameMetadata);
+		}
+		CallbackScope scope;
+		item->Callback(item, status);
+	});
+	return DSDecodeOK;
+}
+
+AsyncDecoder::AsyncDecoder() : queue(nullptr) { }
+AsyncDecoder::~AsyncDecoder() { Close(); }
+
+DecodeStatus AsyncDecoder::Open(size_t noOfThreads) {
+	if (!queue)
+		queue = new Mock::WorkQueue(noOfThreads ? noOfThreads : ThreadsAvailable());
+	return DSDecodeOK;
+}
+
+void AsyncDecoder::Close() {
+	delete queue;
+	queue = nullptr;
+}
+
+size_t AsyncDecoder::ThreadsAvailable() { return HardwareThreads(); }
+size_t AsyncDecoder::GetSizeBufferNeeded(const AsyncDecompressJob & job) { return RawBufferSize(job); }
+DecodeStatus AsyncDecoder::DecodeForGpuSdk(AsyncDecompressJob & job) { return SubmitRaw(queue, job); }
+
+GpuDecoder::GpuDecoder() : queue(nullptr) { }
+GpuDecoder::~GpuDecoder() { Close(); }
+
+DecodeStatus GpuDecoder::Open() {
+	if (!queue)
+		queue = new Mock::WorkQueue(1);
+	return DSDecodeOK;
+}
+
+void GpuDecoder::Close() {
+	delete queue;
+	queue = nullptr;
+}
+
+DecodeStatus GpuDecoder::DecodeSupportedForClip(const Clip & clip) { return clip.Description() ? DSDecodeOK : DSNoClipOpen; }
+size_t GpuDecoder::GetSizeBufferNeeded(const AsyncDecompressJob & job) { return RawBufferSize(job); }
+DecodeStatus GpuDecoder::DecodeForGpuSdk(AsyncDecompressJob & job) { return SubmitRaw(queue, job); }
+
+// ******************************
+// R3D DECODER
+// ******************************
+
+R3DDecoderOptions::R3DDecoderOptions() : threads(0), deviceCount(0) { }
+
+R3DStatus R3DDecoderOptions::CreateOptions(R3DDecoderOptions ** options) {
+	if (options == nullptr)
+		return R3DStatus_InvalidJobParameter;
+	*options = new R3DDecoderOptions();
+	return R3DStatus_Ok;
+}
+
+void R3DDecoderOptions::ReleaseOptions(R3DDecoderOptions * options) { delete options; }
+
+R3DStatus R3DDecoderOptions::setScratchFolder(const std::string &) { return R3DStatus_Ok; }
+R3DStatus R3DDecoderOptions::setDecompressionThreadCount(size_t count) { threads = count; return R3DStatus_Ok; }
+R3DStatus R3DDecoderOptions::setConcurrentImageCount(size_t) { return R3DStatus_Ok; }
+R3DStatus R3DDecoderOptions::setMemoryPoolSize(size_t sizeMBs) { return sizeMBs >= 1024 ? R3DStatus_Ok : R3DStatus_ParameterUnsupported; }
+R3DStatus R3DDecoderOptions::setGPUMemoryPoolSize(size_t) { return R3DStatus_Ok; }
+R3DStatus R3DDecoderOptions::setGPUConcurrentFrameCount(size_t count) { return count > 0 ? R3DStatus_Ok : R3DStatus_ParameterUnsupported; }
+
+R3DStatus R3DDecoderOptions::GetOpenCLDeviceList(std::vector<OpenCLDeviceInfo> & list) {
+	OpenCLDeviceInfo device = {};
+	device.platform_id = (void *)1;
+	device.device_id = (void *)1;
+	snprintf(device.name, sizeof(device.name), "Mock OpenCL Device");
+	snprintf(device.platform_name, sizeof(device.platform_name), "Mock OpenCL Platform");
+	list.push_back(device);
+	return R3DStatus_Ok;
+}
+
+R3DStatus R3DDecoderOptions::GetCudaDeviceList(std::vector<CudaDeviceInfo> & list) {
+	CudaDeviceInfo device = {};
+	device.pci_bus_id = 1;
+	snprintf(device.name, sizeof(device.name), "Mock CUDA Device");
+	list.push_back(device);
+	return R3DStatus_Ok;
+}
+
+R3DStatus R3DDecoderOptions::useDevice(const OpenCLDeviceInfo & device) {
+	if (device.device_id != (void *)1)
+		return R3DStatus_UnableToUseGPUDevice;
+	deviceCount++;
+	return R3DStatus_Ok;
+}
+
+R3DStatus R3DDecoderOptions::useDevice(const CudaDeviceInfo & device) {
+	if (device.pci_bus_id != 1)
+		return R3DStatus_UnableToUseGPUDevice;
+	deviceCount++;
+	return R3DStatus_Ok;
+}
+
+R3DDecodeJob::R3DDecodeJob()
+	: clip(nullptr), mode(DECODE_FULL_RES_PREMIUM), videoTrackNo(0), videoFrameNo(0), outputBuffer(nullptr), outputBufferSize(0),
+	  pixelType(PixelType_16Bit_RGB_Interleaved), imageProcessingSettings(nullptr), hdrProcessingSettings(nullptr),
+	  callback(nullptr), privateData(nullptr), outputFrameMetadata(nullptr) { }
+
+R3DDecoder::R3DDecoder() : queue(nullptr) { }
+R3DDecoder::~R3DDecoder() { delete queue; }
+
+R3DStatus R3DDecoder::CreateDecoder(R3DDecoderOptions * options, R3DDecoder ** decoder) {
+	if (options == nullptr || decoder == nullptr)
+		return R3DStatus_InvalidJobParameter;
+	if (!g_initialized || (g_components & OPTION_RED_DECODER) == 0)
+		return R3DStatus_UnableToLoadLibrary;
+	if (options->deviceCount == 0)
+		return R3DStatus_NoGPUDeviceSpecified;
+	R3DDecoder * d = new R3DDecoder();
+	d->queue = new Mock::WorkQueue(options->threads ? options->threads : HardwareThreads());
+	*decoder = d;
+	return R3DStatus_Ok;
+}
+
+// Waits for all submitted jobs to finish
+void R3DDecoder::ReleaseDecoder(R3DDecoder * decoder) { delete decoder; }
+
+R3DStatus R3DDecoder::CreateDecodeJob(R3DDecodeJob ** job) {
+	if (job == nullptr)
+		return R3DStatus_InvalidJobParameter;
+	*job = new R3DDecodeJob();
+	return R3DStatus_Ok;
+}
+
+void R3DDecoder::ReleaseDecodeJob(R3DDecodeJob * job) {
+	CheckNotInCallback("R3DDecoder::ReleaseDecodeJob");
+	delete job;
+}
+
+R3DStatus R3DDecoder::decode(R3DDecodeJob * job) {
+	if (job == nullptr || job->callback == nullptr || job->imageProcessingSettings == nullptr)
+		return R3DStatus_InvalidJobParameter;
+	const Mock::ClipDescription * c = job->clip ? job->clip->Description() : nullptr;
+	if (!c)
+		return R3DStatus_InvalidJobParameter_clip;
+	const size_t div = ModeDivisor(job->mode);
+	if (div == 0)
+		return R3DStatus_InvalidJobParameter_mode;
+	const size_t bpp = BytesPerPixel(job->pixelType);
+	if (bpp == 0 || job->pixelType == PixelType_8Bit_BGRA_Interleaved)
+		return R3DStatus_InvalidJobParameter_pixelType;
+	if (job->imageProcessingSettings->Version == ColorVersion1)
+		return R3DStatus_InvalidJobParameter_ColorVersion1;
+	if (job->outputBuffer == nullptr)
+		return R3DStatus_InvalidJobParameter_output_device_mem;
+	if (job->outputBufferSize < (c->width / div) * (c->height / div) * bpp)
+		return R3DStatus_InvalidJobParameter_output_device_mem_size;
+	if (job->videoFrameNo >= c->frameCount || job->videoTrackNo >= c->trackCount || (job->hdrProcessingSettings && c->trackCount < 2))
+		return R3DStatus_InvalidJobParameter;
+
+	queue->Push([job] {
+		const Mock::ClipDescription & clip = *job->clip->Description();
+		SimulateDelay(clip);
+		R3DStatus status = R3DStatus_Ok;
+		if (IsDroppedFrame(clip, job->videoFrameNo)) {
+			status = R3DStatus_ErrorProcessing;
+		} else {
+			RenderFrame(clip, job->videoTrackNo, job->hdrProcessingSettings, job->videoFrameNo, job->mode, job->pixelType,
+			            job->imageProcessingSettings, job->outputBuffer);
+			FillFrameMetadata(clip, job->outputFrameMetadata);
+		}
+		CallbackScope scope;
+		job->callback(job, status);
+	});
+	return R3DStatus_Ok;
+}
+
+// ******************************
+// CUSTOM I/O
+// ******************************
+
+IOInterface::IOInterface() { }
+IOInterface::~IOInterface() { }
+
+void SetIoInterface(IOInterface * io) { g_io = io; }
+void ResetIoInterface() { g_io = nullptr; }
+
+// ******************************
+// GPU DEBAYERING
+// ******************************
+
+// Size of the debayered frame of a raw frame produced by DecodeForGpuSdk, 0 if raw is not a mock raw frame
+static size_t DebayerResultSize(const void * raw, VideoPixelType pixelType) {
+	if (raw == nullptr)
+		return 0;
+	MockRawHeader header;
+	memcpy(&header, raw, sizeof(header));
+	if (header.magic != MOCK_RAW_MAGIC)
+		return 0;
+	return (size_t)header.width * header.height * BytesPerPixel(pixelType);
+}
+
+// Common validation of the debayer jobs, returns the index of the matching Status_* value
+template <typename Job> static int CheckDebayerJob(const Job * job) {
+	if (job == nullptr)
+		return 2;
+	if (job->raw_host_mem == nullptr || DebayerResultSize(job->raw_host_mem, PixelType_16Bit_RGB_Interleaved) == 0)
+		return 4;
+	if (job->raw_device_mem == nullptr)
+		return 5;
+	if (BytesPerPixel(job->pixelType) == 0)
+		return 6;
+	if (job->imageProcessingSettings && job->imageProcessingSettings->Version == ColorVersion1)
+		return 9;
+	if (job->output_device_mem_size < DebayerResultSize(job->raw_host_mem, job->pixelType))
+		return 7;
+	if (job->output_device_mem == nullptr)
+		return 8;
+	// Valid job, but there is nothing to run it on
+	return 10;
+}
+
+#ifndef __APPLE__
+REDCuda::REDCuda(EXT_CUDA_API &) { }
+REDCuda::~REDCuda() { }
+DebayerCudaJob * REDCuda::createDebayerJob() { return new DebayerCudaJob(); }
+void REDCuda::releaseDebayerJob(DebayerCudaJob * job) { delete job; }
+REDCuda::Status REDCuda::checkCompatibility(int, cudaStream_t, cudaError_t &) { return Status_UnableToUseGPUDevice; }
+REDCuda::Status REDCuda::process(int, cudaStream_t, DebayerCudaJob * job, cudaError_t &) { return (Status)CheckDebayerJob(job); }
+REDCuda::Status REDCuda::processAsync(int, cudaStream_t, DebayerCudaJob * job, cudaError_t &) { return (Status)CheckDebayerJob(job); }
+void DebayerCudaJob::completeAsync() { }
+size_t DebayerCudaJob::ResultFrameSize(const DebayerCudaJob * job) { return job ? DebayerResultSize(job->raw_host_mem, job->pixelType) : 0; }
+size_t DebayerCudaJob::ResultFrameSize(const DebayerCudaJob & job) { return DebayerResultSize(job.raw_host_mem, job.pixelType); }
+#endif
+
+REDCL::REDCL(EXT_OCLAPI_1_1 &, const char *) { }
+REDCL::~REDCL() { }
+DebayerOpenCLJob * REDCL::createDebayerJob() { return new DebayerOpenCLJob(); }
+void REDCL::releaseDebayerJob(DebayerOpenCLJob * job) { delete job; }
+REDCL::Status REDCL::checkCompatibility(cl_context, cl_command_queue, cl_int &) { return Status_UnableToUseGPUDevice; }
+REDCL::Status REDCL::process(cl_context, cl_command_queue, DebayerOpenCLJob * job, cl_int &) { return (Status)CheckDebayerJob(job); }
+REDCL::Status REDCL::processAsync(cl_context, cl_command_queue, DebayerOpenCLJob * job, cl_int &) { return (Status)CheckDebayerJob(job); }
+void DebayerOpenCLJob::completeAsync() { }
+size_t DebayerOpenCLJob::ResultFrameSize(const DebayerOpenCLJob & job) { return DebayerResultSize(job.raw_host_mem, job.pixelType); }
+
+#if defined(__APPLE__) && defined(__OBJC__)
+REDMetal::REDMetal(EXT_METAL_API &) { }
+REDMetal::~REDMetal() { }
+DebayerMetalJob * REDMetal::createDebayerJob() { return new DebayerMetalJob(); }
+void REDMetal::releaseDebayerJob(DebayerMetalJob * job) { delete job; }
+REDMetal::Status REDMetal::checkCompatibility(id<MTLCommandQueue>, int &) { return Status_UnableToUseGPUDevice; }
+REDMetal::Status REDMetal::process(id<MTLCommandQueue>, DebayerMetalJob * job, int &) { return (Status)CheckDebayerJob(job); }
+REDMetal::Status REDMetal::processAsync(id<MTLCommandQueue>, DebayerMetalJob * job, int &) { return (Status)CheckDebayerJob(job); }
+REDMetal::Status REDMetal::flush(id<MTLCommandQueue>, int &) { return Status_Ok; }
+void DebayerMetalJob::completeAsync() { }
+size_t DebayerMetalJob::ResultFrameSize(const DebayerMetalJob & job) { return DebayerResultSize(job.raw_host_mem, job.pixelType); }
+#endif
+
+}
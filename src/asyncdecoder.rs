@@ -1,9 +1,10 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
-use crate::{ RedResult, RedError,  clip::AlignedBuffer, enums::*, future::*, metadata::* };
+use crate::{ RedResult, RedError, ErrorContext, Sdk, DecodeFailure, metrics::*, clip::AlignedBuffer, enums::*, future::*, metadata::* };
 use core::ffi::c_void;
-use std::sync::{ Arc, atomic::Ordering };
+use std::sync::Arc;
+use std::time::Duration;
 use cpp::*;
 
 cpp!{{
@@ -13,23 +14,38 @@ cpp!{{
 /// Class for asynchronously decompressing a frame for further processing on the GPU.
 pub struct AsyncDecoder {
     ptr: *mut core::ffi::c_void,
+    timeout: Option<Duration>,
+    tracker: Arc<JobTracker>,
+    _sdk: Sdk,
 }
+// SAFETY: DecodeForGpuSdk() is thread-safe, the decoder only holds an opaque pointer to the SDK object.
+unsafe impl Send for AsyncDecoder {}
+unsafe impl Sync for AsyncDecoder {}
 
 impl AsyncDecoder {
 	/// Create and open the asynchronous decoder with the specified number of threads
 	/// If threads_no is set to 0, threads_available() number of threads will be used.
-    pub fn new(threads_no: usize) -> Self {
+    pub fn new(threads_no: usize) -> RedResult<Self> {
+        let sdk = Sdk::current()?;
         let ptr = cpp!(unsafe [threads_no as "size_t"] -> *mut core::ffi::c_void as "R3DSDK::AsyncDecoder *" {
             R3DSDK::AsyncDecoder *ptr = new R3DSDK::AsyncDecoder();
             ptr->Open(threads_no);
             return ptr;
         });
-        Self { ptr }
+        Ok(Self { ptr, timeout: None, tracker: Arc::default(), _sdk: sdk })
     }
     pub fn as_mut_ptr(&self) -> *mut core::ffi::c_void {
         self.ptr
     }
 
+    /// Default timeout applied to every future returned by this decoder, see [`CallbackFuture::with_timeout`].
+    pub fn set_default_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+    pub fn default_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
 	/// Returns the number of threads the decoder will use if opened when
 	/// noOfThreads in Open() is set to 0 (the default).
     pub fn threads_available() -> usize {
@@ -55,36 +71,74 @@ impl AsyncDecoder {
     ///
     /// You should await the returned future to get the status of the decompress operation.
     /// The future gives you back ownership of the job you passed in, so you can re-use it if needed.
-    pub fn decode_for_gpu_sdk(&self, job: AsyncDecompressJob) -> RedResult<CallbackFuture<AsyncDecompressJob>> {
+    /// The job is also returned on failure, as part of [`DecodeFailure`].
+    pub fn decode_for_gpu_sdk(&self, job: AsyncDecompressJob) -> Result<CallbackFuture<AsyncDecompressJob>, DecodeFailure<AsyncDecompressJob>> {
+        const OPERATION: &str = "AsyncDecoder::decode_for_gpu_sdk";
         unsafe {
             let ptr = self.ptr;
             let job_ptr = job.as_mut_ptr();
+            self.tracker.release_retired();
 
-            let state = Arc::new(State::new(job));
-            let state_ptr = Arc::as_ptr(&state) as *mut c_void;
+            let metrics = JobMetrics::start(DecodeSource::AsyncDecoder, job.video_frame_no());
+            let _scope = metrics.enter();
+            let state = Arc::new(State::new(job, metrics, self.tracker.clone()).abort_on_timeout(AsyncDecompressJob::abort));
+            // The callback owns one reference, so the state outlives the future if it's dropped before completion.
+            let state_ptr = Arc::into_raw(state.clone()) as *mut c_void;
 
-            let callback_ptr = async_decode_callback as extern "C" fn(*mut c_void, DecodeStatus);
+            let callback_ptr = async_decode_callback as extern "C" fn(*mut c_void, i32);
+            self.tracker.add(job_ptr);
 
-            let status: DecodeStatus = std::mem::transmute(cpp!([ptr as "R3DSDK::AsyncDecoder *", job_ptr as "R3DSDK::AsyncDecompressJob *", state_ptr as "void *", callback_ptr as "void *"] -> i32 as "int" {
+            let status = DecodeStatus::from(cpp!([ptr as "R3DSDK::AsyncDecoder *", job_ptr as "R3DSDK::AsyncDecompressJob *", state_ptr as "void *", callback_ptr as "void *"] -> i32 as "int" {
                 job_ptr->Callback = (R3DSDK::AsyncDecompressJob::AsyncDecompressCallback)callback_ptr;
                 job_ptr->PrivateData = state_ptr;
                 return ptr->DecodeForGpuSdk(*job_ptr);
             }));
             if status != DecodeStatus::Ok {
-                Err(RedError::from(status))
+                // The job was not scheduled, so the callback will never be called
+                drop(Arc::from_raw(state_ptr as *const State<AsyncDecompressJob>));
+                self.tracker.remove(job_ptr);
+                state.finish_metrics(Err(status));
+                let job = state.job.lock().unwrap().take();
+                let error = RedError::from(status).with_context(AsyncDecompressJob::error_context(job.as_ref(), OPERATION).status(status.code()));
+                Err(DecodeFailure { job, error })
             } else {
-                Ok(CallbackFuture { state })
+                Ok(CallbackFuture::new(state, self.timeout))
             }
         }
     }
+
+    /// Close the decoder once all submitted jobs have finished.
+    ///
+    /// All outstanding jobs are aborted, the returned future resolves after their callbacks fired
+    /// (resolving their futures, usually with `Cancelled`), then the decoder is closed and released.
+    /// Dropping the decoder does the same, but blocks the current thread while waiting.
+    ///
+    /// The wait is limited to the [default timeout](Self::set_default_timeout), or 30 seconds without one. If some callbacks
+    /// still didn't fire by then (e.g. a hung decode), the future resolves anyway and the native decoder is leaked, see `Drop`.
+    pub fn close(self) -> impl Future<Output = ()> + Send {
+        abort_pending(&self.tracker);
+        let (tracker, timeout) = (self.tracker.clone(), self.timeout);
+        DecoderShutdown::new(self, tracker, timeout)
+    }
 }
 impl Drop for AsyncDecoder {
+    /// Aborts and waits for all submitted jobs, at most for the [default timeout](Self::set_default_timeout)
+    /// (or 30 seconds without one) counted from the drop or from [`close`](Self::close).
+    /// If jobs are still pending after that, the decoder is not closed and the native decoder and the SDK are leaked
+    /// with a warning, so a late callback can never touch freed memory.
     fn drop(&mut self) {
+        // Make sure no callback can fire after the decoder is released
+        abort_pending(&self.tracker);
+        if !self.tracker.wait_release(self.timeout, "AsyncDecoder") {
+            std::mem::forget(self._sdk.clone());
+            return;
+        }
         let self_ptr = self.ptr;
         cpp!(unsafe [self_ptr as "R3DSDK::AsyncDecoder *"] {
             self_ptr->Close();
             delete self_ptr;
         });
+        self.tracker.release_retired();
     }
 }
 
@@ -93,28 +147,43 @@ impl Drop for AsyncDecoder {
 /// NOTE: 4GB minimum video RAM recommended when using GPU decode
 pub struct GpuDecoder {
     ptr: *mut core::ffi::c_void,
+    timeout: Option<Duration>,
+    tracker: Arc<JobTracker>,
+    _sdk: Sdk,
 }
+// SAFETY: same as AsyncDecoder.
+unsafe impl Send for GpuDecoder {}
+unsafe impl Sync for GpuDecoder {}
 
 impl GpuDecoder {
 	/// Create and open the asynchronous decoder.
-    pub fn new() -> Self {
+    pub fn new() -> RedResult<Self> {
+        let sdk = Sdk::current()?;
         let ptr = cpp!(unsafe [] -> *mut core::ffi::c_void as "R3DSDK::GpuDecoder *" {
             R3DSDK::GpuDecoder *ptr = new R3DSDK::GpuDecoder();
             ptr->Open();
             return ptr;
         });
-        Self { ptr }
+        Ok(Self { ptr, timeout: None, tracker: Arc::default(), _sdk: sdk })
     }
     pub fn as_mut_ptr(&self) -> *mut core::ffi::c_void {
         self.ptr
     }
 
+    /// Default timeout applied to every future returned by this decoder, see [`CallbackFuture::with_timeout`].
+    pub fn set_default_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+    pub fn default_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
 	/// Determines if the current clip is supported for GPU decompression.
 	/// All clips except for ones recorded on RED ONE are supported.
     pub fn decode_supported_for_clip(clip: &crate::Clip) -> bool {
         let clip_ptr = clip.as_mut_ptr();
         unsafe {
-            let status: DecodeStatus = std::mem::transmute(cpp!([clip_ptr as "const R3DSDK::Clip *"] -> i32 as "int" {
+            let status = DecodeStatus::from(cpp!([clip_ptr as "const R3DSDK::Clip *"] -> i32 as "int" {
                 return (R3DSDK::DecodeStatus)R3DSDK::GpuDecoder::DecodeSupportedForClip(*clip_ptr);
             }));
             status == DecodeStatus::Ok
@@ -139,36 +208,74 @@ impl GpuDecoder {
     ///
     /// You should await the returned future to get the status of the decompress operation.
     /// The future gives you back ownership of the job you passed in, so you can re-use it if needed.
-    pub fn decode_for_gpu_sdk(&self, job: AsyncDecompressJob) -> RedResult<CallbackFuture<AsyncDecompressJob>> {
+    /// The job is also returned on failure, as part of [`DecodeFailure`].
+    pub fn decode_for_gpu_sdk(&self, job: AsyncDecompressJob) -> Result<CallbackFuture<AsyncDecompressJob>, DecodeFailure<AsyncDecompressJob>> {
+        const OPERATION: &str = "GpuDecoder::decode_for_gpu_sdk";
         unsafe {
             let ptr = self.ptr;
             let job_ptr = job.as_mut_ptr();
+            self.tracker.release_retired();
 
-            let state = Arc::new(State::new(job));
-            let state_ptr = Arc::as_ptr(&state) as *mut c_void;
+            let metrics = JobMetrics::start(DecodeSource::GpuDecoder, job.video_frame_no());
+            let _scope = metrics.enter();
+            let state = Arc::new(State::new(job, metrics, self.tracker.clone()).abort_on_timeout(AsyncDecompressJob::abort));
+            // The callback owns one reference, so the state outlives the future if it's dropped before completion.
+            let state_ptr = Arc::into_raw(state.clone()) as *mut c_void;
 
-            let callback_ptr = async_decode_callback as extern "C" fn(*mut c_void, DecodeStatus);
+            let callback_ptr = gpu_decode_callback as extern "C" fn(*mut c_void, i32);
+            self.tracker.add(job_ptr);
 
-            let status: DecodeStatus = std::mem::transmute(cpp!([ptr as "R3DSDK::GpuDecoder *", job_ptr as "R3DSDK::AsyncDecompressJob *", state_ptr as "void *", callback_ptr as "void *"] -> i32 as "int" {
+            let status = DecodeStatus::from(cpp!([ptr as "R3DSDK::GpuDecoder *", job_ptr as "R3DSDK::AsyncDecompressJob *", state_ptr as "void *", callback_ptr as "void *"] -> i32 as "int" {
                 job_ptr->Callback = (R3DSDK::AsyncDecompressJob::AsyncDecompressCallback)callback_ptr;
                 job_ptr->PrivateData = state_ptr;
                 return ptr->DecodeForGpuSdk(*job_ptr);
             }));
             if status != DecodeStatus::Ok {
-                Err(RedError::from(status))
+                // The job was not scheduled, so the callback will never be called
+                drop(Arc::from_raw(state_ptr as *const State<AsyncDecompressJob>));
+                self.tracker.remove(job_ptr);
+                state.finish_metrics(Err(status));
+                let job = state.job.lock().unwrap().take();
+                let error = RedError::from(status).with_context(AsyncDecompressJob::error_context(job.as_ref(), OPERATION).status(status.code()));
+                Err(DecodeFailure { job, error })
             } else {
-                Ok(CallbackFuture { state })
+                Ok(CallbackFuture::new(state, self.timeout))
             }
         }
     }
+
+    /// Close the decoder once all submitted jobs have finished.
+    ///
+    /// All outstanding jobs are aborted, the returned future resolves after their callbacks fired
+    /// (resolving their futures, usually with `Cancelled`), then the decoder is closed and released.
+    /// Dropping the decoder does the same, but blocks the current thread while waiting.
+    ///
+    /// The wait is limited to the [default timeout](Self::set_default_timeout), or 30 seconds without one. If some callbacks
+    /// still didn't fire by then (e.g. a hung decode), the future resolves anyway and the native decoder is leaked, see `Drop`.
+    pub fn close(self) -> impl Future<Output = ()> + Send {
+        abort_pending(&self.tracker);
+        let (tracker, timeout) = (self.tracker.clone(), self.timeout);
+        DecoderShutdown::new(self, tracker, timeout)
+    }
 }
 impl Drop for GpuDecoder {
+    /// Aborts and waits for all submitted jobs, at most for the [default timeout](Self::set_default_timeout)
+    /// (or 30 seconds without one) counted from the drop or from [`close`](Self::close).
+    /// If jobs are still pending after that, the decoder is not closed and the native decoder and the SDK are leaked
+    /// with a warning, so a late callback can never touch freed memory.
     fn drop(&mut self) {
+        // Make sure no callback can fire after the decoder is released
+        abort_pending(&self.tracker);
+        if !self.tracker.wait_release(self.timeout, "GpuDecoder") {
+            std::mem::forget(self._sdk.clone());
+            return;
+        }
         let self_ptr = self.ptr;
         cpp!(unsafe [self_ptr as "R3DSDK::GpuDecoder *"] {
             self_ptr->Close();
             delete self_ptr;
         });
+        self.tracker.release_retired();
     }
 }
 
@@ -176,19 +283,32 @@ pub struct AsyncDecompressJob {
     ptr: *mut core::ffi::c_void,
     internal_buffer: Option<AlignedBuffer>,
     metadata_allocated: bool,
+    clip_path: Option<String>,
 }
+// SAFETY: the job is exclusively owned (by the caller or by the pending future), setters take &mut self.
+unsafe impl Send for AsyncDecompressJob {}
+unsafe impl Sync for AsyncDecompressJob {}
+
 impl AsyncDecompressJob {
     pub fn new() -> Self {
         unsafe {
             let mut ptr = std::ptr::null_mut();
             let ptrptr = &mut ptr;
             cpp!([ptrptr as "R3DSDK::AsyncDecompressJob **"] { *ptrptr = new R3DSDK::AsyncDecompressJob(); });
-            Self { ptr, internal_buffer: None, metadata_allocated: false }
+            Self { ptr, internal_buffer: None, metadata_allocated: false, clip_path: None }
         }
     }
     pub fn as_mut_ptr(&self) -> *mut core::ffi::c_void {
         self.ptr
     }
+
+    /// Error context for a failed decode of `job`, the job is `None` if it was lost
+    fn error_context(job: Option<&Self>, operation: &'static str) -> ErrorContext {
+        match job {
+            Some(job) => ErrorContext::new(operation).clip_path(job.clip_path.as_deref()).video_frame_no(job.video_frame_no()).video_track_no(job.video_track_no()),
+            None => ErrorContext::new(operation),
+        }
+    }
 }
 impl Drop for AsyncDecompressJob {
     fn drop(&mut self) {
@@ -210,7 +330,8 @@ impl AsyncDecompressJob {
         let clip_ptr = clip.as_mut_ptr();
 		cpp!(unsafe [self_ptr as "R3DSDK::AsyncDecompressJob *", clip_ptr as "R3DSDK::Clip *"] {
 			self_ptr->Clip = clip_ptr;
-		})
+		});
+        self.clip_path = clip.path().map(str::to_owned);
     }
 
     /// Call this if you want to abort processing this frame as soon as possible
@@ -229,9 +350,17 @@ impl AsyncDecompressJob {
         let self_ptr = self.ptr;
         cpp!(unsafe [self_ptr as "R3DSDK::AsyncDecompressJob *", v as "size_t"] { self_ptr->VideoFrameNo = v; })
     }
+    pub fn video_track_no(&self) -> usize {
+        let self_ptr = self.ptr;
+        cpp!(unsafe [self_ptr as "const R3DSDK::AsyncDecompressJob *"] -> usize as "size_t" { return self_ptr->VideoTrackNo; })
+    }
     pub fn video_frame_no(&self) -> usize {
         let self_ptr = self.ptr;
         cpp!(unsafe [self_ptr as "const R3DSDK::AsyncDecompressJob *"] -> usize as "size_t" { return self_ptr->VideoFrameNo; })
+    }
+    pub fn output_buffer_size(&self) -> usize {
+        let self_ptr = self.ptr;
+        cpp!(unsafe [self_ptr as "const R3DSDK::AsyncDecompressJob *"] -> usize as "size_t" { return self_ptr->OutputBufferSize; })
     }
 	// item is the pointer to the original item as submitted in the Decode() call
 	// typedef void (*DecodeCallback)(AsyncDecompressJob * item, R3DStatus decodeStatus);
@@ -240,10 +369,11 @@ impl AsyncDecompressJob {
 
 
 	/// Resolution/speed to decode the image at. This will also
-	/// influence how much memory is needed for the output buffer
-	pub fn mode(&self) -> VideoDecodeMode {
+	/// influence how much memory is needed for the output buffer.
+	/// `RedError::InvalidEnumValue` if the mode wasn't set yet.
+	pub fn mode(&self) -> RedResult<VideoDecodeMode> {
         let self_ptr = self.ptr;
-		unsafe { std::mem::transmute(cpp!([self_ptr as "const R3DSDK::AsyncDecompressJob *"] -> i32 as "int" { return (int)self_ptr->Mode; })) }
+		VideoDecodeMode::try_from(cpp!(unsafe [self_ptr as "const R3DSDK::AsyncDecompressJob *"] -> i32 as "int" { return (int)self_ptr->Mode; }))
 	}
 	/// Resolution/speed to decode the image at. This will also
 	/// influence how much memory is needed for the output buffer
@@ -289,7 +419,7 @@ impl AsyncDecompressJob {
     /// Optionally return the decoded frame's per-frame metadata
     pub fn metadata(&self) -> RedResult<&Metadata> {
         if !self.metadata_allocated {
-            return Err(RedError::Other("Metadata was not requested in the job".to_string()));
+            return Err(RedError::FrameMetadataNotRequested);
         }
         let self_ptr = self.ptr;
         let ptr = cpp!(unsafe [self_ptr as "const R3DSDK::AsyncDecompressJob *"] -> *const core::ffi::c_void as "void *" {
@@ -299,7 +429,14 @@ impl AsyncDecompressJob {
     }
 }
 
-extern "C" fn async_decode_callback(job: *mut c_void /* AsyncDecompressJob * */, decode_status: DecodeStatus) {
+extern "C" fn async_decode_callback(job: *mut c_void /* AsyncDecompressJob * */, decode_status: i32) {
+    finish_job(job, DecodeStatus::from(decode_status), "AsyncDecoder::decode_for_gpu_sdk");
+}
+extern "C" fn gpu_decode_callback(job: *mut c_void /* AsyncDecompressJob * */, decode_status: i32) {
+    finish_job(job, DecodeStatus::from(decode_status), "GpuDecoder::decode_for_gpu_sdk");
+}
+
+fn finish_job(job: *mut c_void, decode_status: DecodeStatus, operation: &'static str) {
     if job.is_null() {
         log::error!("Job pointer is null in callback.");
         return;
@@ -312,19 +449,31 @@ extern "C" fn async_decode_callback(job: *mut c_void /* AsyncDecompressJob * */,
         return;
     }
 
-    // Safety: `ud` points to the State inside an Arc held by the Future.
-    let state: &mut State<AsyncDecompressJob> = unsafe { &mut *(ud as *mut State<AsyncDecompressJob>) };
+    // Safety: `ud` is the reference created with Arc::into_raw when the job was submitted.
+    let state: Arc<State<AsyncDecompressJob>> = unsafe { Arc::from_raw(ud as *const State<AsyncDecompressJob>) };
+    let tracker = state.tracker.clone();
+    let scope = state.enter_span();
 
     // Store the result and signal completion
-    {
-        let mut lock = state.result.lock().unwrap();
-        let org_job = state.job.take().unwrap();
-        if decode_status == DecodeStatus::Ok {
-            *lock = Some(Ok(org_job));
-        } else {
-            *lock = Some(Err(RedError::from(decode_status)));
-        }
-    }
-    state.done.store(true, Ordering::Release);
-    state.waker.wake();
+    let org_job = state.job.lock().unwrap().take().unwrap();
+    tracker.detach(org_job.as_mut_ptr());
+    let late = if decode_status == DecodeStatus::Ok {
+        state.finish_metrics(Ok::<_, DecodeStatus>(org_job.output_buffer_size()));
+        state.complete(Ok(org_job))
+    } else {
+        state.finish_metrics(Err(decode_status));
+        let error = RedError::from(decode_status).with_context(AsyncDecompressJob::error_context(Some(&org_job), operation).status(decode_status.code()));
+        state.complete(Err(DecodeFailure { job: Some(org_job), error }))
+    };
+    drop(scope);
+    // Releasing the job here would delete it inside its own callback. Finishing the job
+    // allows the decoder and then the SDK to be released, so this must be the very last step.
+    tracker.finish(Box::new((state, late)));
+}
+
+/// Ask the SDK to abort all jobs which didn't finish yet
+fn abort_pending(tracker: &JobTracker) {
+    tracker.for_each(|job_ptr| {
+        cpp!(unsafe [job_ptr as "R3DSDK::AsyncDecompressJob *"] { job_ptr->AbortDecode = true; })
+    });
 }
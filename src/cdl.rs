@@ -0,0 +1,398 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+//! ASC CDL interchange: `.cdl` (ColorDecisionList), `.ccc` (ColorCorrectionCollection), `.cc` (ColorCorrection)
+//! and CDL values embedded in EDL comments (`*ASC_SOP`, `*ASC_SAT`).
+
+use std::fmt::Write;
+use std::path::Path;
+use crate::{ Clip, ImageProcessingSettings, RangeError, RedError, RedResult, enums::* };
+
+/// Single ASC CDL correction, mapped to the IPP2 CDL fields of [`ImageProcessingSettings`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorCorrection {
+    /// `id` attribute of the ColorCorrection. For EDLs, the clip name of the event (`* FROM CLIP NAME:`) or the event number.
+    pub id: Option<String>,
+    pub description: Option<String>,
+    pub red: SlopeOffsetPower,
+    pub green: SlopeOffsetPower,
+    pub blue: SlopeOffsetPower,
+    pub saturation: f32,
+}
+
+/// XML flavour of an ASC CDL file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CdlFormat {
+    /// `.cdl`, ColorDecisionList with a ColorDecision for every correction
+    ColorDecisionList,
+    /// `.ccc`, ColorCorrectionCollection
+    ColorCorrectionCollection,
+    /// `.cc`, a single ColorCorrection
+    ColorCorrection,
+}
+
+/// Corrections read from an ASC CDL file or an EDL, in the order they appear in the file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CdlDocument {
+    pub corrections: Vec<ColorCorrection>,
+}
+
+impl Default for ColorCorrection {
+    /// Identity correction
+    fn default() -> Self {
+        let sop = SlopeOffsetPower { slope: 1.0, offset: 0.0, power: 1.0 };
+        Self { id: None, description: None, red: sop, green: sop, blue: sop, saturation: 1.0 }
+    }
+}
+
+impl ColorCorrection {
+    /// CDL values of the settings, `cdl_enabled` is not checked
+    pub fn from_settings(s: &ImageProcessingSettings) -> Self {
+        Self { red: *s.cdl_red(), green: *s.cdl_green(), blue: *s.cdl_blue(), saturation: s.cdl_saturation(), ..Default::default() }
+    }
+
+    /// Copy the CDL values to the settings and enable the CDL. The CDL is only used by the IPP2 pipeline (ColorVersion3).
+    /// Values outside of `ImageProcessingLimits` are rejected and `s` is left untouched.
+    pub fn apply_to(&self, s: &mut ImageProcessingSettings) -> Result<(), RangeError> {
+        let mut out = *s;
+        out.try_set_cdl_red(self.red)?;
+        out.try_set_cdl_green(self.green)?;
+        out.try_set_cdl_blue(self.blue)?;
+        out.try_set_cdl_saturation(self.saturation)?;
+        out.set_cdl_enabled(true);
+        *s = out;
+        Ok(())
+    }
+
+    /// `*ASC_SOP` and `*ASC_SAT` EDL comment lines for this correction
+    pub fn to_edl_comments(&self) -> String {
+        let [r, g, b] = [&self.red, &self.green, &self.blue];
+        format!("*ASC_SOP ({:.6} {:.6} {:.6})({:.6} {:.6} {:.6})({:.6} {:.6} {:.6})\n*ASC_SAT {:.6}\n",
+            r.slope, g.slope, b.slope, r.offset, g.offset, b.offset, r.power, g.power, b.power, self.saturation)
+    }
+
+    fn write_xml(&self, out: &mut String, indent: &str, xmlns: bool) {
+        let [r, g, b] = [&self.red, &self.green, &self.blue];
+        let _ = write!(out, "{indent}<ColorCorrection");
+        if xmlns {
+            let _ = write!(out, " xmlns=\"{XMLNS}\"");
+        }
+        if let Some(id) = &self.id {
+            let _ = write!(out, " id=\"{}\"", escape(id));
+        }
+        out.push_str(">\n");
+        if let Some(description) = &self.description {
+            let _ = writeln!(out, "{indent}    <Description>{}</Description>", escape(description));
+        }
+        let _ = writeln!(out, "{indent}    <SOPNode>");
+        let _ = writeln!(out, "{indent}        <Slope>{:.6} {:.6} {:.6}</Slope>", r.slope, g.slope, b.slope);
+        let _ = writeln!(out, "{indent}        <Offset>{:.6} {:.6} {:.6}</Offset>", r.offset, g.offset, b.offset);
+        let _ = writeln!(out, "{indent}        <Power>{:.6} {:.6} {:.6}</Power>", r.power, g.power, b.power);
+        let _ = writeln!(out, "{indent}    </SOPNode>");
+        let _ = writeln!(out, "{indent}    <SatNode>");
+        let _ = writeln!(out, "{indent}        <Saturation>{:.6}</Saturation>", self.saturation);
+        let _ = writeln!(out, "{indent}    </SatNode>");
+        let _ = writeln!(out, "{indent}</ColorCorrection>");
+    }
+}
+
+impl CdlFormat {
+    /// Format for the `.cdl`, `.ccc` and `.cc` file extensions (case insensitive)
+    pub fn from_extension(path: impl AsRef<Path>) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "cdl" => Some(Self::ColorDecisionList),
+            "ccc" => Some(Self::ColorCorrectionCollection),
+            "cc"  => Some(Self::ColorCorrection),
+            _ => None,
+        }
+    }
+}
+
+const XMLNS: &str = "urn:ASC:CDL:v1.01";
+
+impl CdlDocument {
+    /// Parse an ASC CDL XML document (ColorDecisionList, ColorCorrectionCollection or ColorCorrection).
+    /// Missing SOP or saturation nodes are identity. ColorCorrectionRef elements are not resolved.
+    pub fn parse(xml: &str) -> RedResult<Self> {
+        let doc = roxmltree::Document::parse(xml).map_err(|e| parse_error(e.to_string()))?;
+        let root = doc.root_element();
+        if !matches!(root.tag_name().name(), "ColorDecisionList" | "ColorCorrectionCollection" | "ColorCorrection") {
+            return Err(parse_error(format!("unexpected root element <{}>", root.tag_name().name())));
+        }
+        let corrections = root.descendants()
+            .filter(|x| x.tag_name().name() == "ColorCorrection")
+            .map(parse_correction)
+            .collect::<RedResult<Vec<_>>>()?;
+        if corrections.is_empty() {
+            return Err(parse_error("no ColorCorrection found".into()));
+        }
+        Ok(Self { corrections })
+    }
+
+    /// Parse the `*ASC_SOP` and `*ASC_SAT` comments of a CMX3600 EDL, one correction per event which has them
+    pub fn parse_edl(edl: &str) -> RedResult<Self> {
+        let mut corrections = Vec::new();
+        let mut event: Option<(String, Option<String>, Option<ColorCorrection>)> = None;
+        let finish = |event: Option<(String, Option<String>, Option<ColorCorrection>)>, corrections: &mut Vec<ColorCorrection>| {
+            if let Some((number, clip_name, Some(cc))) = event {
+                corrections.push(ColorCorrection { id: Some(clip_name.unwrap_or(number)), ..cc });
+            }
+        };
+        for (line_no, line) in edl.lines().enumerate() {
+            let line = line.trim();
+            let edl_error = |message: &str| RedError::Parse { format: "EDL", message: format!("line {}: {message}", line_no + 1) };
+            if let Some(comment) = line.strip_prefix('*') {
+                let comment = comment.trim_start();
+                let Some((number, clip_name, cc)) = event.as_mut() else { continue };
+                if let Some(sop) = comment.strip_prefix("ASC_SOP") {
+                    let v = sop.replace(['(', ')'], " ").split_whitespace().map(str::parse::<f32>).collect::<Result<Vec<_>, _>>().map_err(|_| edl_error("invalid ASC_SOP"))?;
+                    let [rs, gs, bs, ro, go, bo, rp, gp, bp] = v[..] else { return Err(edl_error("ASC_SOP needs 9 values")) };
+                    let cc = cc.get_or_insert_with(|| ColorCorrection { description: Some(format!("EDL event {number}")), ..Default::default() });
+                    cc.red   = SlopeOffsetPower { slope: rs, offset: ro, power: rp };
+                    cc.green = SlopeOffsetPower { slope: gs, offset: go, power: gp };
+                    cc.blue  = SlopeOffsetPower { slope: bs, offset: bo, power: bp };
+                } else if let Some(sat) = comment.strip_prefix("ASC_SAT") {
+                    let sat = sat.trim().parse::<f32>().map_err(|_| edl_error("invalid ASC_SAT"))?;
+                    cc.get_or_insert_with(|| ColorCorrection { description: Some(format!("EDL event {number}")), ..Default::default() }).saturation = sat;
+                } else if let Some(name) = comment.strip_prefix("FROM CLIP NAME:") {
+                    *clip_name = Some(name.trim().to_owned());
+                }
+            } else if let Some(number) = line.split_whitespace().next().filter(|x| x.bytes().all(|c| c.is_ascii_digit())) {
+                finish(event.take(), &mut corrections);
+                event = Some((number.to_owned(), None, None));
+            }
+        }
+        finish(event, &mut corrections);
+        if corrections.is_empty() {
+            return Err(RedError::Parse { format: "EDL", message: "no ASC_SOP or ASC_SAT found".into() });
+        }
+        Ok(Self { corrections })
+    }
+
+    /// Read a `.cdl`, `.ccc`, `.cc` or `.edl` file. Files with other extensions are parsed as XML.
+    pub fn from_path(path: impl AsRef<Path>) -> RedResult<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        if path.extension().is_some_and(|x| x.eq_ignore_ascii_case("edl")) {
+            Self::parse_edl(&text)
+        } else {
+            Self::parse(&text)
+        }
+    }
+
+    /// Correction with the given `id`
+    pub fn get(&self, id: &str) -> Option<&ColorCorrection> {
+        self.corrections.iter().find(|x| x.id.as_deref() == Some(id))
+    }
+
+    /// Serialize to ASC CDL XML. [`CdlFormat::ColorCorrection`] only writes the first correction.
+    pub fn to_xml(&self, format: CdlFormat) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        match format {
+            CdlFormat::ColorDecisionList => {
+                let _ = writeln!(out, "<ColorDecisionList xmlns=\"{XMLNS}\">");
+                for cc in &self.corrections {
+                    out.push_str("    <ColorDecision>\n");
+                    cc.write_xml(&mut out, "        ", false);
+                    out.push_str("    </ColorDecision>\n");
+                }
+                out.push_str("</ColorDecisionList>\n");
+            }
+            CdlFormat::ColorCorrectionCollection => {
+                let _ = writeln!(out, "<ColorCorrectionCollection xmlns=\"{XMLNS}\">");
+                for cc in &self.corrections {
+                    cc.write_xml(&mut out, "    ", false);
+                }
+                out.push_str("</ColorCorrectionCollection>\n");
+            }
+            CdlFormat::ColorCorrection => {
+                self.corrections.first().cloned().unwrap_or_default().write_xml(&mut out, "", true);
+            }
+        }
+        out
+    }
+
+    /// Write the corrections in the format given by the file extension (`.cdl`, `.ccc` or `.cc`).
+    /// Returns `InvalidPath` for other extensions.
+    pub fn write(&self, path: impl AsRef<Path>) -> RedResult<()> {
+        let format = CdlFormat::from_extension(&path).ok_or(RedError::InvalidPath)?;
+        std::fs::write(path, self.to_xml(format))?;
+        Ok(())
+    }
+}
+
+impl Clip {
+    /// Read the `.cdl` sidecar file next to the clip (see [`Clip::file_list`]), `None` if there is none.
+    pub fn cdl_sidecar(&self) -> RedResult<Option<CdlDocument>> {
+        let path = self.file_list().into_iter()
+            .find(|(file_type, path)| *file_type == FileType::Metadata && CdlFormat::from_extension(path) == Some(CdlFormat::ColorDecisionList));
+        path.map(|(_, path)| CdlDocument::from_path(path)).transpose()
+    }
+}
+
+fn parse_error(message: String) -> RedError {
+    RedError::Parse { format: "ASC CDL", message }
+}
+
+fn parse_correction(node: roxmltree::Node) -> RedResult<ColorCorrection> {
+    fn child<'a, 'input>(parent: roxmltree::Node<'a, 'input>, names: &[&str]) -> Option<roxmltree::Node<'a, 'input>> {
+        parent.children().find(|x| names.contains(&x.tag_name().name()))
+    }
+    let text = |node: roxmltree::Node| node.text().unwrap_or_default().trim().to_owned();
+    let values = |node: Option<roxmltree::Node>, name: &str, default: f32| -> RedResult<[f32; 3]> {
+        let Some(node) = node else { return Ok([default; 3]) };
+        let v = node.text().unwrap_or_default().split_whitespace().map(str::parse::<f32>).collect::<Result<Vec<_>, _>>()
+            .map_err(|e| parse_error(format!("<{name}>: {e}")))?;
+        v.try_into().map_err(|_| parse_error(format!("<{name}> needs 3 values")))
+    };
+
+    let mut cc = ColorCorrection {
+        id: node.attribute("id").map(str::to_owned),
+        description: child(node, &["Description"]).map(text),
+        ..Default::default()
+    };
+    if let Some(sop) = child(node, &["SOPNode"]) {
+        let slope  = values(child(sop, &["Slope"]),  "Slope",  1.0)?;
+        let offset = values(child(sop, &["Offset"]), "Offset", 0.0)?;
+        let power  = values(child(sop, &["Power"]),  "Power",  1.0)?;
+        cc.red   = SlopeOffsetPower { slope: slope[0], offset: offset[0], power: power[0] };
+        cc.green = SlopeOffsetPower { slope: slope[1], offset: offset[1], power: power[1] };
+        cc.blue  = SlopeOffsetPower { slope: slope[2], offset: offset[2], power: power[2] };
+    }
+    // `SATNode` is used by v1.01 of the spec, `SatNode` by the later versions
+    if let Some(saturation) = child(node, &["SatNode", "SATNode"]).and_then(|x| child(x, &["Saturation"])) {
+        let v = text(saturation);
+        cc.saturation = v.parse().map_err(|_| parse_error(format!("<Saturation>: invalid value {v:?}")))?;
+    }
+    Ok(cc)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sop(slope: f32, offset: f32, power: f32) -> SlopeOffsetPower {
+        SlopeOffsetPower { slope, offset, power }
+    }
+
+    #[test]
+    fn parse_xml() {
+        let doc = CdlDocument::parse(r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ColorDecisionList xmlns="urn:ASC:CDL:v1.01">
+                <ColorDecision>
+                    <ColorCorrection id="A001_C003">
+                        <Description>Day &amp; night</Description>
+                        <SOPNode>
+                            <Slope>1.1 1.2 1.3</Slope>
+                            <Offset>-0.01 0.02 0.03</Offset>
+                            <Power>0.9 1.0 1.1</Power>
+                        </SOPNode>
+                        <SATNode><Saturation>0.8</Saturation></SATNode>
+                    </ColorCorrection>
+                </ColorDecision>
+                <ColorDecision>
+                    <ColorCorrection id="A001_C004">
+                        <SatNode><Saturation> 1.25 </Saturation></SatNode>
+                    </ColorCorrection>
+                </ColorDecision>
+            </ColorDecisionList>"#).unwrap();
+        assert_eq!(doc.corrections.len(), 2);
+        let cc = doc.get("A001_C003").unwrap();
+        assert_eq!(cc.description.as_deref(), Some("Day & night"));
+        assert_eq!((cc.red, cc.green, cc.blue), (sop(1.1, -0.01, 0.9), sop(1.2, 0.02, 1.0), sop(1.3, 0.03, 1.1)));
+        assert_eq!(cc.saturation, 0.8);
+        // Missing SOP node is identity
+        assert_eq!(doc.get("A001_C004").unwrap(), &ColorCorrection { id: Some("A001_C004".into()), saturation: 1.25, ..Default::default() });
+        assert!(doc.get("A001_C005").is_none());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let message = |xml: &str| match CdlDocument::parse(xml) {
+            Err(RedError::Parse { format: "ASC CDL", message }) => message,
+            x => panic!("unexpected {x:?}"),
+        };
+        assert_eq!(message("<ColorCorrectionCollection/>"), "no ColorCorrection found");
+        assert_eq!(message("<Foo/>"), "unexpected root element <Foo>");
+        assert_eq!(message("<ColorCorrection><SOPNode><Slope>1 1</Slope></SOPNode></ColorCorrection>"), "<Slope> needs 3 values");
+        assert!(message("<ColorCorrection><SatNode><Saturation>x</Saturation></SatNode></ColorCorrection>").starts_with("<Saturation>: invalid value"));
+        assert!(CdlDocument::parse("<ColorCorrection>").is_err());
+    }
+
+    #[test]
+    fn xml_round_trip() {
+        let doc = CdlDocument { corrections: vec![
+            ColorCorrection { id: Some("a<b>&\"c\"".into()), description: Some("<Look> & more".into()),
+                              red: sop(1.5, -0.25, 0.75), green: sop(1.0, 0.0, 1.0), blue: sop(0.5, 0.125, 2.0), saturation: 0.5 },
+            ColorCorrection { id: Some("second".into()), ..Default::default() },
+        ] };
+        for format in [CdlFormat::ColorDecisionList, CdlFormat::ColorCorrectionCollection] {
+            assert_eq!(CdlDocument::parse(&doc.to_xml(format)).unwrap(), doc, "{format:?}");
+        }
+        let single = CdlDocument::parse(&doc.to_xml(CdlFormat::ColorCorrection)).unwrap();
+        assert_eq!(single.corrections, doc.corrections[..1]);
+        assert!(doc.to_xml(CdlFormat::ColorCorrection).contains(&format!("<ColorCorrection xmlns=\"{XMLNS}\"")));
+    }
+
+    #[test]
+    fn escape_xml() {
+        assert_eq!(escape("a & b < c > d \"e\""), "a &amp; b &lt; c &gt; d &quot;e&quot;");
+        assert_eq!(escape("&amp;"), "&amp;amp;");
+        assert_eq!(escape("plain"), "plain");
+    }
+
+    #[test]
+    fn parse_edl() {
+        let edl = "TITLE: Day 1\nFCM: NON-DROP FRAME\n\n\
+            001  A001C003 V     C        01:00:00:00 01:00:05:00 00:00:00:00 00:00:05:00\n\
+            * FROM CLIP NAME: A001_C003\n\
+            *ASC_SOP (1.1 1.2 1.3)(-0.01 0.02 0.03)(0.9 1.0 1.1)\n\
+            *ASC_SAT 0.8\n\n\
+            002  A001C004 V     C        01:00:10:00 01:00:12:00 00:00:05:00 00:00:07:00\n\
+            * FROM CLIP NAME: A001_C004\n\n\
+            003  A001C005 V     C        01:00:20:00 01:00:22:00 00:00:07:00 00:00:09:00\n\
+            * ASC_SAT 1.5\n";
+        let doc = CdlDocument::parse_edl(edl).unwrap();
+        // Event 002 has no ASC lines and is skipped
+        assert_eq!(doc.corrections.len(), 2);
+        let cc = doc.get("A001_C003").unwrap();
+        assert_eq!(cc.description.as_deref(), Some("EDL event 001"));
+        assert_eq!((cc.red, cc.green, cc.blue, cc.saturation), (sop(1.1, -0.01, 0.9), sop(1.2, 0.02, 1.0), sop(1.3, 0.03, 1.1), 0.8));
+        // Without a clip name the event number is the id
+        assert_eq!(doc.get("003").unwrap(), &ColorCorrection { id: Some("003".into()), description: Some("EDL event 003".into()), saturation: 1.5, ..Default::default() });
+
+        let round_trip = CdlDocument::parse_edl(&format!("001  A V C 01:00:00:00 01:00:01:00 00:00:00:00 00:00:01:00\n{}", cc.to_edl_comments())).unwrap();
+        assert_eq!(round_trip.corrections[0], ColorCorrection { id: Some("001".into()), ..cc.clone() });
+
+        assert!(matches!(CdlDocument::parse_edl("001  A V C\n* FROM CLIP NAME: x\n"), Err(RedError::Parse { format: "EDL", .. })));
+        match CdlDocument::parse_edl("001  A V C\n*ASC_SOP (1 1 1)(0 0 0)\n") {
+            Err(RedError::Parse { message, .. }) => assert_eq!(message, "line 2: ASC_SOP needs 9 values"),
+            x => panic!("unexpected {x:?}"),
+        }
+    }
+
+    #[test]
+    fn apply_to_settings() {
+        let mut settings = ImageProcessingSettings::default();
+        let cc = ColorCorrection { red: sop(1.5, 0.1, 0.9), saturation: 1.2, ..Default::default() };
+        cc.apply_to(&mut settings).unwrap();
+        assert!(settings.cdl_enabled());
+        assert_eq!((*settings.cdl_red(), *settings.cdl_green(), settings.cdl_saturation()), (cc.red, cc.green, 1.2));
+        assert_eq!(ColorCorrection::from_settings(&settings), cc);
+
+        // Out of range values are rejected without touching the settings
+        let mut settings = ImageProcessingSettings::default();
+        let bad = ColorCorrection { red: sop(1.5, 0.1, 0.9), blue: sop(1.0, 20.0, 1.0), ..Default::default() };
+        let e = bad.apply_to(&mut settings).unwrap_err();
+        assert_eq!((e.field, e.value), ("cdl_blue.offset", 20.0));
+        assert!(!settings.cdl_enabled());
+        assert_eq!(*settings.cdl_red(), sop(1.0, 0.0, 1.0));
+        let e = ColorCorrection { saturation: -1.0, ..Default::default() }.apply_to(&mut settings).unwrap_err();
+        assert_eq!(e.field, "cdl_saturation");
+    }
+}
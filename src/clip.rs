@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
-use crate::{RedResult, RedError, metadata::*};
+use crate::{RedResult, RedError, ErrorContext, Sdk, metadata::*, metrics::*};
 use cpp::*;
 use core::ffi::c_void;
 use crate::enums::*;
@@ -13,17 +13,35 @@ cpp!{{
 }}
 
 cpp_class! {
-	/// Class for loading a clip, retrieving information and extracting images.
-	/// This class is thread-safe as long as no call to LoadFrom() or Close()
-	/// is in progress.
-	pub unsafe struct Clip as "std::unique_ptr<R3DSDK::Clip>"
+	unsafe struct ClipPtr as "std::unique_ptr<R3DSDK::Clip>"
 }
+
+/// Class for loading a clip, retrieving information and extracting images.
+/// This class is thread-safe as long as no call to LoadFrom() or Close()
+/// is in progress.
+///
+/// The clip keeps the SDK initialized until it's dropped.
+#[repr(C)]
+pub struct Clip {
+	// Must be the first field: the cpp! blocks take `&Clip` as a pointer to the std::unique_ptr
+	ptr: ClipPtr,
+	_sdk: Sdk,
+	/// Path of the loaded clip, for error context
+	path: Option<String>,
+}
+// SAFETY: the SDK allows concurrent use of a Clip, except for LoadFrom() and Close().
+// These, as well as the timecode getters which return an internal buffer, take &mut self.
+unsafe impl Send for Clip {}
+unsafe impl Sync for Clip {}
+
 impl Clip {
     /// Create a new Clip instance. Use this if you do not want to load a clip
 	/// when constructing this class. Use LoadFrom() to load a clip before
 	/// calling any other functions.
-    pub fn new() -> Self {
-        cpp!(unsafe [] -> Clip as "std::unique_ptr<R3DSDK::Clip>" { return std::make_unique<R3DSDK::Clip>(); })
+    pub fn new() -> RedResult<Self> {
+        let sdk = Sdk::current()?;
+        let ptr = cpp!(unsafe [] -> ClipPtr as "std::unique_ptr<R3DSDK::Clip>" { return std::make_unique<R3DSDK::Clip>(); });
+        Ok(Self { ptr, _sdk: sdk, path: None })
     }
 
 
@@ -34,23 +52,34 @@ impl Clip {
 	/// Create a new Clip instance from a file path. Use this if you want to load a clip from a UTF-8
 	/// path when you construct the class. Load status can be checked through Status().
     pub fn from_path(path: &str) -> RedResult<Self> {
-        let c_path = std::ffi::CString::new(path).unwrap();
+        let context = || ErrorContext::new("Clip::from_path").clip_path(Some(path));
+        let c_path = std::ffi::CString::new(path).map_err(|_| RedError::ClipPathNotFound.with_context(context()))?;
 		let c_ptr = c_path.as_ptr();
-        let clip = cpp!(unsafe [c_ptr as "const char *"] -> Clip as "std::unique_ptr<R3DSDK::Clip>" { return std::make_unique<R3DSDK::Clip>(c_ptr); });
+        let sdk = Sdk::current().map_err(|e| e.with_context(context()))?;
+        let ptr = cpp!(unsafe [c_ptr as "const char *"] -> ClipPtr as "std::unique_ptr<R3DSDK::Clip>" { return std::make_unique<R3DSDK::Clip>(c_ptr); });
+        let clip = Self { ptr, _sdk: sdk, path: Some(path.to_owned()) };
         let status = clip.status();
         if status == LoadStatus::ClipLoaded {
             Ok(clip)
         } else {
-            Err(status.into())
+            Err(RedError::from(status).with_context(context().status(status.code())))
         }
     }
 
+	/// Path the clip was loaded from, `None` if no clip was loaded
+	pub fn path(&self) -> Option<&str> {
+		self.path.as_deref()
+	}
+
+	/// Error context for a failed call on this clip
+	pub(crate) fn error_context(&self, operation: &'static str) -> ErrorContext {
+		ErrorContext::new(operation).clip_path(self.path())
+	}
+
 	pub fn status(&self) -> LoadStatus {
-		unsafe {
-			std::mem::transmute(cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *"] -> i32 as "int" {
-				return (int)(*self)->Status();
-			}))
-		}
+		LoadStatus::from(cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *"] -> i32 as "int" {
+			return (int)(*self)->Status();
+		}))
 	}
 
     /// Load the R3D file from the given UTF-8 path. If there are any other R3D
@@ -58,21 +87,25 @@ impl Clip {
 	/// If a clip was already open it will be closed before opening the
 	/// one specified in the pathToFile parameter.
     pub fn load_from(&mut self, path: &str) -> RedResult<()> {
-        let c_path = std::ffi::CString::new(path).unwrap();
+        let context = || ErrorContext::new("Clip::load_from").clip_path(Some(path));
+        let c_path = std::ffi::CString::new(path).map_err(|_| RedError::ClipPathNotFound.with_context(context()))?;
 		let c_ptr = c_path.as_ptr();
-        let status: LoadStatus = unsafe { std::mem::transmute(cpp!([self as "const std::unique_ptr<R3DSDK::Clip> *", c_ptr as "const char *"] -> i32 as "int" {
+        let status = LoadStatus::from(cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *", c_ptr as "const char *"] -> i32 as "int" {
             return (int)(*self)->LoadFrom(c_ptr);
-        })) };
+        }));
         if status == LoadStatus::ClipLoaded {
+            self.path = Some(path.to_owned());
             Ok(())
         } else {
-            Err(status.into())
+            self.path = None;
+            Err(RedError::from(status).with_context(context().status(status.code())))
         }
     }
 
 	/// Closes any open files (class destructor will also call this function).
 	pub fn close(&mut self) {
-        cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *"]  { (*self)->Close(); })
+        cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *"]  { (*self)->Close(); });
+        self.path = None;
     }
 
 	/// Closes all handles for all R3D parts belonging to the clip. Handles
@@ -99,18 +132,44 @@ impl Clip {
             Some(c_str.to_str().unwrap().to_string())
         }
     }
+
+	/// Clip files (.R3D or .NEV, there's always at least 1 of these) and the optional sidecar / external files
+	/// (.RMD, .cdl, .cube, .wav). Any other files in the same folder are ignored.
+	/// Returns an empty list if no clip has been (succesfully) loaded.
+	pub fn file_list(&self) -> Vec<(FileType, String)> {
+		let count = cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *"] -> usize as "size_t" { return (*self)->FileListCount(); });
+		let mut result = Vec::with_capacity(count);
+		for i in 0..count {
+			let mut file_type: i32 = 0;
+			let file_type_ptr = &mut file_type;
+			let cppstr = cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *", i as "size_t", file_type_ptr as "int *"] -> *mut c_void as "void *" {
+				std::string * path = new std::string();
+				*file_type_ptr = (int)(*self)->FileList(i, *path);
+				return path;
+			});
+			let c_ptr = cpp!(unsafe [cppstr as "std::string *"] -> *const core::ffi::c_char as "const char *" { return cppstr->c_str(); });
+			let path = unsafe { std::ffi::CStr::from_ptr(c_ptr) }.to_string_lossy().into_owned();
+			cpp!(unsafe [cppstr as "std::string *"] { delete cppstr; });
+			// types unknown to this crate are skipped
+			if let Ok(file_type) = FileType::try_from(file_type) && file_type != FileType::Invalid {
+				result.push((file_type, path));
+			}
+		}
+		result
+	}
+
 	/// Checks frame CRC if present. For HDRx both tracks are checked. CRC specific return values:
 	///
 	/// DSDecodeOK             : CRC present and matched
 	/// DSDecodeFailed         : CRC present and check failed
 	/// DSUnsupportedClipFormat: clip/frame does not have CRC
     pub fn check_frame(&self, video_frame_no: usize) -> RedResult<()> {
-		let status: DecodeStatus = unsafe { std::mem::transmute(cpp!([self as "const std::unique_ptr<R3DSDK::Clip> *", video_frame_no as "size_t"] -> i32 as "int" {
+		let status = DecodeStatus::from(cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *", video_frame_no as "size_t"] -> i32 as "int" {
 			return (int)(*self)->CheckFrame(video_frame_no);
-		})) };
+		}));
         match status {
             DecodeStatus::Ok => Ok(()),
-            status => Err(status.into()),
+            status => Err(RedError::from(status).with_context(self.error_context("Clip::check_frame").video_frame_no(video_frame_no).status(status.code()))),
         }
     }
     pub fn extended_highlights_enabled(&self) -> bool {
@@ -121,7 +180,7 @@ impl Clip {
             return (*self)->SetExtendedHighlights(enabled);
         }) {
             true => Ok(true),
-            false => Err(RedError::Other("Failed to set extended highlights".into())),
+            false => Err(RedError::ExtendedHighlightsUnsupported.with_context(self.error_context("Clip::set_extended_highlights"))),
         }
     }
 
@@ -212,8 +271,54 @@ impl Clip {
 		hdr_settings: Option<&HdrProcessingSettings>,
 		buffer: Option<&mut AlignedBuffer>,
 	) -> RedResult<Option<AlignedBuffer>> {
+		self.decode_frame(None, video_frame_no, mode, pixel_type, image_settings, hdr_settings, buffer)
+	}
+
+	// ******************************
+	// MULTI-TRACK VIDEO DECODING
+	// Can be used to do manual HDR blending or decode a single track
+	// ******************************
+
+	/// Decode the given video frame on the given track with the supplied decode
+	/// settings, output buffer and image processing settings (through decodeJob)
+	///
+	/// Buffer handling is the same as in [`decode_video_frame`](Self::decode_video_frame).
+	#[allow(clippy::too_many_arguments)]
+	pub fn video_track_decode_frame(
+		&self,
+		video_track_no: usize,
+		video_frame_no: usize,
+		mode: VideoDecodeMode,
+		pixel_type: VideoPixelType,
+		image_settings: Option<&ImageProcessingSettings>,
+		hdr_settings: Option<&HdrProcessingSettings>,
+		buffer: Option<&mut AlignedBuffer>,
+	) -> RedResult<Option<AlignedBuffer>> {
+		self.decode_frame(Some(video_track_no), video_frame_no, mode, pixel_type, image_settings, hdr_settings, buffer)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn decode_frame(
+		&self,
+		video_track_no: Option<usize>,
+		video_frame_no: usize,
+		mode: VideoDecodeMode,
+		pixel_type: VideoPixelType,
+		image_settings: Option<&ImageProcessingSettings>,
+		hdr_settings: Option<&HdrProcessingSettings>,
+		buffer: Option<&mut AlignedBuffer>,
+	) -> RedResult<Option<AlignedBuffer>> {
+		let context = || {
+			let operation = if video_track_no.is_some() { "Clip::video_track_decode_frame" } else { "Clip::decode_video_frame" };
+			let context = self.error_context(operation).video_frame_no(video_frame_no);
+			match video_track_no {
+				Some(video_track_no) => context.video_track_no(video_track_no),
+				None => context,
+			}
+		};
+
 		// Validate combination and compute needed size based on the clip dimensions.
-		let size_needed = self.calculate_buffer_size(&mode, &pixel_type)?;
+		let size_needed = self.calculate_buffer_size(&mode, &pixel_type).map_err(|e| e.with_context(context()))?;
 
 		// Build the decode job
 		let mut job = VideoDecodeJob::default();
@@ -230,29 +335,25 @@ impl Clip {
 		match buffer {
 			Some(out) => {
 				if out.len() < size_needed {
-					return Err(RedError::BufferTooSmall { needed: size_needed, provided: out.len() });
+					return Err(RedError::BufferTooSmall { needed: size_needed, provided: out.len() }.with_context(context()));
 				}
 				job.set_output_buffer(out.ptr as *mut _, size_needed);
 
 
-				let result: DecodeStatus = unsafe { std::mem::transmute(cpp!([self as "const std::unique_ptr<R3DSDK::Clip> *", video_frame_no as "size_t", job_ref as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" {
-					return (int)(*self)->DecodeVideoFrame(video_frame_no, *job_ref);
-				})) };
+				let result = self.decode_job(video_track_no, video_frame_no, job_ref, size_needed);
 				match result {
 					DecodeStatus::Ok => Ok(None),
-					s => Err(s.into()),
+					s => Err(RedError::from(s).with_context(context().status(s.code()))),
 				}
 			}
 			None => {
 				// Allocate an aligned buffer internally (over-allocate and align the pointer)
-    			let buf = AlignedBuffer::new(size_needed, 16)?;
+    			let buf = AlignedBuffer::new(size_needed, 16).map_err(|e| e.with_context(context()))?;
 				job.set_output_buffer(buf.ptr as *mut _, buf.len());
 
-				let status: DecodeStatus = unsafe { std::mem::transmute(cpp!([self as "const std::unique_ptr<R3DSDK::Clip> *", video_frame_no as "size_t", job_ref as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" {
-					return (int)(*self)->DecodeVideoFrame(video_frame_no, *job_ref);
-				})) };
+				let status = self.decode_job(video_track_no, video_frame_no, job_ref, buf.len());
 				if status != DecodeStatus::Ok {
-					return Err(status.into());
+					return Err(RedError::from(status).with_context(context().status(status.code())));
 				}
 
 				Ok(Some(buf))
@@ -260,6 +361,22 @@ impl Clip {
 		}
 	}
 
+	fn decode_job(&self, video_track_no: Option<usize>, video_frame_no: usize, job_ref: *const VideoDecodeJob, output_size: usize) -> DecodeStatus {
+		let metrics = JobMetrics::start(DecodeSource::Clip, video_frame_no);
+		let _scope = metrics.enter();
+		let status = match video_track_no {
+			None => cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *", video_frame_no as "size_t", job_ref as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" {
+				return (int)(*self)->DecodeVideoFrame(video_frame_no, *job_ref);
+			}),
+			Some(video_track_no) => cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *", video_track_no as "size_t", video_frame_no as "size_t", job_ref as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" {
+				return (int)(*self)->VideoTrackDecodeFrame(video_track_no, video_frame_no, *job_ref);
+			}),
+		};
+		let status = DecodeStatus::from(status);
+		metrics.finish(if status == DecodeStatus::Ok { Ok(output_size) } else { Err(status) });
+		status
+	}
+
 	// ******************************
 	// CLIP INFORMATION
 	// ******************************
@@ -423,12 +540,12 @@ impl Clip {
 	pub fn metadata_for_frame(&self, video_frame_no: usize) -> RedResult<Metadata> {
 		let mut metadata = Metadata::default();
 		let metadata_ptr = &mut metadata;
-		let status = unsafe { std::mem::transmute(cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *", video_frame_no as "size_t", metadata_ptr as "R3DSDK::Metadata *"] -> i32 as "int" {
-			return (*self)->GetFrameMetadata(*metadata_ptr, video_frame_no);
-		})) };
+		let status = DecodeStatus::from(cpp!(unsafe [self as "const std::unique_ptr<R3DSDK::Clip> *", video_frame_no as "size_t", metadata_ptr as "R3DSDK::Metadata *"] -> i32 as "int" {
+			return (int)(*self)->GetFrameMetadata(*metadata_ptr, video_frame_no);
+		}));
         match status {
             DecodeStatus::Ok => Ok(metadata),
-            status => Err(status.into()),
+            status => Err(RedError::from(status).with_context(self.error_context("Clip::metadata_for_frame").video_frame_no(video_frame_no).status(status.code()))),
         }
 	}
 	/// Retrieve a metadata item from the clip's metadata table.
@@ -437,9 +554,9 @@ impl Clip {
         unsafe {
             let c_key = std::ffi::CString::new(key).unwrap();
             let c_key = c_key.as_ptr();
-            let meta_type: MetadataType = std::mem::transmute(cpp!([self as "const std::unique_ptr<R3DSDK::Clip> *", c_key as "const char*"] -> i32 as "int" {
+            let meta_type = MetadataType::try_from(cpp!([self as "const std::unique_ptr<R3DSDK::Clip> *", c_key as "const char*"] -> i32 as "int" {
                 return (int)(*self)->MetadataItemType(c_key);
-            }));
+            })).unwrap_or(MetadataType::Invalid); // types unknown to this crate are skipped
             match meta_type {
                 MetadataType::Int => {
                     let value: u32 = cpp!([self as "const std::unique_ptr<R3DSDK::Clip> *", c_key as "const char*"] -> u32 as "unsigned int" {
@@ -511,6 +628,48 @@ impl Clip {
 	}
 }
 
+/// Reference-counted [`Clip`] that can be shared between threads.
+///
+/// Only the `&self` methods of [`Clip`] are reachable through it, so LoadFrom(), Close() and other
+/// functions which are not safe to call concurrently are statically prevented.
+/// Use [`SharedClip::try_unwrap`] to get back the owned [`Clip`] once all clones are dropped.
+#[derive(Clone)]
+pub struct SharedClip(std::sync::Arc<Clip>);
+
+impl SharedClip {
+	pub fn new(clip: Clip) -> Self {
+		Self(std::sync::Arc::new(clip))
+	}
+
+	/// Load the clip from a UTF-8 path, see [`Clip::from_path`].
+	pub fn from_path(path: &str) -> RedResult<Self> {
+		Ok(Self::new(Clip::from_path(path)?))
+	}
+
+	/// Returns the inner [`Clip`] if this is the only reference to it.
+	pub fn try_unwrap(this: Self) -> Result<Clip, Self> {
+		std::sync::Arc::try_unwrap(this.0).map_err(Self)
+	}
+
+	/// Number of `SharedClip` instances pointing to the same clip.
+	pub fn strong_count(this: &Self) -> usize {
+		std::sync::Arc::strong_count(&this.0)
+	}
+}
+
+impl std::ops::Deref for SharedClip {
+	type Target = Clip;
+	fn deref(&self) -> &Clip {
+		&self.0
+	}
+}
+
+impl From<Clip> for SharedClip {
+	fn from(clip: Clip) -> Self {
+		Self::new(clip)
+	}
+}
+
 /*
 	// Decode the given audio block into the supplied buffer. This audio block has 24- or 32-bit, see
 	// the RMD_SAMPLE_SIZE metadata, Big Endian audio. For 24-bit audio it will be MSB aligned in a
@@ -584,15 +743,6 @@ impl Clip {
 	// is out of bounds.
 	int GetFloatAudioDefaultConversionGain(size_t channelNo) const;
 
-	// ******************************
-	// MULTI-TRACK VIDEO DECODING
-	// Can be used to do manual HDR blending or decode a single track
-	// ******************************
-
-	// Decode the given video frame on the given track with the supplied decode
-	// settings, output buffer and image processing settings (through decodeJob)
-	DecodeStatus VideoTrackDecodeFrame(size_t videoTrackNo, size_t videoFrameNo, const VideoDecodeJob & decodeJob) const;
-
 	// ******************************
 	// XMP RMD FUNCTIONS
 	// ******************************
@@ -705,9 +855,10 @@ cpp_class! {
 }
 impl VideoDecodeJob {
 	/// Resolution/speed to decode the image at. This will also
-	/// influence how much memory is needed for the output buffer
-	pub fn mode(&self) -> VideoDecodeMode {
-		unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" { return (int)(*self).Mode; })) }
+	/// influence how much memory is needed for the output buffer.
+	/// `RedError::InvalidEnumValue` if the mode wasn't set yet.
+	pub fn mode(&self) -> RedResult<VideoDecodeMode> {
+		VideoDecodeMode::try_from(cpp!(unsafe [self as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" { return (int)(*self).Mode; }))
 	}
 	/// Resolution/speed to decode the image at. This will also
 	/// influence how much memory is needed for the output buffer
@@ -717,8 +868,8 @@ impl VideoDecodeJob {
 	}
 	/// Which pixel type to decode the image in. See the VideoPixelType enum for the available options. To get the image in the
 	/// original pixel type supported by earlier SDK's set PixelType_16Bit_RGB_Planar
-	pub fn pixel_type(&self) -> VideoPixelType {
-		unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" { return (int)(*self).PixelType; })) }
+	pub fn pixel_type(&self) -> RedResult<VideoPixelType> {
+		VideoPixelType::try_from(cpp!(unsafe [self as "const R3DSDK::VideoDecodeJob *"] -> i32 as "int" { return (int)(*self).PixelType; }))
 	}
 	/// Which pixel type to decode the image in. See the VideoPixelType enum for the available options. To get the image in the
 	/// original pixel type supported by earlier SDK's set PixelType_16Bit_RGB_Planar
@@ -825,6 +976,9 @@ pub struct AlignedBuffer {
 	pub ptr: *mut core::ffi::c_void,
 	pub layout: std::alloc::Layout
 }
+// SAFETY: the buffer exclusively owns its allocation.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
 
 impl AlignedBuffer {
 	pub fn new(size: usize, alignment: usize) -> RedResult<Self> {
@@ -832,7 +986,7 @@ impl AlignedBuffer {
 		let layout = std::alloc::Layout::from_size_align(size, alignment)?;
 		let ptr = unsafe { std::alloc::alloc(layout) } as *mut core::ffi::c_void;
 		if ptr.is_null() {
-			Err(RedError::Other(format!("Failed to allocate {size} aligned to {alignment} bytes")))
+			Err(RedError::AllocationFailed { size, alignment })
 		} else {
 			Ok(Self { ptr, layout })
 		}
@@ -844,6 +998,10 @@ impl AlignedBuffer {
 		let len = self.len() / std::mem::size_of::<T>();
 		unsafe { std::slice::from_raw_parts(self.ptr as *const T, len) }
 	}
+	pub fn as_mut_slice<T>(&mut self) -> &mut [T] {
+		let len = self.len() / std::mem::size_of::<T>();
+		unsafe { std::slice::from_raw_parts_mut(self.ptr as *mut T, len) }
+	}
 }
 impl Drop for AlignedBuffer {
 	fn drop(&mut self) {
@@ -0,0 +1,458 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+//! Adobe/Resolve `.cube` LUTs: parsing, writing and applying them to decoded frames on the CPU.
+
+use std::fmt::Write;
+use std::path::Path;
+use crate::{ RedError, RedResult, enums::* };
+
+/// 3D LUT interpolation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LutInterpolation {
+    /// 8 surrounding lattice points
+    Trilinear,
+    /// 4 lattice points of the enclosing tetrahedron, smoother along the neutral axis
+    #[default]
+    Tetrahedral,
+}
+
+/// `.cube` LUT with a 1D LUT, a 3D LUT or both (1D shaper applied first, as written by Resolve).
+///
+/// The input is scaled from `domain_min` -- `domain_max` to 0 -- 1 before every LUT stage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubeLut {
+    pub title: Option<String>,
+    pub domain_min: [f32; 3],
+    pub domain_max: [f32; 3],
+    /// `LUT_1D_SIZE` entries, empty if there's no 1D LUT
+    pub table_1d: Vec<[f32; 3]>,
+    /// `LUT_3D_SIZE`, 0 if there's no 3D LUT
+    pub size_3d: usize,
+    /// `size_3d`³ entries, red changing fastest, then green, then blue
+    pub table_3d: Vec<[f32; 3]>,
+}
+
+const MAX_1D_SIZE: usize = 65536;
+const MAX_3D_SIZE: usize = 256;
+
+fn cube_error(message: String) -> RedError {
+    RedError::Parse { format: "cube LUT", message }
+}
+
+/// Line without its `#` comment, a `#` in the quoted title is kept
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => { }
+        }
+    }
+    line
+}
+
+impl CubeLut {
+    /// 3D LUT which doesn't change the image
+    pub fn identity_3d(size: usize) -> Self {
+        let step = 1.0 / (size.max(2) - 1) as f32;
+        let mut table_3d = Vec::with_capacity(size * size * size);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    table_3d.push([r as f32 * step, g as f32 * step, b as f32 * step]);
+                }
+            }
+        }
+        Self { title: None, domain_min: [0.0; 3], domain_max: [1.0; 3], table_1d: Vec::new(), size_3d: size, table_3d }
+    }
+
+    /// Parse and [`validate`](Self::validate) a `.cube` file.
+    /// `LUT_1D_INPUT_RANGE` and `LUT_3D_INPUT_RANGE` are read as the domain, unknown keywords are ignored.
+    pub fn parse(text: &str) -> RedResult<Self> {
+        let mut lut = Self { title: None, domain_min: [0.0; 3], domain_max: [1.0; 3], table_1d: Vec::new(), size_3d: 0, table_3d: Vec::new() };
+        let mut size_1d = 0;
+        let mut input_range: Option<(usize, [f32; 2])> = None;
+        let mut values = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            let Some(keyword) = line.split_whitespace().next() else { continue };
+            let error = |message: &str| cube_error(format!("line {}: {message}", line_no + 1));
+            let args = line[keyword.len()..].trim();
+            let floats = || args.split_whitespace().map(str::parse::<f32>).collect::<Result<Vec<_>, _>>().map_err(|_| error(&format!("invalid {keyword}")));
+            let size = |max: usize| args.parse::<usize>().ok().filter(|x| (2..=max).contains(x)).ok_or_else(|| error(&format!("{keyword} must be 2 -- {max}")));
+            match keyword {
+                "TITLE" => lut.title = Some(args.trim_matches('"').to_owned()),
+                "LUT_1D_SIZE" => size_1d = size(MAX_1D_SIZE)?,
+                "LUT_3D_SIZE" => lut.size_3d = size(MAX_3D_SIZE)?,
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let v: [f32; 3] = floats()?.try_into().map_err(|_| error(&format!("{keyword} needs 3 values")))?;
+                    if keyword == "DOMAIN_MIN" { lut.domain_min = v; } else { lut.domain_max = v; }
+                }
+                "LUT_1D_INPUT_RANGE" | "LUT_3D_INPUT_RANGE" => {
+                    let v: [f32; 2] = floats()?.try_into().map_err(|_| error(&format!("{keyword} needs 2 values")))?;
+                    if let Some((prev_line, prev)) = input_range && prev != v {
+                        return Err(error(&format!("different 1D and 3D input ranges are not supported, previous range on line {prev_line}")));
+                    }
+                    input_range = Some((line_no + 1, v));
+                    (lut.domain_min, lut.domain_max) = ([v[0]; 3], [v[1]; 3]);
+                }
+                _ if keyword.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.') => {
+                    let v: [f32; 3] = line.split_whitespace().map(str::parse::<f32>).collect::<Result<Vec<_>, _>>().ok()
+                        .and_then(|x| x.try_into().ok())
+                        .ok_or_else(|| error("LUT entry needs 3 numbers"))?;
+                    values.push(v);
+                }
+                _ => log::debug!("Ignoring unknown .cube keyword {keyword}"),
+            }
+        }
+        let count_3d = lut.size_3d.pow(3);
+        if size_1d == 0 && count_3d == 0 {
+            return Err(cube_error("LUT_1D_SIZE or LUT_3D_SIZE is missing".into()));
+        }
+        if values.len() != size_1d + count_3d {
+            return Err(cube_error(format!("expected {} LUT entries, found {}", size_1d + count_3d, values.len())));
+        }
+        lut.table_3d = values.split_off(size_1d);
+        lut.table_1d = values;
+        lut.validate()?;
+        Ok(lut)
+    }
+
+    /// Read and [`validate`](Self::validate) a `.cube` file, e.g. before passing it to the SDK's `Load3DLut`
+    pub fn from_path(path: impl AsRef<Path>) -> RedResult<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Check the table sizes, the domain and that all values are finite
+    pub fn validate(&self) -> RedResult<()> {
+        if self.table_1d.is_empty() && self.size_3d == 0 {
+            return Err(cube_error("the LUT has no 1D or 3D table".into()));
+        }
+        if !self.table_1d.is_empty() && !(2..=MAX_1D_SIZE).contains(&self.table_1d.len()) {
+            return Err(cube_error(format!("LUT_1D_SIZE {} must be 2 -- {MAX_1D_SIZE}", self.table_1d.len())));
+        }
+        if self.size_3d != 0 && !(2..=MAX_3D_SIZE).contains(&self.size_3d) {
+            return Err(cube_error(format!("LUT_3D_SIZE {} must be 2 -- {MAX_3D_SIZE}", self.size_3d)));
+        }
+        if self.table_3d.len() != self.size_3d.pow(3) {
+            return Err(cube_error(format!("LUT_3D_SIZE {} needs {} entries, found {}", self.size_3d, self.size_3d.pow(3), self.table_3d.len())));
+        }
+        if (0..3).any(|i| !self.domain_min[i].is_finite() || !self.domain_max[i].is_finite() || self.domain_min[i] >= self.domain_max[i]) {
+            return Err(cube_error(format!("DOMAIN_MIN {:?} must be less than DOMAIN_MAX {:?}", self.domain_min, self.domain_max)));
+        }
+        if let Some(i) = self.table_1d.iter().chain(&self.table_3d).position(|x| x.iter().any(|v| !v.is_finite())) {
+            return Err(cube_error(format!("LUT entry {} is not a finite number", i + 1)));
+        }
+        Ok(())
+    }
+
+    /// Serialize to the `.cube` format. `DOMAIN_MIN`/`DOMAIN_MAX` are only written if they differ from 0 -- 1.
+    pub fn to_cube_string(&self) -> String {
+        let mut out = String::new();
+        if let Some(title) = &self.title {
+            let _ = writeln!(out, "TITLE \"{title}\"");
+        }
+        if !self.table_1d.is_empty() {
+            let _ = writeln!(out, "LUT_1D_SIZE {}", self.table_1d.len());
+        }
+        if self.size_3d != 0 {
+            let _ = writeln!(out, "LUT_3D_SIZE {}", self.size_3d);
+        }
+        if self.domain_min != [0.0; 3] || self.domain_max != [1.0; 3] {
+            let [r, g, b] = self.domain_min;
+            let _ = writeln!(out, "DOMAIN_MIN {r:.6} {g:.6} {b:.6}");
+            let [r, g, b] = self.domain_max;
+            let _ = writeln!(out, "DOMAIN_MAX {r:.6} {g:.6} {b:.6}");
+        }
+        out.push('\n');
+        for [r, g, b] in self.table_1d.iter().chain(&self.table_3d) {
+            let _ = writeln!(out, "{r:.6} {g:.6} {b:.6}");
+        }
+        out
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> RedResult<()> {
+        std::fs::write(path, self.to_cube_string())?;
+        Ok(())
+    }
+
+    /// Apply the LUT to a single RGB value
+    pub fn sample(&self, mut rgb: [f32; 3], interpolation: LutInterpolation) -> [f32; 3] {
+        if !self.table_1d.is_empty() {
+            let n = self.table_1d.len();
+            for (c, v) in rgb.iter_mut().enumerate() {
+                let x = self.normalize(*v, c) * (n - 1) as f32;
+                let i = (x as usize).min(n - 2);
+                let f = x - i as f32;
+                *v = self.table_1d[i][c] + (self.table_1d[i + 1][c] - self.table_1d[i][c]) * f;
+            }
+        }
+        if self.size_3d != 0 {
+            rgb = self.sample_3d(rgb, interpolation);
+        }
+        rgb
+    }
+
+    fn normalize(&self, v: f32, c: usize) -> f32 {
+        ((v - self.domain_min[c]) / (self.domain_max[c] - self.domain_min[c])).clamp(0.0, 1.0)
+    }
+
+    fn sample_3d(&self, rgb: [f32; 3], interpolation: LutInterpolation) -> [f32; 3] {
+        let n = self.size_3d;
+        let mut i = [0usize; 3];
+        let mut f = [0f32; 3];
+        for c in 0..3 {
+            let x = self.normalize(rgb[c], c) * (n - 1) as f32;
+            i[c] = (x as usize).min(n - 2);
+            f[c] = x - i[c] as f32;
+        }
+        let at = |r: usize, g: usize, b: usize| self.table_3d[(i[0] + r) + (i[1] + g) * n + (i[2] + b) * n * n];
+        let mix = |weights: [(f32, [f32; 3]); 4]| -> [f32; 3] {
+            std::array::from_fn(|c| weights.iter().map(|(w, v)| w * v[c]).sum())
+        };
+        let [fr, fg, fb] = f;
+        match interpolation {
+            LutInterpolation::Trilinear => {
+                let lerp = |a: [f32; 3], b: [f32; 3], t: f32| -> [f32; 3] { std::array::from_fn(|c| a[c] + (b[c] - a[c]) * t) };
+                let c00 = lerp(at(0, 0, 0), at(1, 0, 0), fr);
+                let c10 = lerp(at(0, 1, 0), at(1, 1, 0), fr);
+                let c01 = lerp(at(0, 0, 1), at(1, 0, 1), fr);
+                let c11 = lerp(at(0, 1, 1), at(1, 1, 1), fr);
+                lerp(lerp(c00, c10, fg), lerp(c01, c11, fg), fb)
+            }
+            LutInterpolation::Tetrahedral => {
+                let (c000, c111) = (at(0, 0, 0), at(1, 1, 1));
+                if fr > fg {
+                    if fg > fb {
+                        mix([(1.0 - fr, c000), (fr - fg, at(1, 0, 0)), (fg - fb, at(1, 1, 0)), (fb, c111)])
+                    } else if fr > fb {
+                        mix([(1.0 - fr, c000), (fr - fb, at(1, 0, 0)), (fb - fg, at(1, 0, 1)), (fg, c111)])
+                    } else {
+                        mix([(1.0 - fb, c000), (fb - fr, at(0, 0, 1)), (fr - fg, at(1, 0, 1)), (fg, c111)])
+                    }
+                } else if fb > fg {
+                    mix([(1.0 - fb, c000), (fb - fg, at(0, 0, 1)), (fg - fr, at(0, 1, 1)), (fr, c111)])
+                } else if fb > fr {
+                    mix([(1.0 - fg, c000), (fg - fb, at(0, 1, 0)), (fb - fr, at(0, 1, 1)), (fr, c111)])
+                } else {
+                    mix([(1.0 - fg, c000), (fg - fr, at(0, 1, 0)), (fr - fb, at(1, 1, 0)), (fb, c111)])
+                }
+            }
+        }
+    }
+
+    /// Apply the LUT in place to a decoded `Rgb16bitInterleaved`, `RgbHalfFloatInterleaved` or `RgbHalfFloatAcesInt` frame
+    /// (e.g. `AlignedBuffer::as_mut_slice::<u16>()`), on `threads` threads (0 = number of available CPU cores).
+    /// 16-bit values are mapped to 0 -- 1, half floats are used as they are. Returns `InvalidPixelType` for the other pixel types.
+    pub fn apply_to_frame(&self, data: &mut [u16], pixel_type: VideoPixelType, interpolation: LutInterpolation, threads: usize) -> RedResult<()> {
+        let half = match pixel_type {
+            VideoPixelType::Rgb16bitInterleaved => false,
+            VideoPixelType::RgbHalfFloatInterleaved | VideoPixelType::RgbHalfFloatAcesInt => true,
+            _ => return Err(RedError::InvalidPixelType),
+        };
+        let threads = if threads == 0 {
+            std::thread::available_parallelism().map(|x| x.get()).unwrap_or(1)
+        } else {
+            threads
+        };
+        let pixels = data.len() / 3;
+        let chunk = pixels.div_ceil(threads).max(1) * 3;
+        std::thread::scope(|s| {
+            for part in data[..pixels * 3].chunks_mut(chunk) {
+                s.spawn(move || {
+                    for px in part.chunks_exact_mut(3) {
+                        let rgb = if half {
+                            [f16_to_f32(px[0]), f16_to_f32(px[1]), f16_to_f32(px[2])]
+                        } else {
+                            [px[0] as f32 / 65535.0, px[1] as f32 / 65535.0, px[2] as f32 / 65535.0]
+                        };
+                        let out = self.sample(rgb, interpolation);
+                        for (dst, v) in px.iter_mut().zip(out) {
+                            *dst = if half { f32_to_f16(v) } else { (v.clamp(0.0, 1.0) * 65535.0).round() as u16 };
+                        }
+                    }
+                });
+            }
+        });
+        Ok(())
+    }
+}
+
+fn f16_to_f32(h: u16) -> f32 {
+    let sign = ((h & 0x8000) as u32) << 16;
+    let exp = ((h >> 10) & 0x1f) as u32;
+    let mant = (h & 0x3ff) as u32;
+    let bits = match (exp, mant) {
+        (0, 0) => sign,
+        (0, _) => {
+            // subnormal, normalize the mantissa
+            let shift = mant.leading_zeros() - 21;
+            sign | ((113 - shift) << 23) | ((mant << shift) & 0x3ff) << 13
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mant << 13),
+        _ => sign | ((exp + 112) << 23) | (mant << 13),
+    };
+    f32::from_bits(bits)
+}
+
+fn f32_to_f16(v: f32) -> u16 {
+    let bits = v.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x7f_ffff;
+    if exp == 0xff {
+        return sign | 0x7c00 | if mant != 0 { 0x200 } else { 0 };
+    }
+    let exp = exp - 127 + 15;
+    if exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exp <= 0 {
+        if exp < -10 {
+            return sign;
+        }
+        // subnormal, round to nearest even
+        let mant = mant | 0x80_0000;
+        let shift = (14 - exp) as u32;
+        let half = 1 << (shift - 1);
+        let rounded = (mant + half - 1 + ((mant >> shift) & 1)) >> shift;
+        return sign | rounded as u16;
+    }
+    // round to nearest even, a carry into the exponent is correct
+    let rounded = ((exp as u32) << 10 | (mant >> 13)) + (((mant & 0x1fff) > 0x1000 || (mant & 0x3fff) == 0x3000) as u32);
+    sign | rounded as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_write() {
+        let text = "# Created by hand\nTITLE \"Look # 2\" # comment\n\nLUT_1D_SIZE 2\nLUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\nCUSTOM_KEYWORD 1\n\
+                    0 0 0\n1 1 1 # end of 1D\n0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n";
+        let lut = CubeLut::parse(text).unwrap();
+        assert_eq!(lut.title.as_deref(), Some("Look # 2"));
+        assert_eq!((lut.domain_min, lut.domain_max), ([0.0; 3], [2.0; 3]));
+        assert_eq!(lut.table_1d, [[0.0; 3], [1.0; 3]]);
+        assert_eq!(lut.size_3d, 2);
+        assert_eq!(lut.table_3d, CubeLut::identity_3d(2).table_3d);
+        assert_eq!(CubeLut::parse(&lut.to_cube_string()).unwrap(), lut);
+
+        let lut = CubeLut { title: None, ..CubeLut::identity_3d(5) };
+        let text = lut.to_cube_string();
+        assert!(!text.contains("DOMAIN_MIN") && !text.contains("TITLE"));
+        assert_eq!(CubeLut::parse(&text).unwrap(), lut);
+
+        let lut = CubeLut::parse("LUT_3D_INPUT_RANGE -0.5 1.5\nLUT_3D_SIZE 2\n0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n").unwrap();
+        assert_eq!((lut.domain_min, lut.domain_max), ([-0.5; 3], [1.5; 3]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let message = |text: &str| match CubeLut::parse(text) {
+            Err(RedError::Parse { message, .. }) => message,
+            x => panic!("unexpected {x:?}"),
+        };
+        assert_eq!(message("TITLE \"x\"\n"), "LUT_1D_SIZE or LUT_3D_SIZE is missing");
+        assert_eq!(message("LUT_1D_SIZE 1\n"), "line 1: LUT_1D_SIZE must be 2 -- 65536");
+        assert_eq!(message("LUT_1D_SIZE 2\n0 0 0\n"), "expected 2 LUT entries, found 1");
+        assert_eq!(message("LUT_1D_SIZE 2\n0 0 0\n1 1\n"), "line 3: LUT entry needs 3 numbers");
+        assert_eq!(message("LUT_1D_SIZE 2\nDOMAIN_MIN 1 1 1\n0 0 0\n1 1 1\n"), "DOMAIN_MIN [1.0, 1.0, 1.0] must be less than DOMAIN_MAX [1.0, 1.0, 1.0]");
+        assert!(message("LUT_1D_INPUT_RANGE 0 1\nLUT_3D_INPUT_RANGE 0 2\n").starts_with("line 2: different 1D and 3D input ranges"));
+    }
+
+    #[test]
+    fn identity_16bit() {
+        let lut = CubeLut::identity_3d(17);
+        let mut data: Vec<u16> = (0..3000u32).map(|x| (x * 7919 % 65536) as u16).collect();
+        let original = data.clone();
+        for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+            lut.apply_to_frame(&mut data, VideoPixelType::Rgb16bitInterleaved, interpolation, 3).unwrap();
+            assert_eq!(data, original);
+        }
+        assert!(matches!(lut.apply_to_frame(&mut data, VideoPixelType::Bgra8bitInterleaved, LutInterpolation::Trilinear, 1), Err(RedError::InvalidPixelType)));
+    }
+
+    #[test]
+    fn identity_half_float() {
+        let lut = CubeLut::identity_3d(33);
+        // 0 -- 1, including subnormals
+        let original: Vec<u16> = (0..=0x3c00u16).step_by(7).chain([0x3c00, 0x0001, 0x03ff, 0x0400]).collect();
+        let mut data = original.clone();
+        lut.apply_to_frame(&mut data, VideoPixelType::RgbHalfFloatInterleaved, LutInterpolation::Tetrahedral, 0).unwrap();
+        assert_eq!(data, original);
+        // Outside of the domain the input is clamped
+        let mut data = [f32_to_f16(2.0), f32_to_f16(-1.0), f32_to_f16(0.5)];
+        lut.apply_to_frame(&mut data, VideoPixelType::RgbHalfFloatAcesInt, LutInterpolation::Trilinear, 1).unwrap();
+        assert_eq!(data, [f32_to_f16(1.0), 0, f32_to_f16(0.5)]);
+    }
+
+    #[test]
+    fn tetrahedral_and_trilinear() {
+        // Every corner black except white
+        let mut lut = CubeLut::identity_3d(2);
+        lut.table_3d = (0..8).map(|i| if i == 7 { [1.0; 3] } else { [0.0; 3] }).collect();
+        let sample = |rgb: [f32; 3], interpolation| lut.sample(rgb, interpolation)[0];
+        for corner in [[0.0, 0.0, 0.0], [1.0, 0.0, 1.0], [1.0, 1.0, 1.0]] {
+            let expected = if corner == [1.0; 3] { 1.0 } else { 0.0 };
+            assert_eq!(sample(corner, LutInterpolation::Trilinear), expected);
+            assert_eq!(sample(corner, LutInterpolation::Tetrahedral), expected);
+        }
+        assert_eq!(sample([0.5, 0.5, 0.5], LutInterpolation::Trilinear), 0.125);
+        assert_eq!(sample([0.5, 0.5, 0.5], LutInterpolation::Tetrahedral), 0.5);
+        assert_eq!(sample([0.75, 0.5, 0.25], LutInterpolation::Trilinear), 0.09375);
+        assert_eq!(sample([0.75, 0.5, 0.25], LutInterpolation::Tetrahedral), 0.25);
+        assert_eq!(sample([0.25, 0.5, 0.75], LutInterpolation::Tetrahedral), 0.25);
+
+        // Both reproduce a linear lattice exactly
+        let mut lut = CubeLut::identity_3d(3);
+        lut.table_3d.iter_mut().for_each(|v| *v = [v[0] * 0.5 + v[2] * 0.25, v[1], 1.0 - v[0]]);
+        for rgb in [[0.1, 0.7, 0.3], [0.9, 0.2, 0.55], [0.4, 0.4, 0.8]] {
+            let expected = [rgb[0] * 0.5 + rgb[2] * 0.25, rgb[1], 1.0 - rgb[0]];
+            for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+                let out = lut.sample(rgb, interpolation);
+                assert!((0..3).all(|c| (out[c] - expected[c]).abs() < 1e-6), "{interpolation:?} {rgb:?} {out:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn half_float_conversion() {
+        // Every half float except NaN survives the round trip, including subnormals and infinities
+        for h in 0..=u16::MAX {
+            if h & 0x7c00 == 0x7c00 && h & 0x3ff != 0 {
+                assert!(f16_to_f32(h).is_nan());
+                continue;
+            }
+            assert_eq!(f32_to_f16(f16_to_f32(h)), h, "{h:#06x}");
+        }
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0x03ff), 1023.0 * 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f32_to_f16(f32::NAN) & 0x7e00, 0x7e00);
+
+        // Round to nearest, ties to even
+        let ulp = 2f32.powi(-10);
+        assert_eq!(f32_to_f16(1.0 + ulp * 0.5), 0x3c00);
+        assert_eq!(f32_to_f16(1.0 + ulp * 0.5001), 0x3c01);
+        assert_eq!(f32_to_f16(1.0 + ulp * 1.5), 0x3c02);
+        assert_eq!(f32_to_f16(2.0 - ulp * 0.25), 0x4000);
+        assert_eq!(f32_to_f16(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16(65519.0), 0x7bff);
+        assert_eq!(f32_to_f16(65520.0), 0x7c00);
+        assert_eq!(f32_to_f16(-1e10), 0xfc00);
+
+        // Subnormals
+        let min = 2f32.powi(-24);
+        assert_eq!(f32_to_f16(min * 0.5), 0);
+        assert_eq!(f32_to_f16(min * 0.51), 1);
+        assert_eq!(f32_to_f16(min * 1.5), 2);
+        assert_eq!(f32_to_f16(min * 2.5), 2);
+        assert_eq!(f32_to_f16(min * 0.2), 0);
+        assert_eq!(f32_to_f16(-min * 3.0), 0x8003);
+        assert_eq!(f32_to_f16(1023.5 * min), 0x0400);
+    }
+}
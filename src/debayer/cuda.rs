@@ -5,7 +5,7 @@
 #![allow(non_snake_case)]
 
 use cpp::*;
-use crate::{ RedResult, RedError, enums::*, image_processing_settings::ImageProcessingSettings };
+use crate::{ RedResult, RedError, Sdk, enums::*, enums::status_enum, image_processing_settings::ImageProcessingSettings };
 use core::ffi::c_void;
 
 cpp!{{
@@ -17,11 +17,13 @@ pub type CudaStream = *mut c_void;
 
 pub struct RedCuda {
     ptr: *mut c_void,
+    _sdk: Sdk,
 }
 
 impl RedCuda {
     /// Create REDCuda wrapper and bind cudart functions dynamically
     pub fn new() -> RedResult<Self> {
+        let sdk = Sdk::current()?;
         let funcs = CUDA.as_ref().map_err(|_| RedError::RedCudaLibraryNotFound)?;
 
         let p_cudaFree                 = *funcs.cudaFree                 as *const () as usize;
@@ -66,7 +68,7 @@ impl RedCuda {
             return new R3DSDK::REDCuda(api);
         });
 
-        Ok(Self { ptr })
+        Ok(Self { ptr, _sdk: sdk })
     }
 
     pub fn create_debayer_job(&self) -> DebayerCudaJob {
@@ -83,9 +85,9 @@ impl RedCuda {
         let cuda = self.ptr;
         let mut cuda_error: cudaError_t = 0;
         let cuda_error_ptr = &mut cuda_error;
-        let status: CudaStatus = unsafe { std::mem::transmute(cpp!([cuda as "R3DSDK::REDCuda *", device_id as "int", stream as "cudaStream_t", cuda_error_ptr as "cudaError_t *"] -> i32 as "int" {
+        let status = CudaStatus::from(unsafe { cpp!([cuda as "R3DSDK::REDCuda *", device_id as "int", stream as "cudaStream_t", cuda_error_ptr as "cudaError_t *"] -> i32 as "int" {
             return (int)cuda->checkCompatibility(device_id, stream, *cuda_error_ptr);
-        })) };
+        }) });
         if status == CudaStatus::Ok {
             if cuda_error == 0 { Ok(()) } else { Err(RedError::CudaError(cuda_error)) }
         } else {
@@ -104,9 +106,9 @@ impl RedCuda {
         let job = debayer_job.ptr;
         let mut cuda_error: cudaError_t = 0;
         let cuda_error_ptr = &mut cuda_error;
-        let status: CudaStatus = unsafe { std::mem::transmute(cpp!([cuda as "R3DSDK::REDCuda *", device_id as "int", stream as "cudaStream_t", job as "R3DSDK::DebayerCudaJob *", cuda_error_ptr as "cudaError_t *"] -> i32 as "int" {
+        let status = CudaStatus::from(unsafe { cpp!([cuda as "R3DSDK::REDCuda *", device_id as "int", stream as "cudaStream_t", job as "R3DSDK::DebayerCudaJob *", cuda_error_ptr as "cudaError_t *"] -> i32 as "int" {
             return (int)cuda->process(device_id, stream, job, *cuda_error_ptr);
-        })) };
+        }) });
         if status == CudaStatus::Ok {
             if cuda_error == 0 { Ok(()) } else { Err(RedError::CudaError(cuda_error)) }
         } else {
@@ -124,9 +126,9 @@ impl RedCuda {
         let job_ptr = debayer_job.ptr;
         let mut cuda_error: cudaError_t = 0;
         let cuda_error_ptr = &mut cuda_error;
-        let status: CudaStatus = unsafe { std::mem::transmute(cpp!([cuda as "R3DSDK::REDCuda *", device_id as "int", stream as "cudaStream_t", job_ptr as "R3DSDK::DebayerCudaJob *", cuda_error_ptr as "cudaError_t *"] -> i32 as "int" {
+        let status = CudaStatus::from(unsafe { cpp!([cuda as "R3DSDK::REDCuda *", device_id as "int", stream as "cudaStream_t", job_ptr as "R3DSDK::DebayerCudaJob *", cuda_error_ptr as "cudaError_t *"] -> i32 as "int" {
             return (int)cuda->processAsync(device_id, stream, job_ptr, *cuda_error_ptr);
-        })) };
+        }) });
         if status == CudaStatus::Ok {
             if cuda_error == 0 { Ok(()) } else { Err(RedError::CudaError(cuda_error)) }
         } else {
@@ -238,33 +240,34 @@ impl Drop for DebayerCudaJob {
     }
 }
 
-#[repr(i32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum CudaStatus {
-	Ok = 0,
-	ErrorProcessing = 1,
-	InvalidJobParameter = 2,
-    /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
-	InvalidJobParameter_mode = 3,
-    /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
-	InvalidJobParameter_raw_host_mem = 4,
-    /// Raw device mem was NULL
-	InvalidJobParameter_raw_device_mem = 5,
-    /// unsupported pixel type
-	InvalidJobParameter_pixelType = 6,
-    /// Output buffer Size must be non zero.
-    /// Output buffer must be allocated prior to passing it into the sdk
-	InvalidJobParameter_output_device_mem_size = 7,
-	InvalidJobParameter_output_device_mem = 8,
-    /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
-	InvalidJobParameter_ColorVersion1 = 9,
-    /// GPU Device did not meet minimum requirements.
-	UnableToUseGPUDevice = 10,
-    /// Error loading R3DSDK dynamic library
-    UnableToLoadLibrary = 11,
-    ParameterUnsupported = 12,
-    /// deviceId must be an index between 0 and the number of devices on the system minus one
-    InvalidJobParameter_deviceId = 13
+status_enum! {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub enum CudaStatus {
+        Ok = 0,
+        ErrorProcessing = 1,
+        InvalidJobParameter = 2,
+        /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
+        InvalidJobParameter_mode = 3,
+        /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
+        InvalidJobParameter_raw_host_mem = 4,
+        /// Raw device mem was NULL
+        InvalidJobParameter_raw_device_mem = 5,
+        /// unsupported pixel type
+        InvalidJobParameter_pixelType = 6,
+        /// Output buffer Size must be non zero.
+        /// Output buffer must be allocated prior to passing it into the sdk
+        InvalidJobParameter_output_device_mem_size = 7,
+        InvalidJobParameter_output_device_mem = 8,
+        /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
+        InvalidJobParameter_ColorVersion1 = 9,
+        /// GPU Device did not meet minimum requirements.
+        UnableToUseGPUDevice = 10,
+        /// Error loading R3DSDK dynamic library
+        UnableToLoadLibrary = 11,
+        ParameterUnsupported = 12,
+        /// deviceId must be an index between 0 and the number of devices on the system minus one
+        InvalidJobParameter_deviceId = 13
+    }
 }
 
 impl From<CudaStatus> for RedError {
@@ -284,6 +287,7 @@ impl From<CudaStatus> for RedError {
             CudaStatus::UnableToLoadLibrary => RedError::UnableToLoadLibrary,
             CudaStatus::ParameterUnsupported => RedError::ParameterUnsupported,
             CudaStatus::InvalidJobParameter_deviceId => RedError::InvalidJobParameterDeviceId,
+            CudaStatus::Unknown(code) => RedError::UnknownStatus { name: "CudaStatus", code },
         }
     }
 }
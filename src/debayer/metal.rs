@@ -5,7 +5,7 @@
 #![allow(non_snake_case)]
 
 use cpp::*;
-use crate::{ RedResult, RedError, enums::*, image_processing_settings::ImageProcessingSettings };
+use crate::{ RedResult, RedError, Sdk, enums::*, enums::status_enum, image_processing_settings::ImageProcessingSettings };
 use core::ffi::c_void;
 
 cpp!{{
@@ -14,17 +14,19 @@ cpp!{{
 
 pub struct RedMetal {
     ptr: *mut c_void,
+    _sdk: Sdk,
 }
 
 impl RedMetal {
     /// Create REDMetal wrapper
     pub fn new() -> RedResult<Self> {
+        let sdk = Sdk::current()?;
         let ptr = cpp!(unsafe [] -> *mut c_void as "R3DSDK::REDMetal *" {
             R3DSDK::EXT_METAL_API api;
             return new R3DSDK::REDMetal(api);
         });
 
-        Ok(Self { ptr })
+        Ok(Self { ptr, _sdk: sdk })
     }
 
     pub fn create_debayer_job(&self) -> DebayerMetalJob {
@@ -41,9 +43,9 @@ impl RedMetal {
         let metal = self.ptr;
         let mut error: i32 = 0;
         let error_ptr = &mut error;
-        let status: MetalStatus = unsafe { std::mem::transmute(cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", error_ptr as "int *"] -> i32 as "int" {
+        let status = MetalStatus::from(unsafe { cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", error_ptr as "int *"] -> i32 as "int" {
             return (int)metal->checkCompatibility(queue, *error_ptr);
-        })) };
+        }) });
         if status == MetalStatus::Ok {
             if error == 0 { Ok(()) } else { Err(RedError::MetalError(error)) }
         } else {
@@ -60,9 +62,9 @@ impl RedMetal {
         let job = debayer_job.ptr;
         let mut error: i32 = 0;
         let error_ptr = &mut error;
-        let status: MetalStatus = unsafe { std::mem::transmute(cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", job as "R3DSDK::DebayerMetalJob *", error_ptr as "int *"] -> i32 as "int" {
+        let status = MetalStatus::from(unsafe { cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", job as "R3DSDK::DebayerMetalJob *", error_ptr as "int *"] -> i32 as "int" {
             return (int)metal->process(queue, job, *error_ptr);
-        })) };
+        }) });
         if status == MetalStatus::Ok {
             if error == 0 { Ok(()) } else { Err(RedError::MetalError(error)) }
         } else {
@@ -80,9 +82,9 @@ impl RedMetal {
         let job_ptr = debayer_job.ptr;
         let mut error: i32 = 0;
         let error_ptr = &mut error;
-        let status: MetalStatus = unsafe { std::mem::transmute(cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", job_ptr as "R3DSDK::DebayerMetalJob *", error_ptr as "int *"] -> i32 as "int" {
+        let status = MetalStatus::from(unsafe { cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", job_ptr as "R3DSDK::DebayerMetalJob *", error_ptr as "int *"] -> i32 as "int" {
             return (int)metal->processAsync(queue, job_ptr, *error_ptr);
-        })) };
+        }) });
         if status == MetalStatus::Ok {
             if error == 0 { Ok(()) } else { Err(RedError::MetalError(error)) }
         } else {
@@ -98,9 +100,9 @@ impl RedMetal {
         let metal = self.ptr;
         let mut error: i32 = 0;
         let error_ptr = &mut error;
-        let status: MetalStatus = unsafe { std::mem::transmute(cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", error_ptr as "int *"] -> i32 as "int" {
+        let status = MetalStatus::from(unsafe { cpp!([metal as "R3DSDK::REDMetal *", queue as "id<MTLCommandQueue>", error_ptr as "int *"] -> i32 as "int" {
             return (int)metal->flush(queue, *error_ptr);
-        })) };
+        }) });
         if status == MetalStatus::Ok {
             if error == 0 { Ok(()) } else { Err(RedError::MetalError(error)) }
         } else {
@@ -260,32 +262,33 @@ impl Drop for DebayerMetalJob {
     }
 }
 
-#[repr(i32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum MetalStatus {
-    Ok = 0,
-    ErrorProcessing = 1,
-    InvalidJobParameter = 2,
-    /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
-    InvalidJobParameter_mode = 3,
-    /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
-    InvalidJobParameter_raw_host_mem = 4,
-    /// Raw device mem was NULL
-    InvalidJobParameter_raw_device_mem = 5,
-    /// unsupported pixel type
-    InvalidJobParameter_pixelType = 6,
-    /// Output buffer Size must be non zero.
-    /// Output buffer must be allocated prior to passing it into the sdk
-    InvalidJobParameter_output_device_mem_size = 7,
-    InvalidJobParameter_output_device_mem = 8,
-    /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
-    InvalidJobParameter_ColorVersion1 = 9,
-    /// GPU Device did not meet minimum requirements.
-    UnableToUseGPUDevice = 10,
-    /// Error loading R3DSDK dynamic library
-    UnableToLoadLibrary = 11,
-    ParameterUnsupported = 12,
-	InvalidAPIObject = 13
+status_enum! {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub enum MetalStatus {
+        Ok = 0,
+        ErrorProcessing = 1,
+        InvalidJobParameter = 2,
+        /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
+        InvalidJobParameter_mode = 3,
+        /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
+        InvalidJobParameter_raw_host_mem = 4,
+        /// Raw device mem was NULL
+        InvalidJobParameter_raw_device_mem = 5,
+        /// unsupported pixel type
+        InvalidJobParameter_pixelType = 6,
+        /// Output buffer Size must be non zero.
+        /// Output buffer must be allocated prior to passing it into the sdk
+        InvalidJobParameter_output_device_mem_size = 7,
+        InvalidJobParameter_output_device_mem = 8,
+        /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
+        InvalidJobParameter_ColorVersion1 = 9,
+        /// GPU Device did not meet minimum requirements.
+        UnableToUseGPUDevice = 10,
+        /// Error loading R3DSDK dynamic library
+        UnableToLoadLibrary = 11,
+        ParameterUnsupported = 12,
+        InvalidAPIObject = 13
+    }
 }
 
 impl From<MetalStatus> for RedError {
@@ -305,6 +308,7 @@ impl From<MetalStatus> for RedError {
             MetalStatus::UnableToLoadLibrary => RedError::UnableToLoadLibrary,
             MetalStatus::ParameterUnsupported => RedError::ParameterUnsupported,
             MetalStatus::InvalidAPIObject => RedError::InvalidAPIObject,
+            MetalStatus::Unknown(code) => RedError::UnknownStatus { name: "MetalStatus", code },
         }
     }
 }
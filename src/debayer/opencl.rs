@@ -5,7 +5,7 @@
 #![allow(non_snake_case)]
 
 use cpp::*;
-use crate::{ RedResult, RedError, enums::*, image_processing_settings::ImageProcessingSettings };
+use crate::{ RedResult, RedError, Sdk, enums::*, enums::status_enum, image_processing_settings::ImageProcessingSettings };
 use core::ffi::c_void;
 
 cpp!{{
@@ -14,6 +14,7 @@ cpp!{{
 
 pub struct RedOpenCl {
     ptr: *mut c_void, // R3DSDK::REDCL *
+    _sdk: Sdk,
 }
 
 impl RedOpenCl {
@@ -22,6 +23,7 @@ impl RedOpenCl {
 	/// kernels will be disabled which is NOT recommended as compile times can be long!
 	/// (see "OpenCL kernel caching.txt" for more information)
     pub fn new(compiled_kernel_cache_folder: &str) -> RedResult<Self> {
+        let sdk = Sdk::current()?;
         let funcs = OPENCL.as_ref().map_err(|_| RedError::RedOpenCLLibraryNotFound)?;
 
         let folder_cstr = std::ffi::CString::new(compiled_kernel_cache_folder).unwrap();
@@ -166,7 +168,7 @@ impl RedOpenCl {
             return new R3DSDK::REDCL(api, folder_ptr);
         });
 
-        Ok(Self { ptr })
+        Ok(Self { ptr, _sdk: sdk })
     }
 
     pub fn create_debayer_job(&self) -> DebayerOpenCLJob {
@@ -185,9 +187,9 @@ impl RedOpenCl {
         let opencl = self.ptr;
         let mut opencl_error: cl_int = 0;
         let opencl_error_ptr = &mut opencl_error;
-        let status: OpenClStatus = unsafe { std::mem::transmute(cpp!([opencl as "R3DSDK::REDCL *", context as "cl_context", queue as "cl_command_queue", opencl_error_ptr as "cl_int *"] -> i32 as "int" {
+        let status = OpenClStatus::from(unsafe { cpp!([opencl as "R3DSDK::REDCL *", context as "cl_context", queue as "cl_command_queue", opencl_error_ptr as "cl_int *"] -> i32 as "int" {
             return (int)opencl->checkCompatibility(context, queue, *opencl_error_ptr);
-        })) };
+        }) });
         if status == OpenClStatus::Ok {
             if opencl_error == 0 { Ok(()) } else { Err(RedError::OpenCLError(opencl_error)) }
         } else {
@@ -206,9 +208,9 @@ impl RedOpenCl {
         let job = debayer_job.ptr;
         let mut opencl_error: cl_int = 0;
         let opencl_error_ptr = &mut opencl_error;
-        let status: OpenClStatus = unsafe { std::mem::transmute(cpp!([opencl as "R3DSDK::REDCL *", context as "cl_context", queue as "cl_command_queue", job as "R3DSDK::DebayerOpenCLJob *", opencl_error_ptr as "cl_int *"] -> i32 as "int" {
+        let status = OpenClStatus::from(unsafe { cpp!([opencl as "R3DSDK::REDCL *", context as "cl_context", queue as "cl_command_queue", job as "R3DSDK::DebayerOpenCLJob *", opencl_error_ptr as "cl_int *"] -> i32 as "int" {
             return (int)opencl->process(context, queue, job, *opencl_error_ptr);
-        })) };
+        }) });
         if status == OpenClStatus::Ok {
             if opencl_error == 0 { Ok(()) } else { Err(RedError::OpenCLError(opencl_error)) }
         } else {
@@ -225,9 +227,9 @@ impl RedOpenCl {
         let job_ptr = debayer_job.ptr;
         let mut opencl_error: cl_int = 0;
         let opencl_error_ptr = &mut opencl_error;
-        let status: OpenClStatus = unsafe { std::mem::transmute(cpp!([opencl as "R3DSDK::REDCL *", context as "cl_context", queue as "cl_command_queue", job_ptr as "R3DSDK::DebayerOpenCLJob *", opencl_error_ptr as "cl_int *"] -> i32 as "int" {
+        let status = OpenClStatus::from(unsafe { cpp!([opencl as "R3DSDK::REDCL *", context as "cl_context", queue as "cl_command_queue", job_ptr as "R3DSDK::DebayerOpenCLJob *", opencl_error_ptr as "cl_int *"] -> i32 as "int" {
             return (int)opencl->processAsync(context, queue, job_ptr, *opencl_error_ptr);
-        })) };
+        }) });
         if status == OpenClStatus::Ok {
             if opencl_error == 0 { Ok(()) } else { Err(RedError::OpenCLError(opencl_error)) }
         } else {
@@ -339,32 +341,33 @@ impl Drop for DebayerOpenCLJob {
     }
 }
 
-#[repr(i32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum OpenClStatus {
-    Ok = 0,
-    ErrorProcessing = 1,
-    InvalidJobParameter = 2,
-    /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
-    InvalidJobParameter_mode = 3,
-    /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
-    InvalidJobParameter_raw_host_mem = 4,
-    /// Raw device mem was NULL
-    InvalidJobParameter_raw_device_mem = 5,
-    /// unsupported pixel type
-    InvalidJobParameter_pixelType = 6,
-    /// Output buffer Size must be non zero.
-    /// Output buffer must be allocated prior to passing it into the sdk
-    InvalidJobParameter_output_device_mem_size = 7,
-    InvalidJobParameter_output_device_mem = 8,
-    /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
-    InvalidJobParameter_ColorVersion1 = 9,
-    /// GPU Device did not meet minimum requirements.
-    UnableToUseGPUDevice = 10,
-    /// Error loading R3DSDK dynamic library
-    UnableToLoadLibrary = 11,
-    ParameterUnsupported = 12,
-	InvalidAPIObject = 13
+status_enum! {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub enum OpenClStatus {
+        Ok = 0,
+        ErrorProcessing = 1,
+        InvalidJobParameter = 2,
+        /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
+        InvalidJobParameter_mode = 3,
+        /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
+        InvalidJobParameter_raw_host_mem = 4,
+        /// Raw device mem was NULL
+        InvalidJobParameter_raw_device_mem = 5,
+        /// unsupported pixel type
+        InvalidJobParameter_pixelType = 6,
+        /// Output buffer Size must be non zero.
+        /// Output buffer must be allocated prior to passing it into the sdk
+        InvalidJobParameter_output_device_mem_size = 7,
+        InvalidJobParameter_output_device_mem = 8,
+        /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
+        InvalidJobParameter_ColorVersion1 = 9,
+        /// GPU Device did not meet minimum requirements.
+        UnableToUseGPUDevice = 10,
+        /// Error loading R3DSDK dynamic library
+        UnableToLoadLibrary = 11,
+        ParameterUnsupported = 12,
+        InvalidAPIObject = 13
+    }
 }
 
 impl From<OpenClStatus> for RedError {
@@ -384,6 +387,7 @@ impl From<OpenClStatus> for RedError {
             OpenClStatus::UnableToLoadLibrary => RedError::UnableToLoadLibrary,
             OpenClStatus::ParameterUnsupported => RedError::ParameterUnsupported,
             OpenClStatus::InvalidAPIObject => RedError::InvalidAPIObject,
+            OpenClStatus::Unknown(code) => RedError::UnknownStatus { name: "OpenClStatus", code },
         }
     }
 }
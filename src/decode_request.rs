@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use crate::{ RedResult, RedError, Clip, AlignedBuffer, R3dDecodeJob, AsyncDecompressJob, ImageProcessingSettings, HdrProcessingSettings, GradeTimeline };
+use crate::enums::*;
+
+/// Description of a single frame decode, which can be turned into an [`R3dDecodeJob`],
+/// an [`AsyncDecompressJob`] or decoded synchronously on the CPU.
+///
+/// All parameters are validated against the clip before anything is submitted to the SDK,
+/// so mistakes are reported here instead of as `InvalidJobParameter*` after submission.
+///
+/// Defaults: track 0, `FullResPremium`, `Rgb16bitInterleaved`, clip default image processing settings, no frame metadata.
+#[derive(Clone)]
+pub struct DecodeRequest<'a> {
+    clip: &'a Clip,
+    video_track_no: usize,
+    video_frame_no: usize,
+    mode: VideoDecodeMode,
+    pixel_type: Option<VideoPixelType>,
+    image_settings: Option<&'a ImageProcessingSettings>,
+    hdr_settings: Option<&'a HdrProcessingSettings>,
+    timeline: Option<&'a GradeTimeline>,
+    frame_metadata: bool,
+}
+
+impl<'a> DecodeRequest<'a> {
+    pub fn new(clip: &'a Clip, video_frame_no: usize) -> Self {
+        Self {
+            clip,
+            video_track_no: 0,
+            video_frame_no,
+            mode: VideoDecodeMode::FullResPremium,
+            pixel_type: None,
+            image_settings: None,
+            hdr_settings: None,
+            timeline: None,
+            frame_metadata: false,
+        }
+    }
+
+    /// 0 = main (A) track, 1 = EPIC/Scarlet-X higlight protection track 2 (X track)
+    /// ignored when doing HDRx blending
+    pub fn video_track_no(mut self, v: usize) -> Self { self.video_track_no = v; self }
+    pub fn video_frame_no(mut self, v: usize) -> Self { self.video_frame_no = v; self }
+    /// Resolution/speed to decode the image at.
+    pub fn mode(mut self, v: VideoDecodeMode) -> Self { self.mode = v; self }
+    /// Pixel type to decode the image in. Not supported by [`build_async`](Self::build_async).
+    pub fn pixel_type(mut self, v: VideoPixelType) -> Self { self.pixel_type = Some(v); self }
+    /// Image processing settings, if not set the clip defaults are used. Not supported by [`build_async`](Self::build_async).
+    pub fn image_processing(mut self, v: &'a ImageProcessingSettings) -> Self { self.image_settings = Some(v); self }
+    /// HDRx blending settings, only valid for HDRx clips. Not supported by [`build_async`](Self::build_async).
+    pub fn hdr_processing(mut self, v: &'a HdrProcessingSettings) -> Self { self.hdr_settings = Some(v); self }
+    /// Keyframed grade, the image processing (and HDRx, if the keyframes have it) settings of the requested frame
+    /// are interpolated from it instead of using [`image_processing`](Self::image_processing) and [`hdr_processing`](Self::hdr_processing).
+    /// Ignored if the timeline has no keyframes. Not supported by [`build_async`](Self::build_async).
+    pub fn grade_timeline(mut self, v: &'a GradeTimeline) -> Self { self.timeline = Some(v); self }
+    /// Request the per-frame metadata of the decoded frame.
+    pub fn frame_metadata(mut self, v: bool) -> Self { self.frame_metadata = v; self }
+
+    /// Check the request against the clip without decoding anything.
+    pub fn validate(&self) -> RedResult<()> {
+        self.check().map_err(|e| e.with_context(self.clip.error_context("DecodeRequest::validate").video_frame_no(self.video_frame_no).video_track_no(self.video_track_no)))
+    }
+
+    fn check(&self) -> RedResult<()> {
+        let status = self.clip.status();
+        if status != LoadStatus::ClipLoaded {
+            return Err(status.into());
+        }
+        if self.video_frame_no >= self.clip.video_frame_count() {
+            return Err(RedError::RequestOutOfRange);
+        }
+        if self.video_track_no >= self.clip.video_track_count() {
+            return Err(RedError::RequestOutOfRange);
+        }
+        let (image_settings, hdr_settings) = self.settings();
+        if hdr_settings.is_some() && self.clip.video_track_count() < 2 {
+            return Err(RedError::NotAnHDRxClip);
+        }
+        if let Some(settings) = image_settings {
+            settings.version()?;
+        }
+        Ok(())
+    }
+
+    fn pixel_type_or_default(&self) -> VideoPixelType {
+        self.pixel_type.unwrap_or(VideoPixelType::Rgb16bitInterleaved)
+    }
+
+    /// Settings of the requested frame, from the grade timeline if it's set
+    fn settings(&self) -> (Option<ImageProcessingSettings>, Option<HdrProcessingSettings>) {
+        match self.timeline.and_then(|x| x.grade_at(self.video_frame_no)) {
+            Some(grade) => (Some(grade.to_settings()), grade.to_hdr_settings()),
+            None => (self.image_settings.copied(), self.hdr_settings.copied()),
+        }
+    }
+
+    /// Validate the request and create a job for [`R3dDecoder::decode`](crate::R3dDecoder::decode)
+    /// with an internally allocated output buffer. The job keeps its own copy of the image and HDRx processing settings.
+    ///
+    /// `R3DDecoder` doesn't support ColorVersion1 or `Bgra8bitInterleaved`, these are rejected here.
+    pub fn build(&self) -> RedResult<R3dDecodeJob> {
+        self.validate()?;
+        let context = || self.clip.error_context("DecodeRequest::build").video_frame_no(self.video_frame_no).video_track_no(self.video_track_no);
+        let pixel_type = self.pixel_type_or_default();
+        if pixel_type == VideoPixelType::Bgra8bitInterleaved {
+            return Err(RedError::InvalidJobParameterPixelType.with_context(context()));
+        }
+        let (image_settings, hdr_settings) = self.settings();
+        if let Some(settings) = &image_settings && settings.version()? == ColorVersion::Version1 {
+            return Err(RedError::InvalidJobParameterColorVersion1.with_context(context()));
+        }
+        let mut job = R3dDecodeJob::new()?;
+        job.set_clip(self.clip);
+        job.set_video_track_no(self.video_track_no);
+        job.set_video_frame_no(self.video_frame_no);
+        job.set_mode(self.mode);
+        job.set_pixel_type(pixel_type);
+        job.set_image_processing_owned(match image_settings {
+            Some(settings) => settings,
+            None => self.clip.default_image_processing_settings(),
+        });
+        if let Some(hdr_settings) = hdr_settings {
+            job.set_hdr_processing_owned(hdr_settings)?;
+        }
+        job.allocate_internal_buffer(self.clip)?;
+        if self.frame_metadata {
+            job.allocate_frame_metadata();
+        }
+        Ok(job)
+    }
+
+    /// Validate the request and create a job for [`AsyncDecoder`](crate::AsyncDecoder) or [`GpuDecoder`](crate::GpuDecoder)
+    /// with an internally allocated output buffer.
+    ///
+    /// The job only decompresses the raw frame, so image processing, pixel type, HDRx and grade timeline
+    /// can't be applied to it and return `ParameterUnsupported` if they are set.
+    pub fn build_async(&self) -> RedResult<AsyncDecompressJob> {
+        self.validate()?;
+        if self.image_settings.is_some() || self.pixel_type.is_some() || self.hdr_settings.is_some() || self.timeline.is_some() {
+            return Err(RedError::ParameterUnsupported.with_context(self.clip.error_context("DecodeRequest::build_async").video_frame_no(self.video_frame_no).video_track_no(self.video_track_no)));
+        }
+        let mut job = AsyncDecompressJob::new();
+        job.set_clip(self.clip);
+        job.set_video_track_no(self.video_track_no);
+        job.set_video_frame_no(self.video_frame_no);
+        job.set_mode(self.mode);
+        job.allocate_internal_buffer()?;
+        if self.frame_metadata {
+            job.allocate_frame_metadata();
+        }
+        Ok(job)
+    }
+
+    /// Validate the request and decode the frame synchronously on the CPU.
+    /// Frame metadata is not available with the synchronous decode.
+    pub fn decode(&self) -> RedResult<AlignedBuffer> {
+        self.validate()?;
+        let (image_settings, hdr_settings) = self.settings();
+        let buffer = if self.video_track_no == 0 || hdr_settings.is_some() {
+            self.clip.decode_video_frame(self.video_frame_no, self.mode, self.pixel_type_or_default(), image_settings.as_ref(), hdr_settings.as_ref(), None)?
+        } else {
+            self.clip.video_track_decode_frame(self.video_track_no, self.video_frame_no, self.mode, self.pixel_type_or_default(), image_settings.as_ref(), None, None)?
+        };
+        buffer.ok_or(RedError::OutputBufferInvalid)
+    }
+}
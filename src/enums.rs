@@ -2,6 +2,49 @@
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
 use bitflags::bitflags;
+
+/// `TryFrom<i32>` for a `repr(i32)` enum, listing all its variants.
+/// Returns `RedError::InvalidEnumValue` for values not known to this version of the crate.
+macro_rules! impl_try_from_i32 {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        impl TryFrom<i32> for $name {
+            type Error = crate::RedError;
+            fn try_from(value: i32) -> Result<Self, Self::Error> {
+                $( if value == Self::$variant as i32 { return Ok(Self::$variant); } )*
+                Err(crate::RedError::InvalidEnumValue { name: stringify!($name), value })
+            }
+        }
+    };
+}
+
+/// SDK status enum. Codes unknown to this version of the crate, e.g. returned by a newer runtime SDK, become `Unknown(code)`,
+/// so converting from the SDK integer with `From<i32>` can never fail.
+macro_rules! status_enum {
+    ($(#[$meta:meta])* pub enum $name:ident { $($(#[$vmeta:meta])* $variant:ident = $value:literal),* $(,)? }) => {
+        $(#[$meta])*
+        pub enum $name {
+            $( $(#[$vmeta])* $variant, )*
+            /// Status code not known to this version of the crate
+            Unknown(i32),
+        }
+        impl $name {
+            /// Raw status code as returned by the SDK
+            pub fn code(self) -> i32 {
+                match self { $( Self::$variant => $value, )* Self::Unknown(x) => x }
+            }
+        }
+        impl From<i32> for $name {
+            fn from(value: i32) -> Self {
+                match value { $( $value => Self::$variant, )* x => Self::Unknown(x) }
+            }
+        }
+        impl From<$name> for i32 {
+            fn from(value: $name) -> Self { value.code() }
+        }
+    };
+}
+pub(crate) use status_enum;
+
 bitflags! {
     /// Bit flags that can be passed to InitializeSdk() through the optional_components parameter
     #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -109,6 +152,7 @@ pub enum VideoPixelType {
 /// HDRx blending algorithm to use when doing HDR blending
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HdrBlendAlgorithm {
     /// Simple blend, exactly as implemented in 12_SimpleHDRxBlend
 	/// On the Rocket this will ALWAYS decode in 16-bit to do the blend
@@ -138,169 +182,174 @@ pub enum MetadataType {
     Float = 3,
 }
 
-#[repr(i32)]
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub enum InitializeStatus {
-    Ok = 0,
-    LibraryNotLoaded = 1,
-
-    R3DSDKLibraryNotFound = 2,
-    RedCudaLibraryNotFound = 3,
-    RedOpenCLLibraryNotFound = 4,
-    R3DDecoderLibraryNotFound = 5,
-    RedMetalLibraryNotFound = 17,
-
-    LibraryVersionMismatch = 6,
-
-    InvalidR3DSDKLibrary = 7,
-    InvalidRedCudaLibrary = 8,
-    InvalidRedOpenCLLibrary = 9,
-    InvalidR3DDecoderLibrary = 10,
-    InvalidRedMetalLibrary = 18,
-
-    RedCudaLibraryInitializeFailed = 11,
-    RedOpenCLLibraryInitializeFailed = 12,
-    R3DDecoderLibraryInitializeFailed = 13,
-    R3DSDKLibraryInitializeFailed = 14,
-    RedMetalLibraryInitializeFailed = 19,
+status_enum! {
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub enum InitializeStatus {
+        Ok = 0,
+        LibraryNotLoaded = 1,
+
+        R3DSDKLibraryNotFound = 2,
+        RedCudaLibraryNotFound = 3,
+        RedOpenCLLibraryNotFound = 4,
+        R3DDecoderLibraryNotFound = 5,
+        RedMetalLibraryNotFound = 17,
+
+        LibraryVersionMismatch = 6,
+
+        InvalidR3DSDKLibrary = 7,
+        InvalidRedCudaLibrary = 8,
+        InvalidRedOpenCLLibrary = 9,
+        InvalidR3DDecoderLibrary = 10,
+        InvalidRedMetalLibrary = 18,
+
+        RedCudaLibraryInitializeFailed = 11,
+        RedOpenCLLibraryInitializeFailed = 12,
+        R3DDecoderLibraryInitializeFailed = 13,
+        R3DSDKLibraryInitializeFailed = 14,
+        RedMetalLibraryInitializeFailed = 19,
+
+        InvalidPath = 15,
+        InternalError = 16,
+
+        MetalNotAvailable = 20,
+    }
+}
 
-    InvalidPath = 15,
-    InternalError = 16,
+status_enum! {
+    /// Clip load status
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub enum LoadStatus {
+        ClipLoaded = 0,
+        /// could not find the clip
+        PathNotFound = 1,
+        /// could not open the clip
+        FailedToOpenFile = 2,
+        /// clip does not appear to be a(n) (valid) R3D file
+        NotAnR3DFile = 3,
+        /// clip doesn't have any video frames in it
+        ClipIsEmpty = 4,
+        /// no more memory could be allocated
+        OutOfMemory = 5,
+        /// unknown error (shouldn't happen)
+        UnknownError = 6,
+        /// initial status, no clip has been loaded yet
+        NoClipOpen = 7,
+        /// library wasn't loaded properly (if obj-c, try new() instead)
+        NotInitialized = 8,
+    }
+}
 
-    MetalNotAvailable = 20,
+status_enum! {
+    /// Clip create status
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub enum CreateStatus {
+        /// trim has started but has not finished yet
+        Started = 0,
+        /// a frame has been added to the output clip, but not finished yet
+        FrameAdded = 1,
+        /// trim is done
+        Done = 2,
+        /// no more memory could be allocated
+        OutOfMemory = 3,
+        /// the requested start or end frame does not exist in the clip
+        RequestOutOfRange = 4,
+        InvalidParameter = 5,
+        /// unable to load a frame from the source clip
+        FailedToGetSourceFrame = 6,
+        /// unable to create output clip
+        FailedToCreateDestination = 7,
+        /// unable to write to output clip
+        FailedToWriteToDestination = 8,
+        UnknownError = 9,
+        /// source clip cannot be used for trim (RED ONE firmware build 15 and below not supported)
+        InvalidSourceClip = 10,
+        /// output path is invalid (see trim requirements)
+        InvalidPath = 11,
+        /// unable to load needed audio samples form source clip
+        FailedToGetSourceAudio = 12,
+        /// streaming packets need to be added in order
+        OutOfOrder = 13,
+        /// streaming packet is invalid
+        InvalidStream = 14,
+    }
 }
 
-/// Clip load status
-#[repr(i32)]
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub enum LoadStatus {
-    ClipLoaded = 0,
-    /// could not find the clip
-    PathNotFound = 1,
-    /// could not open the clip
-    FailedToOpenFile = 2,
-    /// clip does not appear to be a(n) (valid) R3D file
-    NotAnR3DFile = 3,
-    /// clip doesn't have any video frames in it
-    ClipIsEmpty = 4,
-    /// no more memory could be allocated
-    OutOfMemory = 5,
-    /// unknown error (shouldn't happen)
-    UnknownError = 6,
-    /// initial status, no clip has been loaded yet
-    NoClipOpen = 7,
-    /// library wasn't loaded properly (if obj-c, try new() instead)
-    NotInitialized = 8,
-}
-
-/// Clip create status
-#[repr(i32)]
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub enum CreateStatus {
-    /// trim has started but has not finished yet
-    Started = 0,
-    /// a frame has been added to the output clip, but not finished yet
-    FrameAdded = 1,
-    /// trim is done
-    Done = 2,
-    /// no more memory could be allocated
-    OutOfMemory = 3,
-    /// the requested start or end frame does not exist in the clip
-    RequestOutOfRange = 4,
-    InvalidParameter = 5,
-    /// unable to load a frame from the source clip
-    FailedToGetSourceFrame = 6,
-    /// unable to create output clip
-    FailedToCreateDestination = 7,
-    /// unable to write to output clip
-    FailedToWriteToDestination = 8,
-    UnknownError = 9,
-    /// source clip cannot be used for trim (RED ONE firmware build 15 and below not supported)
-    InvalidSourceClip = 10,
-    /// output path is invalid (see trim requirements)
-    InvalidPath = 11,
-    /// unable to load needed audio samples form source clip
-    FailedToGetSourceAudio = 12,
-    /// streaming packets need to be added in order
-    OutOfOrder = 13,
-    /// streaming packet is invalid
-    InvalidStream = 14,
-}
-
-/// Video decode status
-#[repr(i32)]
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub enum DecodeStatus {
-    Ok = 0,
-    /// null pointer, too small or not aligned
-    OutputBufferInvalid = 1,
-    /// the requested frame does not exist in the clip
-    RequestOutOfRange = 3,
-    /// one of the supplied parameters is not valid
-    InvalidParameter = 4,
-    /// the requested frame is a dropped frame and can't be decoded (video decode only)
-    IsDroppedFrame = 5,
-    /// decode failed because of a corrupt frame
-    DecodeFailed = 6,
-    /// no more memory could be allocated
-    OutOfMemory = 7,
-    /// unknown error (shouldn't happen)
-    UnknownError = 8,
-    /// open a clip first before trying to decode a frame
-    NoClipOpen = 9,
-    /// I/O error reading the frame
-    CannotReadFromFile = 10,
-    /// cannot decode to specified pixel type
-    InvalidPixelType = 11,
-    /// cannot decode HDRx as this is not an HDRx clip
-    NotAnHDRxClip = 12,
-    /// user requested decode to be cancelled
-    Cancelled = 13,
-    /// this clip format is not supported for the requested decode
-    UnsupportedClipFormat = 14,
-    /// one of the parameters supplied is not supported by this API. Upgrade to newer dynamic libraries.
-    ParameterUnsupported = 15,
-    /// open async decoder first before submitting decodes
-    DecoderNotOpened = 16,
+status_enum! {
+    /// Video decode status
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub enum DecodeStatus {
+        Ok = 0,
+        /// null pointer, too small or not aligned
+        OutputBufferInvalid = 1,
+        /// the requested frame does not exist in the clip
+        RequestOutOfRange = 3,
+        /// one of the supplied parameters is not valid
+        InvalidParameter = 4,
+        /// the requested frame is a dropped frame and can't be decoded (video decode only)
+        IsDroppedFrame = 5,
+        /// decode failed because of a corrupt frame
+        DecodeFailed = 6,
+        /// no more memory could be allocated
+        OutOfMemory = 7,
+        /// unknown error (shouldn't happen)
+        UnknownError = 8,
+        /// open a clip first before trying to decode a frame
+        NoClipOpen = 9,
+        /// I/O error reading the frame
+        CannotReadFromFile = 10,
+        /// cannot decode to specified pixel type
+        InvalidPixelType = 11,
+        /// cannot decode HDRx as this is not an HDRx clip
+        NotAnHDRxClip = 12,
+        /// user requested decode to be cancelled
+        Cancelled = 13,
+        /// this clip format is not supported for the requested decode
+        UnsupportedClipFormat = 14,
+        /// one of the parameters supplied is not supported by this API. Upgrade to newer dynamic libraries.
+        ParameterUnsupported = 15,
+        /// open async decoder first before submitting decodes
+        DecoderNotOpened = 16,
+    }
 }
 
-#[repr(i32)]
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub enum R3DStatus {
-    Ok = 0,
-    ErrorProcessing = 1,
-    InvalidJobParameter = 2,
+status_enum! {
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub enum R3DStatus {
+        Ok = 0,
+        ErrorProcessing = 1,
+        InvalidJobParameter = 2,
 
-    /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
-    InvalidJobParameterMode = 3,
+        /// mode value passed in is not compatible with this SDK or the mode used with the DecodeForGpuSdk call is not compatible
+        InvalidJobParameterMode = 3,
 
-    /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
-    InvalidJobParameterRawHostMem = 4,
-    InvalidJobParameterRawDeviceMem = 5,
+        /// pointer is NULL, data is not from DecodeForGpuSdk, R3DSDK and GPUSDK versions are incompatible or buffer is not actually in host memory.
+        InvalidJobParameterRawHostMem = 4,
+        InvalidJobParameterRawDeviceMem = 5,
 
-    /// unsupported pixel type
-    InvalidJobParameterPixelType = 6,
+        /// unsupported pixel type
+        InvalidJobParameterPixelType = 6,
 
-    /// Output buffer Size must be non zero.
-    /// Output buffer must be allocated prior to passing it into the sdk
-    InvalidJobParameterOutputDeviceMemSize = 7,
-    InvalidJobParameterOutputDeviceMem = 8,
+        /// Output buffer Size must be non zero.
+        /// Output buffer must be allocated prior to passing it into the sdk
+        InvalidJobParameterOutputDeviceMemSize = 7,
+        InvalidJobParameterOutputDeviceMem = 8,
 
-    /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
-    InvalidJobParameterColorVersion1 = 9,
+        /// Image processing settings ColorVersion was set to ColorVersion1 which is not supported by this SDK
+        InvalidJobParameterColorVersion1 = 9,
 
-    /// null, or unopened clip.
-    InvalidJobParameterClip = 10,
+        /// null, or unopened clip.
+        InvalidJobParameterClip = 10,
 
-    /// GPU Device did not meet minimum requirements.
-    UnableToUseGPUDevice = 11,
+        /// GPU Device did not meet minimum requirements.
+        UnableToUseGPUDevice = 11,
 
-    /// No GPU Devices were setup on the R3DDecoderOptions class
-    NoGPUDeviceSpecified = 12,
+        /// No GPU Devices were setup on the R3DDecoderOptions class
+        NoGPUDeviceSpecified = 12,
 
-    /// Error loading R3DSDK dynamic library
-    UnableToLoadLibrary = 13,
-    ParameterUnsupported = 14,
+        /// Error loading R3DSDK dynamic library
+        UnableToLoadLibrary = 13,
+        ParameterUnsupported = 14,
+    }
 }
 
 /// Possible marker types
@@ -315,6 +364,7 @@ pub enum MarkerType {
 
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageGammaCurve {
     Linear = 0,
     SRGB = 8,
@@ -362,6 +412,7 @@ pub enum ImageGammaCurve {
 
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageColorSpace {
     /// only available with color version 2 or newer, otherwise ignored (CameraRGB will be used)
     REDWideGamutRGB = 15,
@@ -402,6 +453,7 @@ pub enum ImageColorSpace {
 /// IPP2 (ColorVersion3) only
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImagePipeline {
     /// output in REDWideGamutRGB & Log3G10. Only Kelvin, Tint, ISO and ExposureAdjust available.
     PrimaryDevelopmentOnly = 0,
@@ -412,6 +464,7 @@ pub enum ImagePipeline {
 /// IPP2 (ColorVersion3) only
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RollOff {
     VerySoft = 4,
     Soft = 3,
@@ -424,6 +477,7 @@ pub enum RollOff {
 /// IPP2 (ColorVersion3) only
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ToneMap {
     Low = 0,
     Medium = 1,
@@ -435,6 +489,7 @@ pub enum ToneMap {
 /// the full resolution premium software decode only!
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageDetail {
     Low = 0,
     Medium = 1,
@@ -446,6 +501,7 @@ pub enum ImageDetail {
 /// the half & full resolution premium software decodes
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageOLPFCompensation {
     Off = 0,
     Low = 1,
@@ -457,6 +513,7 @@ pub enum ImageOLPFCompensation {
 /// full resolution premium software decode
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageDenoise {
     Off = 0,
     Minimum = 1,
@@ -469,6 +526,7 @@ pub enum ImageDenoise {
 
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FlashingPixelAdjust {
     Off = 0,
     Mild = 1179667780,
@@ -480,6 +538,7 @@ pub enum FlashingPixelAdjust {
 /// Default is ColorVersion3, which is the new IPP2 color science.
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColorVersion {
     /// Legacy
     Version1 = 1,
@@ -492,6 +551,8 @@ pub enum ColorVersion {
 
 /// Legacy (FLUT) only. Lift, Gamma and Gain settings.
 #[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LiftGammaGain {
     /// -1 -- 1
     pub lift: f32,
@@ -503,6 +564,8 @@ pub struct LiftGammaGain {
 
 /// IPP2 only, Color Decision List settings.
 #[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlopeOffsetPower {
     pub slope: f32,
     pub offset: f32,
@@ -526,3 +589,92 @@ pub struct Axes {
     pub y: f32,
     pub z: f32,
 }
+
+impl_try_from_i32!(FileId { Unknown, R3D, NevNraw, R3dNe });
+impl_try_from_i32!(FileType { Invalid, Clip, Metadata, Lut, Audio });
+impl_try_from_i32!(VideoDecodeMode { FullResPremium, HalfResPremium, HalfResGood, QuarterResGood, EightResGood, SixteenthResGood });
+impl_try_from_i32!(VideoPixelType { Rgb16bitInterleaved, RgbHalfFloatInterleaved, RgbHalfFloatAcesInt, Rgb16bitPlanar, Bgr8bitInterleaved, Bgra8bitInterleaved, Dpx10bitMethodB });
+impl_try_from_i32!(HdrBlendAlgorithm { HDRxSimpleBlend, HDRxMagicMotion });
+impl_try_from_i32!(HdrMode { UseTrackNo, DoBlend });
+impl_try_from_i32!(MetadataType { Invalid, Int, String, Float });
+impl_try_from_i32!(MarkerType { SubClip, StillFrame });
+impl_try_from_i32!(ImagePipeline { PrimaryDevelopmentOnly, FullGraded });
+impl_try_from_i32!(RollOff { VerySoft, Soft, Medium, Hard, None });
+impl_try_from_i32!(ToneMap { Low, Medium, High, None });
+impl_try_from_i32!(ImageDetail { Low, Medium, High });
+impl_try_from_i32!(ImageOLPFCompensation { Off, Low, Medium, High });
+impl_try_from_i32!(ImageDenoise { Off, Minimum, Milder, Mild, Medium, Strong, Maximum });
+impl_try_from_i32!(FlashingPixelAdjust { Off, Mild, Medium, Strong });
+impl_try_from_i32!(ColorVersion { Version1, Version2, Version3, VersionBC });
+impl_try_from_i32!(ImageGammaCurve { Linear, SRGB, HDR2084, BT1886, Log3G12, Log3G10, REDlogFilm, HybridLogGamma, Gamma2_2, Gamma2_6,
+                                     Rec709, REDgamma4, PDlog685, PDlog985, CustomPDlog, REDspace, REDlog, REDgamma, REDgamma2, REDgamma3 });
+impl_try_from_i32!(ImageColorSpace { REDWideGamutRGB, Rec2020, Rec709, SRGB, Adobe1998, DCIP3, ProPhotoRGB, DCIP3D65,
+                                     DRAGONcolor2, REDcolor4, CameraRGB, REDspace, REDcolor, REDcolor2, REDcolor3, DRAGONcolor });
+
+impl ImageGammaCurve {
+    /// Whether the curve is applied with this color version, instead of being ignored or replaced by the SDK.
+    /// Broadcast Color is limited to BT1886, HDR2084 (PQ) and Hybrid Log Gamma.
+    pub fn is_supported_by(self, version: ColorVersion) -> bool {
+        use ImageGammaCurve::*;
+        let legacy_only = matches!(self, Rec709 | REDgamma4 | PDlog685 | PDlog985 | CustomPDlog | REDspace | REDlog | REDgamma | REDgamma2 | REDgamma3);
+        let ipp2_only = matches!(self, HybridLogGamma | Gamma2_2 | Gamma2_6);
+        match version {
+            ColorVersion::Version1  => !ipp2_only && self != REDlogFilm,
+            ColorVersion::Version2  => !ipp2_only,
+            ColorVersion::Version3  => !legacy_only,
+            ColorVersion::VersionBC => matches!(self, BT1886 | HDR2084 | HybridLogGamma),
+        }
+    }
+}
+
+impl ImageColorSpace {
+    /// Whether the color space is applied with this color version, instead of being ignored or replaced by the SDK.
+    /// Broadcast Color is limited to Rec709, Rec2020 and DCI-P3 D65.
+    pub fn is_supported_by(self, version: ColorVersion) -> bool {
+        use ImageColorSpace::*;
+        let legacy_only = matches!(self, DRAGONcolor2 | REDcolor4 | CameraRGB | REDspace | REDcolor | REDcolor2 | REDcolor3 | DRAGONcolor);
+        match version {
+            ColorVersion::Version1  => !matches!(self, REDWideGamutRGB | Rec2020 | DCIP3 | ProPhotoRGB | DCIP3D65),
+            ColorVersion::Version2  => self != DCIP3D65,
+            ColorVersion::Version3  => !legacy_only,
+            ColorVersion::VersionBC => matches!(self, Rec709 | Rec2020 | DCIP3D65),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RedError;
+
+    #[test]
+    fn try_from_i32() {
+        assert_eq!(ImageGammaCurve::try_from(ImageGammaCurve::Log3G10 as i32).unwrap(), ImageGammaCurve::Log3G10);
+        assert_eq!(ColorVersion::try_from(ColorVersion::VersionBC as i32).unwrap(), ColorVersion::VersionBC);
+        assert!(matches!(ImageGammaCurve::try_from(999), Err(RedError::InvalidEnumValue { name: "ImageGammaCurve", value: 999 })));
+        assert!(matches!(VideoPixelType::try_from(-1), Err(RedError::InvalidEnumValue { name: "VideoPixelType", value: -1 })));
+    }
+
+    #[test]
+    fn status_codes() {
+        assert_eq!(DecodeStatus::from(0), DecodeStatus::Ok);
+        assert_eq!(DecodeStatus::from(999), DecodeStatus::Unknown(999));
+        assert_eq!(DecodeStatus::Unknown(999).code(), 999);
+        assert_eq!(i32::from(LoadStatus::NotInitialized), 8);
+        assert_eq!(LoadStatus::from(LoadStatus::ClipIsEmpty.code()), LoadStatus::ClipIsEmpty);
+        assert!(matches!(RedError::from(DecodeStatus::from(999)), RedError::UnknownStatus { name: "DecodeStatus", code: 999 }));
+    }
+
+    #[test]
+    fn supported_by_color_version() {
+        assert!(ImageGammaCurve::REDgamma4.is_supported_by(ColorVersion::Version2));
+        assert!(!ImageGammaCurve::REDgamma4.is_supported_by(ColorVersion::Version3));
+        assert!(!ImageGammaCurve::HybridLogGamma.is_supported_by(ColorVersion::Version2));
+        assert!(!ImageGammaCurve::REDlogFilm.is_supported_by(ColorVersion::Version1));
+        assert!(ImageGammaCurve::HDR2084.is_supported_by(ColorVersion::VersionBC));
+        assert!(!ImageGammaCurve::Log3G10.is_supported_by(ColorVersion::VersionBC));
+        assert!(ImageColorSpace::DCIP3D65.is_supported_by(ColorVersion::Version3));
+        assert!(!ImageColorSpace::DCIP3D65.is_supported_by(ColorVersion::Version2));
+        assert!(!ImageColorSpace::REDcolor4.is_supported_by(ColorVersion::Version3));
+    }
+}
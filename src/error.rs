@@ -27,6 +27,10 @@ pub enum RedError {
     InvalidPath,
     InternalError,
     MetalNotAvailable,
+    /// `Sdk::initialize` wasn't called or all `Sdk` guards were dropped
+    SdkNotInitialized,
+    /// The SDK is already initialized with these components, which don't include the requested ones
+    SdkAlreadyInitialized(InitializeFlags),
 
     // Clip errors
     ClipPathNotFound,
@@ -90,8 +94,187 @@ pub enum RedError {
     MetalError(i32),
 
     BufferTooSmall { needed: usize, provided: usize },
+    /// The allocator couldn't provide an output buffer of this size and alignment
+    AllocationFailed { size: usize, alignment: usize },
+    /// Frame metadata was read from a job which didn't request it
+    FrameMetadataNotRequested,
+    /// The clip doesn't support extended highlights
+    ExtendedHighlightsUnsupported,
+    /// The decode callback didn't fire before the deadline, see `CallbackFuture::with_deadline`.
+    Timeout,
     Alloc(std::alloc::LayoutError),
+    /// Status code `name::Unknown(code)`, not known to this version of the crate
+    UnknownStatus { name: &'static str, code: i32 },
+    /// Integer which doesn't match any variant of the enum `name`
+    InvalidEnumValue { name: &'static str, value: i32 },
+    /// The gamma curve isn't available with this color version or image pipeline
+    UnsupportedGammaCurve { gamma_curve: ImageGammaCurve, version: ColorVersion, pipeline: ImagePipeline },
+    /// The color space isn't available with this color version or image pipeline
+    UnsupportedColorSpace { color_space: ImageColorSpace, version: ColorVersion, pipeline: ImagePipeline },
+    /// Image processing setting outside of its `ImageProcessingLimits`
+    OutOfRange(Box<RangeError>),
+    /// Reading or writing a file (CDL, LUT) failed
+    Io(std::sync::Arc<std::io::Error>),
+    /// Malformed `format` file (e.g. `"ASC CDL"`, `"EDL"`)
+    Parse { format: &'static str, message: String },
     Other(String),
+
+    /// `source` with information about the call which failed, see [`RedError::context`].
+    /// Use [`RedError::root`] to match on the underlying error.
+    WithContext { context: Box<ErrorContext>, source: Box<RedError> },
+}
+
+/// Where an error happened. Attached to the errors returned by `Clip`, `R3dDecoder`, `AsyncDecoder` and `GpuDecoder`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// API call which failed, e.g. `"Clip::decode_video_frame"`
+    pub operation: &'static str,
+    pub clip_path: Option<String>,
+    pub video_frame_no: Option<usize>,
+    pub video_track_no: Option<usize>,
+    /// Raw status code returned by the SDK, if the error came from the SDK
+    pub status: Option<i32>,
+}
+
+impl ErrorContext {
+    pub fn new(operation: &'static str) -> Self {
+        Self { operation, ..Default::default() }
+    }
+    pub fn clip_path(mut self, v: Option<&str>) -> Self { self.clip_path = v.map(str::to_owned); self }
+    pub fn video_frame_no(mut self, v: usize) -> Self { self.video_frame_no = Some(v); self }
+    pub fn video_track_no(mut self, v: usize) -> Self { self.video_track_no = Some(v); self }
+    pub fn status(mut self, v: i32) -> Self { self.status = Some(v); self }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.operation)?;
+        let mut details = Vec::new();
+        if let Some(path) = &self.clip_path { details.push(format!("clip {path:?}")); }
+        if let Some(frame) = self.video_frame_no { details.push(format!("frame {frame}")); }
+        if let Some(track) = self.video_track_no { details.push(format!("track {track}")); }
+        if let Some(status) = self.status { details.push(format!("status {status}")); }
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Image processing setting outside of the range allowed by `ImageProcessingLimits`.
+/// Returned by the `try_set_*` functions of `ImageProcessingSettings` and by `GradePreset::validate`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeError {
+    /// Name of the setting, e.g. `"kelvin"` or `"lgg_red.gamma"`
+    pub field: &'static str,
+    pub value: f32,
+    pub allowed: AllowedValues,
+}
+
+/// Values accepted for a setting, see [`RangeError`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AllowedValues {
+    /// Inclusive range
+    Range { min: f32, max: f32 },
+    /// One of the listed values, e.g. `ImageProcessingLimits::iso_list`
+    OneOf(Vec<f32>),
+}
+
+impl std::fmt::Display for AllowedValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Range { min, max } => write!(f, "{min} -- {max}"),
+            Self::OneOf(values) => write!(f, "one of {}", values.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
+        }
+    }
+}
+
+impl std::fmt::Display for RangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} is invalid, expected {}", self.field, self.value, self.allowed)
+    }
+}
+impl std::error::Error for RangeError { }
+
+/// Broad category of a [`RedError`], see [`RedError::kind`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The SDK or one of its dynamic libraries couldn't be loaded or initialized
+    Initialization,
+    /// File not found, couldn't be opened or read
+    Io,
+    /// Damaged clip or frame: empty clip, dropped frame, failed decode or CRC check
+    CorruptData,
+    /// Clip format, parameter, feature or GPU device not supported
+    Unsupported,
+    /// Invalid parameters, jobs, buffers or API use (e.g. decoding without an open clip)
+    Configuration,
+    /// Out of host or device memory
+    OutOfMemory,
+    /// The decode was cancelled
+    Cancelled,
+    /// The decode didn't finish before the deadline
+    Timeout,
+    /// GPU API errors, unknown and internal SDK errors
+    Other,
+}
+
+impl RedError {
+    /// Wrap the error with information about the failed call
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        Self::WithContext { context: Box::new(context), source: Box::new(self) }
+    }
+
+    /// Outermost context attached to this error
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The underlying error without any context, for matching on the variant
+    pub fn root(&self) -> &RedError {
+        match self {
+            Self::WithContext { source, .. } => source.root(),
+            _ => self,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::LibraryNotLoaded | Self::R3DSDKLibraryNotFound | Self::RedCudaLibraryNotFound | Self::RedOpenCLLibraryNotFound |
+            Self::R3DDecoderLibraryNotFound | Self::RedMetalLibraryNotFound | Self::LibraryVersionMismatch |
+            Self::InvalidR3DSDKLibrary | Self::InvalidRedCudaLibrary | Self::InvalidRedOpenCLLibrary | Self::InvalidR3DDecoderLibrary | Self::InvalidRedMetalLibrary |
+            Self::RedCudaLibraryInitializeFailed | Self::RedOpenCLLibraryInitializeFailed | Self::R3DDecoderLibraryInitializeFailed |
+            Self::R3DSDKLibraryInitializeFailed | Self::RedMetalLibraryInitializeFailed | Self::UnableToLoadLibrary => ErrorKind::Initialization,
+
+            Self::ClipPathNotFound | Self::ClipFailedToOpenFile | Self::CannotReadFromFile | Self::Io(_) => ErrorKind::Io,
+
+            Self::ClipIsEmpty | Self::IsDroppedFrame | Self::DecodeFailed | Self::Parse { .. } => ErrorKind::CorruptData,
+
+            Self::ClipNotAnR3DFile | Self::UnsupportedClipFormat | Self::ParameterUnsupported | Self::NotAnHDRxClip | Self::MetalNotAvailable |
+            Self::UnableToUseGPUDevice | Self::InvalidJobParameterColorVersion1 | Self::ExtendedHighlightsUnsupported => ErrorKind::Unsupported,
+
+            Self::InvalidPath | Self::SdkNotInitialized | Self::SdkAlreadyInitialized(_) | Self::ClipNoClipOpen | Self::ClipNotInitialized |
+            Self::OutputBufferInvalid | Self::RequestOutOfRange | Self::InvalidParameter | Self::NoClipOpen | Self::InvalidPixelType |
+            Self::DecoderNotOpened | Self::InvalidJobParameter | Self::InvalidJobParameterMode | Self::InvalidJobParameterRawHostMem |
+            Self::InvalidJobParameterRawDeviceMem | Self::InvalidJobParameterPixelType | Self::InvalidJobParameterOutputDeviceMemSize |
+            Self::InvalidJobParameterOutputDeviceMem | Self::InvalidJobParameterClip | Self::NoGPUDeviceSpecified | Self::InvalidJobParameterDeviceId |
+            Self::BufferNotAligned | Self::InvalidAPIObject | Self::BufferTooSmall { .. } | Self::Alloc(_) | Self::FrameMetadataNotRequested | Self::OutOfRange(_) |
+            Self::InvalidEnumValue { .. } | Self::UnsupportedGammaCurve { .. } | Self::UnsupportedColorSpace { .. } => ErrorKind::Configuration,
+
+            Self::ClipOutOfMemory | Self::OutOfMemory | Self::AllocationFailed { .. } => ErrorKind::OutOfMemory,
+
+            Self::Cancelled => ErrorKind::Cancelled,
+            Self::Timeout => ErrorKind::Timeout,
+
+            Self::InternalError | Self::ClipUnknownError | Self::UnknownError | Self::ErrorProcessing |
+            Self::CudaError(_) | Self::OpenCLError(_) | Self::MetalError(_) | Self::UnknownStatus { .. } | Self::Other(_) => ErrorKind::Other,
+
+            Self::WithContext { source, .. } => source.kind(),
+        }
+    }
 }
 
 impl std::fmt::Display for RedError {
@@ -117,6 +300,8 @@ impl std::fmt::Display for RedError {
             Self::InvalidPath                       => write!(f, "Invalid path"),
             Self::InternalError                     => write!(f, "Internal error"),
             Self::MetalNotAvailable                 => write!(f, "Metal not available"),
+            Self::SdkNotInitialized                 => write!(f, "R3D SDK is not initialized"),
+            Self::SdkAlreadyInitialized(flags)      => write!(f, "R3D SDK is already initialized with {flags:?}"),
 
             Self::ClipPathNotFound                  => write!(f, "Clip path not found"),
             Self::ClipFailedToOpenFile              => write!(f, "Clip failed to open file"),
@@ -161,16 +346,30 @@ impl std::fmt::Display for RedError {
             Self::BufferNotAligned                  => write!(f, "Buffer not aligned"),
             Self::InvalidAPIObject                  => write!(f, "Invalid API object"),
             Self::BufferTooSmall { needed, provided } => write!(f, "Buffer too small: needed {needed} bytes, provided {provided} bytes"),
+            Self::AllocationFailed { size, alignment } => write!(f, "Failed to allocate {size} bytes aligned to {alignment} bytes"),
+            Self::FrameMetadataNotRequested         => write!(f, "Frame metadata was not requested in the job"),
+            Self::ExtendedHighlightsUnsupported     => write!(f, "Extended highlights are not supported by the clip"),
 
             Self::CudaError(cuda_error)             => write!(f, "CUDA error: {cuda_error}"),
             Self::OpenCLError(opencl_error)         => write!(f, "OpenCL error: {opencl_error}"),
             Self::MetalError(metal_error)           => write!(f, "Metal error: {metal_error}"),
             Self::Alloc(e)                          => write!(f, "Allocation error: {e}"),
+            Self::OutOfRange(e)                     => write!(f, "Invalid setting: {e}"),
+            Self::UnknownStatus { name, code }      => write!(f, "Unknown {name} code: {code}"),
+            Self::InvalidEnumValue { name, value }  => write!(f, "Invalid {name} value: {value}"),
+            Self::UnsupportedGammaCurve { gamma_curve, version, pipeline } => write!(f, "Gamma curve {gamma_curve:?} is not available with {version:?} ({pipeline:?})"),
+            Self::UnsupportedColorSpace { color_space, version, pipeline } => write!(f, "Color space {color_space:?} is not available with {version:?} ({pipeline:?})"),
+            Self::Timeout                           => write!(f, "Timed out waiting for the decode to finish"),
+            Self::Io(e)                             => write!(f, "I/O error: {e}"),
+            Self::Parse { format, message }         => write!(f, "Invalid {format}: {message}"),
 
             Self::Other(s)                          => write!(f, "RED error: {s}"),
+            Self::WithContext { context, source }   => write!(f, "{context}: {source}"),
         }
     }
 }
+// `Display` already includes the wrapped error, so `source()` isn't implemented to avoid printing it twice
+// when walking the chain. Use `RedError::root` to get the underlying error.
 impl std::error::Error for RedError { }
 
 impl From<InitializeStatus> for RedError {
@@ -197,6 +396,7 @@ impl From<InitializeStatus> for RedError {
             InitializeStatus::InternalError                     => Self::InternalError,
             InitializeStatus::MetalNotAvailable                 => Self::MetalNotAvailable,
             InitializeStatus::Ok                                => panic!("Cannot convert ISInitializeOK to RedError"),
+            InitializeStatus::Unknown(code)                     => Self::UnknownStatus { name: "InitializeStatus", code },
         }
     }
 }
@@ -212,6 +412,7 @@ impl From<LoadStatus> for RedError {
             LoadStatus::UnknownError      => Self::ClipUnknownError,
             LoadStatus::NoClipOpen        => Self::ClipNoClipOpen,
             LoadStatus::NotInitialized    => Self::ClipNotInitialized,
+            LoadStatus::Unknown(code)     => Self::UnknownStatus { name: "LoadStatus", code },
         }
     }
 }
@@ -235,6 +436,7 @@ impl From<DecodeStatus> for RedError {
             DecodeStatus::UnsupportedClipFormat  => Self::UnsupportedClipFormat,
             DecodeStatus::ParameterUnsupported   => Self::ParameterUnsupported,
             DecodeStatus::DecoderNotOpened       => Self::DecoderNotOpened,
+            DecodeStatus::Unknown(code)          => Self::UnknownStatus { name: "DecodeStatus", code },
         }
     }
 }
@@ -257,6 +459,7 @@ impl From<R3DStatus> for RedError {
             R3DStatus::NoGPUDeviceSpecified            => Self::NoGPUDeviceSpecified,
             R3DStatus::UnableToLoadLibrary             => Self::UnableToLoadLibrary,
             R3DStatus::ParameterUnsupported            => Self::ParameterUnsupported,
+            R3DStatus::Unknown(code)                   => Self::UnknownStatus { name: "R3DStatus", code },
         }
     }
 }
@@ -266,4 +469,79 @@ impl From<std::alloc::LayoutError> for RedError {
     }
 }
 
+impl From<std::io::Error> for RedError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(std::sync::Arc::new(value))
+    }
+}
+
+impl From<RangeError> for RedError {
+    fn from(value: RangeError) -> Self {
+        Self::OutOfRange(Box::new(value))
+    }
+}
+
 pub type RedResult<T> = Result<T, RedError>;
+
+/// Error of a failed asynchronous decode. Gives back the submitted job,
+/// so its output buffer can be reused and the failing frame can be logged or retried.
+pub struct DecodeFailure<T> {
+    /// `None` if the decode timed out, in that case the job is still owned by the SDK.
+    pub job: Option<T>,
+    pub error: RedError,
+}
+impl<T> std::fmt::Debug for DecodeFailure<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodeFailure")
+            .field("error", &self.error)
+            .field("has_job", &self.job.is_some())
+            .finish()
+    }
+}
+impl<T> std::fmt::Display for DecodeFailure<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+impl<T> std::error::Error for DecodeFailure<T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.error)
+    }
+}
+
+impl<T> From<DecodeFailure<T>> for RedError {
+    fn from(value: DecodeFailure<T>) -> Self {
+        value.error
+    }
+}
+
+/// Result of an asynchronous decode, see [`DecodeFailure`].
+pub type DecodeResult<T> = Result<T, DecodeFailure<T>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    fn chain(e: &dyn Error) -> Vec<String> {
+        let mut messages = vec![e.to_string()];
+        let mut source = e.source();
+        while let Some(e) = source {
+            messages.push(e.to_string());
+            source = e.source();
+        }
+        messages
+    }
+
+    #[test]
+    fn error_chain_without_duplicates() {
+        let range = RedError::OutOfRange(Box::new(RangeError { field: "kelvin", value: 1.0, allowed: AllowedValues::Range { min: 1700.0, max: 50000.0 } }));
+        let io = RedError::Io(std::sync::Arc::new(std::io::Error::new(std::io::ErrorKind::NotFound, "grade.cdl")));
+        let e = range.with_context(ErrorContext::new("GradePreset::validate")).with_context(ErrorContext::new("Clip::decode_video_frame").video_frame_no(3));
+
+        assert_eq!(chain(&e), ["Clip::decode_video_frame (frame 3): GradePreset::validate: Invalid setting: kelvin 1 is invalid, expected 1700 -- 50000"]);
+        assert_eq!(chain(&io), ["I/O error: grade.cdl"]);
+        assert!(matches!(e.root(), RedError::OutOfRange(_)));
+        assert_eq!(e.context().map(|c| c.operation), Some("Clip::decode_video_frame"));
+    }
+}
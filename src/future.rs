@@ -1,31 +1,102 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
-use std::{ future::Future, pin::Pin, sync::{ atomic::{ AtomicBool, Ordering}, Arc, Mutex }, task::{ Context, Poll }};
+use std::{ future::Future, pin::Pin, sync::{ atomic::{ AtomicBool, Ordering}, Arc, Weak, Mutex, Condvar, OnceLock }, task::{ Context, Poll }};
+use std::{ collections::{ BinaryHeap, HashSet }, cmp::Ordering as CmpOrdering, time::{ Duration, Instant } };
 use futures_util::task::AtomicWaker;
+use crate::metrics::{ JobMetrics, JobScope };
 use super::*;
 
 pub(crate) struct State<T> {
     pub(crate) waker: AtomicWaker,
     pub(crate) done: AtomicBool,
-    pub(crate) result: Mutex<Option<Result<T, RedError>>>,
-    pub(crate) job: Option<T>,
+    pub(crate) result: Mutex<Option<DecodeResult<T>>>,
+    pub(crate) job: Mutex<Option<T>>,
+    pub(crate) metrics: Mutex<Option<JobMetrics>>,
+    pub(crate) tracker: Arc<JobTracker>,
+    /// Asks the SDK to abort the job when the future times out, if the job type supports it
+    abort: Option<fn(&mut T)>,
 }
 impl<T> State<T> {
-    pub(crate) fn new(job: T) -> Self {
+    pub(crate) fn new(job: T, metrics: JobMetrics, tracker: Arc<JobTracker>) -> Self {
         Self {
             waker: AtomicWaker::new(),
             done: AtomicBool::new(false),
             result: Mutex::new(None),
-            job: Some(job),
+            job: Mutex::new(Some(job)),
+            metrics: Mutex::new(Some(metrics)),
+            tracker,
+            abort: None,
         }
     }
+
+    /// Call `abort` on the job if the future times out before the callback fired
+    pub(crate) fn abort_on_timeout(mut self, abort: fn(&mut T)) -> Self {
+        self.abort = Some(abort);
+        self
+    }
+
+    /// Enter the job's `tracing` span, `None` once the job finished.
+    pub(crate) fn enter_span(&self) -> Option<JobScope> {
+        self.metrics.lock().unwrap().as_ref().map(JobMetrics::enter)
+    }
+
+    /// Record the end of the job in the decode metrics.
+    pub(crate) fn finish_metrics<S: std::fmt::Debug>(&self, result: Result<usize, S>) {
+        if let Some(metrics) = self.metrics.lock().unwrap().take() {
+            metrics.finish(result);
+        }
+    }
+
+    /// Store the result and wake the future. If the future was already completed (e.g. it timed out), the result
+    /// is handed back instead, so the callback can leave releasing the job to [`JobTracker::finish`].
+    pub(crate) fn complete(&self, result: DecodeResult<T>) -> Option<DecodeResult<T>> {
+        {
+            let mut lock = self.result.lock().unwrap();
+            if self.done.load(Ordering::Acquire) {
+                return Some(result);
+            }
+            *lock = Some(result);
+            self.done.store(true, Ordering::Release);
+        }
+        self.waker.wake();
+        None
+    }
 }
 pub struct CallbackFuture<T> {
     pub(crate) state: Arc<State<T>>,
 }
+impl<T: Send + 'static> CallbackFuture<T> {
+    pub(crate) fn new(state: Arc<State<T>>, timeout: Option<Duration>) -> Self {
+        let fut = Self { state };
+        match timeout {
+            Some(timeout) => fut.with_timeout(timeout),
+            None => fut,
+        }
+    }
+
+    /// Resolve to `RedError::Timeout` if the SDK callback didn't fire before `deadline`.
+    ///
+    /// On timeout the job (and its output buffer) stays owned by the decoder until the callback eventually fires,
+    /// `DecodeFailure::job` is `None` in that case. The job is then released on the next submission to the decoder
+    /// or when the decoder is released, never on the SDK thread inside its callback. `AsyncDecoder` and `GpuDecoder`
+    /// jobs are aborted (`AbortDecode`) so the callback fires as soon as possible; jobs submitted to `R3dDecoder`
+    /// can't be aborted and run to completion.
+    ///
+    /// The SDK keeps using the clip passed to `set_clip` until the callback fires, so the clip must outlive a timed out job.
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        let state: Arc<dyn Expire> = self.state.clone();
+        timer().add(deadline, Arc::downgrade(&state));
+        self
+    }
+
+    /// Resolve to `RedError::Timeout` if the SDK callback didn't fire within `timeout`, see [`with_deadline`](Self::with_deadline).
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+}
 impl<T> Future for CallbackFuture<T> {
-    type Output = Result<T, RedError>;
+    type Output = DecodeResult<T>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
@@ -38,3 +109,212 @@ impl<T> Future for CallbackFuture<T> {
         Poll::Pending
     }
 }
+
+trait Expire: Send + Sync {
+    fn expire(&self);
+}
+impl<T: Send> Expire for State<T> {
+    fn expire(&self) {
+        // The callback takes the job out of the state under this lock, so the job can't be released while it's aborted
+        if let Some(abort) = self.abort && let Some(job) = self.job.lock().unwrap().as_mut() {
+            abort(job);
+        }
+        // No job to release, it's still owned by the state
+        let _ = self.complete(Err(DecodeFailure { job: None, error: RedError::Timeout }));
+    }
+}
+impl Expire for JobTracker {
+    /// Wake the shutdown future at the release deadline
+    fn expire(&self) {
+        self.waker.wake();
+    }
+}
+
+struct TimerEntry {
+    deadline: Instant,
+    state: Weak<dyn Expire>,
+}
+impl PartialEq for TimerEntry { fn eq(&self, other: &Self) -> bool { self.deadline == other.deadline } }
+impl Eq for TimerEntry { }
+impl PartialOrd for TimerEntry { fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> { Some(self.cmp(other)) } }
+// Reversed, so the BinaryHeap pops the earliest deadline first
+impl Ord for TimerEntry { fn cmp(&self, other: &Self) -> CmpOrdering { other.deadline.cmp(&self.deadline) } }
+
+/// Single background thread expiring the futures with a deadline, so this works with any executor.
+struct Timer {
+    queue: Mutex<BinaryHeap<TimerEntry>>,
+    cond: Condvar,
+}
+impl Timer {
+    fn add(&self, deadline: Instant, state: Weak<dyn Expire>) {
+        self.queue.lock().unwrap().push(TimerEntry { deadline, state });
+        self.cond.notify_one();
+    }
+    fn run(&self) {
+        let mut expired = Vec::new();
+        loop {
+            {
+                let mut queue = self.queue.lock().unwrap();
+                loop {
+                    let now = Instant::now();
+                    while queue.peek().is_some_and(|x| x.deadline <= now) {
+                        expired.push(queue.pop().unwrap().state);
+                    }
+                    if !expired.is_empty() {
+                        break;
+                    }
+                    queue = match queue.peek() {
+                        Some(next) => { let wait = next.deadline - now; self.cond.wait_timeout(queue, wait).unwrap().0 }
+                        None => self.cond.wait(queue).unwrap(),
+                    };
+                }
+            }
+            // Wake outside of the lock. Futures which were already dropped don't need to be expired.
+            for state in expired.drain(..) {
+                if let Some(state) = state.upgrade() {
+                    state.expire();
+                }
+            }
+        }
+    }
+}
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    let mut spawn = false;
+    let timer = TIMER.get_or_init(|| {
+        spawn = true;
+        Timer { queue: Mutex::new(BinaryHeap::new()), cond: Condvar::new() }
+    });
+    if spawn {
+        std::thread::Builder::new()
+            .name("r3d-timeout".into())
+            .spawn(move || timer.run())
+            .expect("failed to spawn the timeout thread");
+    }
+    timer
+}
+
+/// How long dropping a decoder waits for the callbacks of its pending jobs if it has no default timeout
+pub(crate) const RELEASE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Keeps track of the jobs submitted to a decoder whose callback didn't return yet,
+/// so the native decoder is only released once no callback can run anymore.
+#[derive(Default)]
+pub(crate) struct JobTracker {
+    jobs: Mutex<Jobs>,
+    cond: Condvar,
+    waker: AtomicWaker,
+    release_deadline: Mutex<Option<Instant>>,
+    /// Kept by the callbacks so nothing is released on the SDK thread, see [`finish`](Self::finish)
+    retired: Mutex<Vec<Box<dyn Send>>>,
+}
+#[derive(Default)]
+struct Jobs {
+    /// Jobs which weren't handed back to the user yet, so they can still be aborted
+    running: HashSet<usize>,
+    /// Jobs whose callback didn't finish yet
+    pending: usize,
+}
+impl JobTracker {
+    /// Must be called before the job is submitted, the callback can fire before the submit call returns.
+    pub(crate) fn add(&self, job_ptr: *mut core::ffi::c_void) {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.running.insert(job_ptr as usize);
+        jobs.pending += 1;
+    }
+    /// Must be called before the job is handed back to the user, so `for_each` never sees a released job.
+    pub(crate) fn detach(&self, job_ptr: *mut core::ffi::c_void) {
+        self.jobs.lock().unwrap().running.remove(&(job_ptr as usize));
+    }
+    /// Called when the job was not submitted, the callbacks use [`detach`](Self::detach) and [`finish`](Self::finish) instead.
+    pub(crate) fn remove(&self, job_ptr: *mut core::ffi::c_void) {
+        self.detach(job_ptr);
+        self.done();
+    }
+    /// Last step of a job's callback. `retired` (the callback's reference to the job state and a result the future
+    /// didn't take because it timed out) is kept until [`release_retired`](Self::release_retired), so no job is released
+    /// on the SDK thread inside its own callback. Finishing the last job lets the decoder and then the SDK be released,
+    /// so the callback must not do anything else after this.
+    pub(crate) fn finish(&self, retired: Box<dyn Send>) {
+        self.retired.lock().unwrap().push(retired);
+        self.done();
+    }
+    fn done(&self) {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.pending -= 1;
+        if jobs.pending == 0 {
+            self.cond.notify_all();
+            self.waker.wake();
+        }
+    }
+    /// Release what the callbacks retired. Called on the user's thread: before each submission and after the decoder was released.
+    pub(crate) fn release_retired(&self) {
+        let retired = std::mem::take(&mut *self.retired.lock().unwrap());
+        drop(retired);
+    }
+    /// Call `f` for every running job. The jobs can't be handed back while this is running.
+    pub(crate) fn for_each(&self, f: impl Fn(*mut core::ffi::c_void)) {
+        for &job_ptr in self.jobs.lock().unwrap().running.iter() {
+            f(job_ptr as *mut core::ffi::c_void);
+        }
+    }
+    /// Block until the callbacks of all pending jobs finished, `false` if some are still pending at `deadline`
+    pub(crate) fn wait_idle(&self, deadline: Instant) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        while jobs.pending > 0 {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            jobs = self.cond.wait_timeout(jobs, deadline - now).unwrap().0;
+        }
+        true
+    }
+    /// Deadline for releasing the decoder: `timeout` (or [`RELEASE_TIMEOUT`]) from the first call, i.e. from `shutdown`/`close` or the drop
+    fn release_deadline(&self, timeout: Option<Duration>) -> Instant {
+        *self.release_deadline.lock().unwrap().get_or_insert_with(|| Instant::now() + timeout.unwrap_or(RELEASE_TIMEOUT))
+    }
+    /// Wait until the decoder can be released. If some callbacks still didn't fire at the release deadline,
+    /// log a warning and return `false`: the native decoder must then be leaked, so the callbacks never touch freed memory.
+    pub(crate) fn wait_release(&self, timeout: Option<Duration>, decoder: &str) -> bool {
+        if self.wait_idle(self.release_deadline(timeout)) {
+            return true;
+        }
+        log::warn!("{decoder} dropped with {} jobs still pending after the release deadline, leaking the native decoder", self.jobs.lock().unwrap().pending);
+        false
+    }
+    pub(crate) fn poll_idle(&self, cx: &mut Context<'_>) -> Poll<()> {
+        self.waker.register(cx.waker());
+        if self.jobs.lock().unwrap().pending == 0 { Poll::Ready(()) } else { Poll::Pending }
+    }
+}
+
+/// Future returned by the decoder shutdown functions. Resolves once all pending jobs got their callback
+/// or at the release deadline, then drops the decoder. If it's dropped before that, the decoder's `Drop` blocks until then instead.
+pub(crate) struct DecoderShutdown<D> {
+    decoder: Option<D>,
+    tracker: Arc<JobTracker>,
+    deadline: Instant,
+}
+impl<D> DecoderShutdown<D> {
+    pub(crate) fn new(decoder: D, tracker: Arc<JobTracker>, timeout: Option<Duration>) -> Self {
+        let deadline = tracker.release_deadline(timeout);
+        let expire: Arc<dyn Expire> = tracker.clone();
+        timer().add(deadline, Arc::downgrade(&expire));
+        Self { decoder: Some(decoder), tracker, deadline }
+    }
+}
+impl<D: Unpin> Future for DecoderShutdown<D> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        if this.tracker.poll_idle(cx).is_pending() && Instant::now() < this.deadline {
+            return Poll::Pending;
+        }
+        // Either nothing is in flight anymore and dropping the decoder releases it right away,
+        // or the deadline passed and the decoder's `Drop` leaks it without waiting again
+        drop(this.decoder.take());
+        Poll::Ready(())
+    }
+}
@@ -0,0 +1,371 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use crate::{ ImageProcessingSettings, HdrProcessingSettings, ImageProcessingLimits as L, RangeError, RedError, RedResult, enums::* };
+use crate::image_processing_settings::{ check_lgg, check_cdl };
+use crate::user_curve::{ UserCurve, check_user_curve, curve_fields };
+
+/// Plain Rust copy of every field of [`ImageProcessingSettings`] (and optionally [`HdrProcessingSettings`]),
+/// for storing, comparing and sharing looks. With the `serde` feature it can be serialized to JSON, TOML etc.
+///
+/// Values are not validated, [`apply_to`](Self::apply_to) copies them as they are.
+/// The 3D LUT is only referenced by its path: the LUT handle of the settings a preset is applied to is kept.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GradePreset {
+    /// Format of the preset, see [`GradePreset::SCHEMA_VERSION`].
+    /// Deserializing a preset with a newer schema version fails.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_schema_version"))]
+    pub schema_version: u32,
+
+    // Core / global
+    pub version: ColorVersion,
+    pub image_pipeline_mode: ImagePipeline,
+    pub kelvin: f32,
+    pub tint: f32,
+    pub iso: usize,
+    pub brightness: f32,
+    pub exposure_compensation: f32,
+    pub exposure_adjust: f32,
+    pub gain_red: f32,
+    pub gain_green: f32,
+    pub gain_blue: f32,
+    pub saturation: f32,
+    pub contrast: f32,
+    pub drx: f32,
+    pub gamma_curve: ImageGammaCurve,
+    pub color_space: ImageColorSpace,
+
+    // Custom PDLog
+    pub custom_pdlog_black_point: usize,
+    pub custom_pdlog_white_point: usize,
+    pub custom_pdlog_gamma: f32,
+
+    // User curves, 5 (x, y) points each
+    pub user_curve: [f32; 10],
+    pub user_curve_red: [f32; 10],
+    pub user_curve_green: [f32; 10],
+    pub user_curve_blue: [f32; 10],
+
+    // Premium decode only
+    pub detail: ImageDetail,
+    pub olpf_compensation: ImageOLPFCompensation,
+    pub denoise: ImageDenoise,
+    /// Shares storage with `chroma_noise_reduction` in the SDK, used with ColorVersion1/2
+    pub deb: bool,
+    /// Shares storage with `deb` in the SDK, used with ColorVersion3/BC
+    pub chroma_noise_reduction: bool,
+    pub flashing_pixel_adjustment: FlashingPixelAdjust,
+
+    // ColorVersion2 only
+    pub shadow: f32,
+    pub flut: f32,
+    pub lgg_red: LiftGammaGain,
+    pub lgg_green: LiftGammaGain,
+    pub lgg_blue: LiftGammaGain,
+
+    // IPP2
+    pub cdl_enabled: bool,
+    pub cdl_saturation: f32,
+    pub cdl_red: SlopeOffsetPower,
+    pub cdl_green: SlopeOffsetPower,
+    pub cdl_blue: SlopeOffsetPower,
+    pub output_tone_map: ToneMap,
+    pub highlight_roll_off: RollOff,
+    pub hdr_peak_nits: u32,
+    pub maskline_adjust: bool,
+    pub lut3d_enabled: bool,
+    /// Path of the 3D LUT the settings were created with, not loaded by [`apply_to`](Self::apply_to)
+    pub lut_path: Option<String>,
+
+    /// HDRx blending, `None` for a single track look
+    pub hdr: Option<HdrPreset>,
+}
+
+/// Plain Rust copy of [`HdrProcessingSettings`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdrPreset {
+    pub blend_algorithm: HdrBlendAlgorithm,
+    /// -1 -- +1
+    pub bias: f32,
+}
+
+impl GradePreset {
+    /// Current format of the preset. Incremented whenever fields are changed in a way older versions can't read.
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Copy of the image processing settings, without HDRx blending.
+    /// `RedError::InvalidEnumValue` if any of the enum fields has a value unknown to this crate.
+    pub fn from_settings(s: &ImageProcessingSettings) -> RedResult<Self> {
+        let curve = |x: &[f32]| -> [f32; 10] { x.try_into().unwrap() };
+        let (deb, chroma_noise_reduction) = (s.deb(), s.chroma_noise_reduction());
+        Ok(Self {
+            schema_version:            Self::SCHEMA_VERSION,
+            version:                   s.version()?,
+            image_pipeline_mode:       s.image_pipeline_mode()?,
+            kelvin:                    s.kelvin(),
+            tint:                      s.tint(),
+            iso:                       s.iso(),
+            brightness:                s.brightness(),
+            exposure_compensation:     s.exposure_compensation(),
+            exposure_adjust:           s.exposure_adjust(),
+            gain_red:                  s.gain_red(),
+            gain_green:                s.gain_green(),
+            gain_blue:                 s.gain_blue(),
+            saturation:                s.saturation(),
+            contrast:                  s.contrast(),
+            drx:                       s.drx(),
+            gamma_curve:               s.gamma_curve()?,
+            color_space:               s.color_space()?,
+            custom_pdlog_black_point:  s.custom_pdlog_black_point(),
+            custom_pdlog_white_point:  s.custom_pdlog_white_point(),
+            custom_pdlog_gamma:        s.custom_pdlog_gamma(),
+            user_curve:                curve(s.user_curve()),
+            user_curve_red:            curve(s.user_curve_red()),
+            user_curve_green:          curve(s.user_curve_green()),
+            user_curve_blue:           curve(s.user_curve_blue()),
+            detail:                    s.detail()?,
+            olpf_compensation:         s.olpf_compensation()?,
+            denoise:                   s.denoise()?,
+            deb,
+            chroma_noise_reduction,
+            flashing_pixel_adjustment: s.flashing_pixel_adjustment()?,
+            shadow:                    s.shadow(),
+            flut:                      s.flut(),
+            lgg_red:                   *s.lgg_red(),
+            lgg_green:                 *s.lgg_green(),
+            lgg_blue:                  *s.lgg_blue(),
+            cdl_enabled:               s.cdl_enabled(),
+            cdl_saturation:            s.cdl_saturation(),
+            cdl_red:                   *s.cdl_red(),
+            cdl_green:                 *s.cdl_green(),
+            cdl_blue:                  *s.cdl_blue(),
+            output_tone_map:           s.output_tone_map()?,
+            highlight_roll_off:        s.highlight_roll_off()?,
+            hdr_peak_nits:             s.hdr_peak_nits(),
+            maskline_adjust:           s.maskline_adjust(),
+            lut3d_enabled:             s.lut3d_enabled(),
+            lut_path:                  s.lut3d_path(),
+            hdr:                       None,
+        })
+    }
+
+    /// Copy of the image processing and HDRx blending settings
+    pub fn from_settings_with_hdr(s: &ImageProcessingSettings, hdr: &HdrProcessingSettings) -> RedResult<Self> {
+        Ok(Self { hdr: Some(HdrPreset::from_settings(hdr)?), ..Self::from_settings(s)? })
+    }
+
+    /// Copy every field to `s`, except the 3D LUT handle
+    pub fn apply_to(&self, s: &mut ImageProcessingSettings) {
+        s.set_version(self.version);
+        s.set_image_pipeline_mode(self.image_pipeline_mode);
+        s.set_kelvin(self.kelvin);
+        s.set_tint(self.tint);
+        s.set_iso(self.iso);
+        s.set_brightness(self.brightness);
+        s.set_exposure_compensation(self.exposure_compensation);
+        s.set_exposure_adjust(self.exposure_adjust);
+        s.set_gain_red(self.gain_red);
+        s.set_gain_green(self.gain_green);
+        s.set_gain_blue(self.gain_blue);
+        s.set_saturation(self.saturation);
+        s.set_contrast(self.contrast);
+        s.set_drx(self.drx);
+        s.set_gamma_curve(self.gamma_curve);
+        s.set_color_space(self.color_space);
+        s.set_custom_pdlog_black_point(self.custom_pdlog_black_point);
+        s.set_custom_pdlog_white_point(self.custom_pdlog_white_point);
+        s.set_custom_pdlog_gamma(self.custom_pdlog_gamma);
+        s.user_curve_mut().copy_from_slice(&self.user_curve);
+        s.user_curve_red_mut().copy_from_slice(&self.user_curve_red);
+        s.user_curve_green_mut().copy_from_slice(&self.user_curve_green);
+        s.user_curve_blue_mut().copy_from_slice(&self.user_curve_blue);
+        s.set_detail(self.detail);
+        s.set_olpf_compensation(self.olpf_compensation);
+        s.set_denoise(self.denoise);
+        // DEB and ChromaNoiseReduction are a union, write the one used by the color version
+        match self.version {
+            ColorVersion::Version1 | ColorVersion::Version2 => s.set_deb(self.deb),
+            ColorVersion::Version3 | ColorVersion::VersionBC => s.set_chroma_noise_reduction(self.chroma_noise_reduction),
+        }
+        s.set_flashing_pixel_adjustment(self.flashing_pixel_adjustment);
+        s.set_shadow(self.shadow);
+        s.set_flut(self.flut);
+        *s.lgg_red_mut() = self.lgg_red;
+        *s.lgg_green_mut() = self.lgg_green;
+        *s.lgg_blue_mut() = self.lgg_blue;
+        s.set_cdl_enabled(self.cdl_enabled);
+        s.set_cdl_saturation(self.cdl_saturation);
+        *s.cdl_red_mut() = self.cdl_red;
+        *s.cdl_green_mut() = self.cdl_green;
+        *s.cdl_blue_mut() = self.cdl_blue;
+        s.set_output_tone_map(self.output_tone_map);
+        s.set_highlight_roll_off(self.highlight_roll_off);
+        s.set_hdr_peak_nits(self.hdr_peak_nits);
+        s.set_maskline_adjust(self.maskline_adjust);
+        s.set_lut3d_enabled(self.lut3d_enabled);
+    }
+
+    /// Check every value against `ImageProcessingLimits` and return all the ones out of range.
+    /// The ISO must be one of `ImageProcessingLimits::iso_list` and the user curve X coordinates strictly increasing, see [`UserCurve::validate`].
+    pub fn validate(&self) -> Result<(), Vec<RangeError>> {
+        let results = [
+            L::check_iso(self.iso),
+            L::check_range("kelvin",                self.kelvin,                L::kelvin_min(),          L::kelvin_max()),
+            L::check_range("tint",                  self.tint,                  L::tint_min(),            L::tint_max()),
+            L::check_range("exposure_compensation", self.exposure_compensation, L::exposure_min(),        L::exposure_max()),
+            L::check_range("exposure_adjust",       self.exposure_adjust,       L::exposure_adjust_min(), L::exposure_adjust_max()),
+            L::check_range("brightness",            self.brightness,            L::brightness_min(),      L::brightness_max()),
+            L::check_range("gain_red",              self.gain_red,              L::gains_min(),           L::gains_max()),
+            L::check_range("gain_green",            self.gain_green,            L::gains_min(),           L::gains_max()),
+            L::check_range("gain_blue",             self.gain_blue,             L::gains_min(),           L::gains_max()),
+            L::check_range("saturation",            self.saturation,            L::saturation_min(),      L::saturation_max()),
+            L::check_range("contrast",              self.contrast,              L::contrast_min(),        L::contrast_max()),
+            L::check_range("drx",                   self.drx,                   L::drx_min(),             L::drx_max()),
+            L::check_range("shadow",                self.shadow,                L::shadow_min(),          L::shadow_max()),
+            L::check_range("flut",                  self.flut,                  L::flut_min(),            L::flut_max()),
+            L::check_range("custom_pdlog_gamma",    self.custom_pdlog_gamma,    L::custom_pdlog_gamma_min(), L::custom_pdlog_gamma_max()),
+            L::check_range("custom_pdlog_black_point", self.custom_pdlog_black_point as f32, L::custom_pdlog_black_point_min() as f32, L::custom_pdlog_black_point_max() as f32),
+            L::check_range("custom_pdlog_white_point", self.custom_pdlog_white_point as f32, L::custom_pdlog_white_point_min() as f32, L::custom_pdlog_white_point_max() as f32),
+            L::check_range("hdr_peak_nits",         self.hdr_peak_nits as f32,  L::hdr_peak_nits_min() as f32, L::hdr_peak_nits_max() as f32),
+            check_lgg(&self.lgg_red,   ["lgg_red.lift",   "lgg_red.gamma",   "lgg_red.gain"]),
+            check_lgg(&self.lgg_green, ["lgg_green.lift", "lgg_green.gamma", "lgg_green.gain"]),
+            check_lgg(&self.lgg_blue,  ["lgg_blue.lift",  "lgg_blue.gamma",  "lgg_blue.gain"]),
+            check_cdl(&self.cdl_red,   ["cdl_red.slope",   "cdl_red.offset",   "cdl_red.power"]),
+            check_cdl(&self.cdl_green, ["cdl_green.slope", "cdl_green.offset", "cdl_green.power"]),
+            check_cdl(&self.cdl_blue,  ["cdl_blue.slope",  "cdl_blue.offset",  "cdl_blue.power"]),
+            check_user_curve(&UserCurve::from(self.user_curve),       curve_fields!("user_curve")),
+            check_user_curve(&UserCurve::from(self.user_curve_red),   curve_fields!("user_curve_red")),
+            check_user_curve(&UserCurve::from(self.user_curve_green), curve_fields!("user_curve_green")),
+            check_user_curve(&UserCurve::from(self.user_curve_blue),  curve_fields!("user_curve_blue")),
+            self.hdr.map_or(Ok(()), |x| L::check_range("hdr.bias", x.bias, -1.0, 1.0)),
+        ];
+        let errors: Vec<RangeError> = results.into_iter().filter_map(Result::err).collect();
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Check the gamma curve and color space against the color version and image pipeline, see `ImageProcessingLimits::check_gamma_curve`
+    pub fn check_color_pipeline(&self) -> RedResult<()> {
+        L::check_gamma_curve(self.gamma_curve, self.version, self.image_pipeline_mode)?;
+        L::check_color_space(self.color_space, self.version, self.image_pipeline_mode)
+    }
+
+    /// [`validate`](Self::validate) and only then [`apply_to`](Self::apply_to), `s` is left untouched if any value is out of range
+    pub fn try_apply_to(&self, s: &mut ImageProcessingSettings) -> Result<(), Vec<RangeError>> {
+        self.validate()?;
+        self.apply_to(s);
+        Ok(())
+    }
+
+    /// New settings with this preset applied over the SDK defaults, see [`apply_to`](Self::apply_to)
+    pub fn to_settings(&self) -> ImageProcessingSettings {
+        let mut s = ImageProcessingSettings::default();
+        self.apply_to(&mut s);
+        s
+    }
+
+    /// HDRx blending settings, if the preset has them
+    pub fn to_hdr_settings(&self) -> Option<HdrProcessingSettings> {
+        self.hdr.map(|x| x.to_settings())
+    }
+}
+
+impl HdrPreset {
+    pub fn from_settings(s: &HdrProcessingSettings) -> RedResult<Self> {
+        Ok(Self { blend_algorithm: s.hdr_blend_algorithm()?, bias: s.bias() })
+    }
+    pub fn apply_to(&self, s: &mut HdrProcessingSettings) {
+        s.set_hdr_blend_algorithm(self.blend_algorithm);
+        s.set_bias(self.bias);
+    }
+    pub fn to_settings(&self) -> HdrProcessingSettings {
+        let mut s = HdrProcessingSettings::default();
+        self.apply_to(&mut s);
+        s
+    }
+}
+
+impl TryFrom<&ImageProcessingSettings> for GradePreset {
+    type Error = RedError;
+    fn try_from(s: &ImageProcessingSettings) -> RedResult<Self> { Self::from_settings(s) }
+}
+impl From<&GradePreset> for ImageProcessingSettings {
+    fn from(p: &GradePreset) -> Self { p.to_settings() }
+}
+impl TryFrom<&HdrProcessingSettings> for HdrPreset {
+    type Error = RedError;
+    fn try_from(s: &HdrProcessingSettings) -> RedResult<Self> { Self::from_settings(s) }
+}
+impl From<&HdrPreset> for HdrProcessingSettings {
+    fn from(p: &HdrPreset) -> Self { p.to_settings() }
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_schema_version<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let v = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+    if v > GradePreset::SCHEMA_VERSION {
+        return Err(serde::de::Error::custom(format!("grade preset schema version {v} is newer than the supported version {}", GradePreset::SCHEMA_VERSION)));
+    }
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset() -> GradePreset {
+        let mut s = ImageProcessingSettings::default();
+        s.set_version(ColorVersion::Version2);
+        s.set_kelvin(3200.0);
+        s.set_iso(800);
+        s.set_gamma_curve(ImageGammaCurve::REDgamma4);
+        s.set_color_space(ImageColorSpace::Rec709);
+        s.set_detail(ImageDetail::Medium);
+        s.set_deb(true);
+        s.lgg_red_mut().gamma = 1.5;
+        s.cdl_blue_mut().slope = 1.25;
+        s.user_curve_mut()[5] = 0.6;
+        GradePreset::from_settings(&s).unwrap()
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let grade = preset();
+        assert_eq!((grade.kelvin, grade.iso, grade.gamma_curve), (3200.0, 800, ImageGammaCurve::REDgamma4));
+        assert_eq!((grade.lgg_red.gamma, grade.cdl_blue.slope, grade.user_curve[5]), (1.5, 1.25, 0.6));
+        assert!(grade.hdr.is_none());
+        assert_eq!(GradePreset::from_settings(&grade.to_settings()).unwrap(), grade);
+
+        let hdr = HdrPreset { blend_algorithm: HdrBlendAlgorithm::HDRxMagicMotion, bias: 0.5 };
+        let grade = GradePreset::from_settings_with_hdr(&grade.to_settings(), &hdr.to_settings()).unwrap();
+        assert_eq!(grade.hdr, Some(hdr));
+        assert_eq!(HdrPreset::from_settings(&grade.to_hdr_settings().unwrap()).unwrap(), hdr);
+    }
+
+    #[test]
+    fn deb_and_chroma_noise_reduction() {
+        // The union is written through the field of the color version, the other one reads the same value
+        let grade = GradePreset { deb: true, chroma_noise_reduction: false, ..preset() };
+        let s = grade.to_settings();
+        assert!(s.deb() && s.chroma_noise_reduction());
+
+        let grade = GradePreset { version: ColorVersion::Version3, gamma_curve: ImageGammaCurve::Log3G10, color_space: ImageColorSpace::REDWideGamutRGB, ..grade };
+        let s = grade.to_settings();
+        assert!(!s.deb() && !s.chroma_noise_reduction());
+        let s = GradePreset { chroma_noise_reduction: true, ..grade }.to_settings();
+        assert!(s.deb() && s.chroma_noise_reduction());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn newer_schema_version() {
+        let grade = preset();
+        let mut json = serde_json::to_value(&grade).unwrap();
+        assert_eq!(serde_json::from_value::<GradePreset>(json.clone()).unwrap(), grade);
+
+        json["schema_version"] = (GradePreset::SCHEMA_VERSION + 1).into();
+        let e = serde_json::from_value::<GradePreset>(json).unwrap_err();
+        assert!(e.to_string().contains("newer than the supported version"), "{e}");
+    }
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use crate::{ GradePreset, ImageProcessingSettings, HdrProcessingSettings };
+
+/// How the values change from a keyframe to the next one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    /// Ease in and out (smoothstep)
+    Smooth,
+}
+
+/// Grade at a single frame of a [`GradeTimeline`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe {
+    pub frame: usize,
+    pub grade: GradePreset,
+    /// Interpolation towards the next keyframe
+    pub interpolation: Interpolation,
+}
+
+/// Keyframed grade for per-frame processing, e.g. exposure ramps or day-for-night shots.
+///
+/// Between two keyframes the exposure compensation, exposure adjust, kelvin, tint, saturation, CDL slope/offset/power
+/// and HDRx bias are interpolated, all other settings (including the enums) are taken from the previous keyframe
+/// and switch at the next one. Frames before the first or after the last keyframe use that keyframe as it is.
+///
+/// Pass it to [`DecodeRequest::grade_timeline`](crate::DecodeRequest::grade_timeline), [`ParallelDecode::grade_timeline`](crate::ParallelDecode::grade_timeline)
+/// or [`R3dDecodeJob::set_grade_timeline`](crate::R3dDecodeJob::set_grade_timeline) in place of static settings.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vec<Keyframe>", into = "Vec<Keyframe>"))]
+pub struct GradeTimeline {
+    /// Sorted by frame, one keyframe per frame
+    keyframes: Vec<Keyframe>,
+}
+
+impl GradeTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a keyframe, replacing the one at the same frame
+    pub fn insert(&mut self, frame: usize, grade: GradePreset, interpolation: Interpolation) {
+        let keyframe = Keyframe { frame, grade, interpolation };
+        match self.keyframes.binary_search_by_key(&frame, |k| k.frame) {
+            Ok(i) => self.keyframes[i] = keyframe,
+            Err(i) => self.keyframes.insert(i, keyframe),
+        }
+    }
+
+    /// Remove the keyframe at `frame`
+    pub fn remove(&mut self, frame: usize) -> Option<Keyframe> {
+        let i = self.keyframes.binary_search_by_key(&frame, |k| k.frame).ok()?;
+        Some(self.keyframes.remove(i))
+    }
+
+    /// All keyframes, sorted by frame
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    /// Grade at `frame`, `None` if the timeline has no keyframes
+    pub fn grade_at(&self, frame: usize) -> Option<GradePreset> {
+        let next = self.keyframes.partition_point(|k| k.frame <= frame);
+        if next == 0 {
+            return self.keyframes.first().map(|k| k.grade.clone());
+        }
+        let a = &self.keyframes[next - 1];
+        let Some(b) = self.keyframes.get(next) else {
+            return Some(a.grade.clone());
+        };
+        let mut t = (frame - a.frame) as f32 / (b.frame - a.frame) as f32;
+        if a.interpolation == Interpolation::Smooth {
+            t = t * t * (3.0 - 2.0 * t);
+        }
+        Some(interpolate(&a.grade, &b.grade, t))
+    }
+
+    /// Image processing settings at `frame`, `None` if the timeline has no keyframes.
+    /// The 3D LUT is not loaded, see [`GradePreset::to_settings`].
+    pub fn settings_at(&self, frame: usize) -> Option<ImageProcessingSettings> {
+        self.grade_at(frame).map(|x| x.to_settings())
+    }
+
+    /// HDRx blending settings at `frame`, `None` if the timeline has no keyframes or the keyframe has no HDRx settings
+    pub fn hdr_settings_at(&self, frame: usize) -> Option<HdrProcessingSettings> {
+        self.grade_at(frame).and_then(|x| x.to_hdr_settings())
+    }
+}
+
+fn interpolate(a: &GradePreset, b: &GradePreset, t: f32) -> GradePreset {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    let mut out = a.clone();
+    out.exposure_compensation = lerp(a.exposure_compensation, b.exposure_compensation);
+    out.exposure_adjust       = lerp(a.exposure_adjust,       b.exposure_adjust);
+    out.kelvin                = lerp(a.kelvin,                b.kelvin);
+    out.tint                  = lerp(a.tint,                  b.tint);
+    out.saturation            = lerp(a.saturation,            b.saturation);
+    for (out, (a, b)) in [&mut out.cdl_red, &mut out.cdl_green, &mut out.cdl_blue].into_iter().zip([(a.cdl_red, b.cdl_red), (a.cdl_green, b.cdl_green), (a.cdl_blue, b.cdl_blue)]) {
+        out.slope  = lerp(a.slope,  b.slope);
+        out.offset = lerp(a.offset, b.offset);
+        out.power  = lerp(a.power,  b.power);
+    }
+    if let (Some(out), Some(b)) = (out.hdr.as_mut(), b.hdr) {
+        out.bias = lerp(out.bias, b.bias);
+    }
+    out
+}
+
+impl From<Vec<Keyframe>> for GradeTimeline {
+    /// Keyframes can be in any order, of keyframes at the same frame the last one is kept
+    fn from(keyframes: Vec<Keyframe>) -> Self {
+        let mut timeline = Self::new();
+        for k in keyframes {
+            timeline.insert(k.frame, k.grade, k.interpolation);
+        }
+        timeline
+    }
+}
+impl From<GradeTimeline> for Vec<Keyframe> {
+    fn from(timeline: GradeTimeline) -> Self { timeline.keyframes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HdrPreset;
+    use crate::enums::HdrBlendAlgorithm;
+
+    fn preset(kelvin: f32, hdr_bias: Option<f32>) -> GradePreset {
+        let mut grade = GradePreset::from_settings(&ImageProcessingSettings::default()).unwrap();
+        grade.kelvin = kelvin;
+        grade.hdr = hdr_bias.map(|bias| HdrPreset { blend_algorithm: HdrBlendAlgorithm::HDRxSimpleBlend, bias });
+        grade
+    }
+
+    #[test]
+    fn grade_at_ends() {
+        let mut timeline = GradeTimeline::new();
+        assert!(timeline.grade_at(0).is_none());
+        timeline.insert(10, preset(3000.0, None), Interpolation::Linear);
+        timeline.insert(20, preset(5000.0, None), Interpolation::Linear);
+        assert_eq!(timeline.grade_at(0).unwrap().kelvin, 3000.0);
+        assert_eq!(timeline.grade_at(10).unwrap().kelvin, 3000.0);
+        assert_eq!(timeline.grade_at(20).unwrap().kelvin, 5000.0);
+        assert_eq!(timeline.grade_at(100).unwrap().kelvin, 5000.0);
+    }
+
+    #[test]
+    fn grade_at_linear_and_smooth() {
+        let mut timeline = GradeTimeline::new();
+        timeline.insert(0, preset(3000.0, Some(-1.0)), Interpolation::Linear);
+        timeline.insert(4, preset(5000.0, Some(1.0)), Interpolation::Smooth);
+        timeline.insert(8, preset(3000.0, None), Interpolation::Linear);
+
+        let grade = timeline.grade_at(1).unwrap();
+        assert_eq!(grade.kelvin, 3500.0);
+        assert_eq!(grade.hdr.unwrap().bias, -0.5);
+        assert_eq!(timeline.grade_at(2).unwrap().kelvin, 4000.0);
+
+        // Smoothstep: slower than linear at the start, same value in the middle
+        assert!(timeline.grade_at(5).unwrap().kelvin > 4500.0);
+        assert_eq!(timeline.grade_at(6).unwrap().kelvin, 4000.0);
+        assert!(timeline.grade_at(7).unwrap().kelvin < 3500.0);
+        // The HDRx settings of the previous keyframe are kept until the next one
+        assert_eq!(timeline.grade_at(7).unwrap().hdr.unwrap().bias, 1.0);
+        assert!(timeline.grade_at(8).unwrap().hdr.is_none());
+    }
+
+    #[test]
+    fn insert_replaces_keyframe() {
+        let mut timeline = GradeTimeline::new();
+        timeline.insert(5, preset(3000.0, None), Interpolation::Linear);
+        timeline.insert(0, preset(4000.0, None), Interpolation::Linear);
+        timeline.insert(5, preset(6000.0, None), Interpolation::Smooth);
+        assert_eq!(timeline.keyframes().iter().map(|k| k.frame).collect::<Vec<_>>(), [0, 5]);
+        assert_eq!(timeline.keyframes()[1].interpolation, Interpolation::Smooth);
+        assert_eq!(timeline.grade_at(5).unwrap().kelvin, 6000.0);
+        assert_eq!(timeline.remove(5).unwrap().grade.kelvin, 6000.0);
+        assert!(timeline.remove(5).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_sorts_keyframes() {
+        let keyframes = vec![
+            Keyframe { frame: 20, grade: preset(5000.0, None), interpolation: Interpolation::Linear },
+            Keyframe { frame: 10, grade: preset(3000.0, None), interpolation: Interpolation::Linear },
+            Keyframe { frame: 20, grade: preset(6000.0, None), interpolation: Interpolation::Smooth },
+        ];
+        let timeline: GradeTimeline = serde_json::from_str(&serde_json::to_string(&keyframes).unwrap()).unwrap();
+        assert_eq!(timeline.keyframes().iter().map(|k| (k.frame, k.grade.kelvin)).collect::<Vec<_>>(), [(10, 3000.0), (20, 6000.0)]);
+        assert_eq!(serde_json::to_value(&timeline).unwrap(), serde_json::to_value(timeline.keyframes()).unwrap());
+    }
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
-use crate::enums::*;
+use crate::{ enums::*, RangeError, AllowedValues, RedError, RedResult };
 use cpp::*;
 
 /// This structure gives access to the default, minimum and maximum setting
@@ -64,8 +64,8 @@ impl ImageProcessingLimits {
 
 	#[inline] pub fn iso_default() -> usize { cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::ISODefault; }) }
 
-	#[inline] pub fn gamma_curve_default() -> ImageGammaCurve { unsafe { std::mem::transmute(cpp!([] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::GammaCurveDefault; })) } }
-	#[inline] pub fn color_space_default() -> ImageColorSpace { unsafe { std::mem::transmute(cpp!([] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::ColorSpaceDefault; })) } }
+	#[inline] pub fn gamma_curve_default() -> RedResult<ImageGammaCurve> { ImageGammaCurve::try_from(cpp!(unsafe [] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::GammaCurveDefault; })) }
+	#[inline] pub fn color_space_default() -> RedResult<ImageColorSpace> { ImageColorSpace::try_from(cpp!(unsafe [] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::ColorSpaceDefault; })) }
 
 	#[inline] pub fn custom_pdlog_black_point_min() -> usize { cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::CustomPDLogBlackPointMin; }) }
 	#[inline] pub fn custom_pdlog_black_point_max() -> usize { cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::CustomPDLogBlackPointMax; }) }
@@ -79,9 +79,9 @@ impl ImageProcessingLimits {
 	#[inline] pub fn custom_pdlog_gamma_max() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::CustomPDLogGammaMax; }) }
 	#[inline] pub fn custom_pdlog_gamma_default() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::CustomPDLogGammaDefault; }) }
 
-	#[inline] pub fn image_pipeline_mode_default() -> ImagePipeline { unsafe { std::mem::transmute(cpp!([] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::ImagePipelineModeDefault; })) } }
-	#[inline] pub fn highlight_roll_off_default() -> RollOff { unsafe { std::mem::transmute(cpp!([] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::HighlightRollOffDefault; })) } }
-	#[inline] pub fn output_tone_map_default() -> ToneMap { unsafe { std::mem::transmute(cpp!([] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::OutputToneMapDefault; })) } }
+	#[inline] pub fn image_pipeline_mode_default() -> RedResult<ImagePipeline> { ImagePipeline::try_from(cpp!(unsafe [] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::ImagePipelineModeDefault; })) }
+	#[inline] pub fn highlight_roll_off_default() -> RedResult<RollOff> { RollOff::try_from(cpp!(unsafe [] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::HighlightRollOffDefault; })) }
+	#[inline] pub fn output_tone_map_default() -> RedResult<ToneMap> { ToneMap::try_from(cpp!(unsafe [] -> i32 as "int" { return (int)R3DSDK::ImageProcessingLimits::OutputToneMapDefault; })) }
 
 	#[inline] pub fn exposure_adjust_min() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::ExposureAdjustMin; }) }
 	#[inline] pub fn exposure_adjust_max() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::ExposureAdjustMax; }) }
@@ -103,6 +103,10 @@ impl ImageProcessingLimits {
 	#[inline] pub fn cdl_power_max() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::CdlPowerMax; }) }
 	#[inline] pub fn cdl_power_default() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::CdlPowerDefault; }) }
 
+	#[inline] pub fn cdl_saturation_min() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::CdlSaturationMin; }) }
+	#[inline] pub fn cdl_saturation_max() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::CdlSaturationMax; }) }
+	#[inline] pub fn cdl_saturation_default() -> f32 { cpp!(unsafe [] -> f32 as "float" { return R3DSDK::ImageProcessingLimits::CdlSaturationDefault; }) }
+
 	/// Get the list of valid ISO values
 	pub fn iso_list() -> Vec<usize> {
 		let count = cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::ISOCount; });
@@ -116,15 +120,25 @@ impl ImageProcessingLimits {
 		result
 	}
 
-	/// Get the list of gamma curve options
+	/// Get the list of gamma curve options.
+	/// Values not known to this version of the crate are skipped.
 	pub fn gamma_curve_map() -> Vec<ImageGammaCurve> {
+		Self::gamma_curve_options().into_iter().map(|(v, _)| v).collect()
+	}
+
+	/// Get the gamma curve options together with their labels.
+	/// Unlike zipping `gamma_curve_map()` with `gamma_curve_labels()`, the pairs stay aligned when values unknown to this crate are skipped.
+	pub fn gamma_curve_options() -> Vec<(ImageGammaCurve, String)> {
 		let count = cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::GammaCurveCount; });
+		let labels = Self::gamma_curve_labels();
 		let mut result = Vec::with_capacity(count);
-		for i in 0..count {
+		for (i, label) in labels.into_iter().enumerate() {
 			let val = cpp!(unsafe [i as "size_t"] -> i32 as "int" {
 				return (int)R3DSDK::ImageProcessingLimits::GammaCurveMap[i];
 			});
-			result.push(unsafe { std::mem::transmute(val) });
+			if let Ok(v) = ImageGammaCurve::try_from(val) {
+				result.push((v, label));
+			}
 		}
 		result
 	}
@@ -143,15 +157,25 @@ impl ImageProcessingLimits {
 		result
 	}
 
-	/// Get the list of color space options
+	/// Get the list of color space options.
+	/// Values not known to this version of the crate are skipped.
 	pub fn color_space_map() -> Vec<ImageColorSpace> {
+		Self::color_space_options().into_iter().map(|(v, _)| v).collect()
+	}
+
+	/// Get the color space options together with their labels.
+	/// Unlike zipping `color_space_map()` with `color_space_labels()`, the pairs stay aligned when values unknown to this crate are skipped.
+	pub fn color_space_options() -> Vec<(ImageColorSpace, String)> {
 		let count = cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::ColorSpaceCount; });
+		let labels = Self::color_space_labels();
 		let mut result = Vec::with_capacity(count);
-		for i in 0..count {
+		for (i, label) in labels.into_iter().enumerate() {
 			let val = cpp!(unsafe [i as "size_t"] -> i32 as "int" {
 				return (int)R3DSDK::ImageProcessingLimits::ColorSpaceMap[i];
 			});
-			result.push(unsafe { std::mem::transmute(val) });
+			if let Ok(v) = ImageColorSpace::try_from(val) {
+				result.push((v, label));
+			}
 		}
 		result
 	}
@@ -170,15 +194,25 @@ impl ImageProcessingLimits {
 		result
 	}
 
-	/// Get the list of image pipeline mode options
+	/// Get the list of image pipeline mode options.
+	/// Values not known to this version of the crate are skipped.
 	pub fn image_pipeline_mode_map() -> Vec<ImagePipeline> {
+		Self::image_pipeline_mode_options().into_iter().map(|(v, _)| v).collect()
+	}
+
+	/// Get the image pipeline mode options together with their labels.
+	/// Unlike zipping `image_pipeline_mode_map()` with `image_pipeline_mode_labels()`, the pairs stay aligned when values unknown to this crate are skipped.
+	pub fn image_pipeline_mode_options() -> Vec<(ImagePipeline, String)> {
 		let count = cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::ImagePipelineModeCount; });
+		let labels = Self::image_pipeline_mode_labels();
 		let mut result = Vec::with_capacity(count);
-		for i in 0..count {
+		for (i, label) in labels.into_iter().enumerate() {
 			let val = cpp!(unsafe [i as "size_t"] -> i32 as "int" {
 				return (int)R3DSDK::ImageProcessingLimits::ImagePipelineModeMap[i];
 			});
-			result.push(unsafe { std::mem::transmute(val) });
+			if let Ok(v) = ImagePipeline::try_from(val) {
+				result.push((v, label));
+			}
 		}
 		result
 	}
@@ -197,15 +231,25 @@ impl ImageProcessingLimits {
 		result
 	}
 
-	/// Get the list of highlight roll-off options
+	/// Get the list of highlight roll-off options.
+	/// Values not known to this version of the crate are skipped.
 	pub fn highlight_roll_off_map() -> Vec<RollOff> {
+		Self::highlight_roll_off_options().into_iter().map(|(v, _)| v).collect()
+	}
+
+	/// Get the highlight roll-off options together with their labels.
+	/// Unlike zipping `highlight_roll_off_map()` with `highlight_roll_off_labels()`, the pairs stay aligned when values unknown to this crate are skipped.
+	pub fn highlight_roll_off_options() -> Vec<(RollOff, String)> {
 		let count = cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::HighlightRollOffCount; });
+		let labels = Self::highlight_roll_off_labels();
 		let mut result = Vec::with_capacity(count);
-		for i in 0..count {
+		for (i, label) in labels.into_iter().enumerate() {
 			let val = cpp!(unsafe [i as "size_t"] -> i32 as "int" {
 				return (int)R3DSDK::ImageProcessingLimits::HighlightRollOffMap[i];
 			});
-			result.push(unsafe { std::mem::transmute(val) });
+			if let Ok(v) = RollOff::try_from(val) {
+				result.push((v, label));
+			}
 		}
 		result
 	}
@@ -224,15 +268,25 @@ impl ImageProcessingLimits {
 		result
 	}
 
-	/// Get the list of output tone map options
+	/// Get the list of output tone map options.
+	/// Values not known to this version of the crate are skipped.
 	pub fn output_tone_map_map() -> Vec<ToneMap> {
+		Self::output_tone_map_options().into_iter().map(|(v, _)| v).collect()
+	}
+
+	/// Get the output tone map options together with their labels.
+	/// Unlike zipping `output_tone_map_map()` with `output_tone_map_labels()`, the pairs stay aligned when values unknown to this crate are skipped.
+	pub fn output_tone_map_options() -> Vec<(ToneMap, String)> {
 		let count = cpp!(unsafe [] -> usize as "size_t" { return R3DSDK::ImageProcessingLimits::OutputToneMapCount; });
+		let labels = Self::output_tone_map_labels();
 		let mut result = Vec::with_capacity(count);
-		for i in 0..count {
+		for (i, label) in labels.into_iter().enumerate() {
 			let val = cpp!(unsafe [i as "size_t"] -> i32 as "int" {
 				return (int)R3DSDK::ImageProcessingLimits::OutputToneMapMap[i];
 			});
-			result.push(unsafe { std::mem::transmute(val) });
+			if let Ok(v) = ToneMap::try_from(val) {
+				result.push((v, label));
+			}
 		}
 		result
 	}
@@ -250,4 +304,64 @@ impl ImageProcessingLimits {
 		}
 		result
 	}
+
+	/// Nearest valid ISO from [`iso_list`](Self::iso_list). Ties go to the lower ISO.
+	pub fn nearest_iso(iso: usize) -> usize {
+		Self::iso_list().into_iter().min_by_key(|x| (x.abs_diff(iso), *x)).unwrap_or(iso)
+	}
+
+	/// `Ok` if `iso` is one of [`iso_list`](Self::iso_list)
+	pub fn check_iso(iso: usize) -> Result<(), RangeError> {
+		let list = Self::iso_list();
+		if list.contains(&iso) { return Ok(()); }
+		Err(RangeError { field: "iso", value: iso as f32, allowed: AllowedValues::OneOf(list.into_iter().map(|x| x as f32).collect()) })
+	}
+
+	/// `Ok` if the gamma curve is available with this color version and image pipeline, see [`ImageGammaCurve::is_supported_by`].
+	/// With ColorVersion3 and `ImagePipeline::PrimaryDevelopmentOnly` the output is always Log3G10.
+	pub fn check_gamma_curve(gamma_curve: ImageGammaCurve, version: ColorVersion, pipeline: ImagePipeline) -> RedResult<()> {
+		let primary_only = version == ColorVersion::Version3 && pipeline == ImagePipeline::PrimaryDevelopmentOnly;
+		if !gamma_curve.is_supported_by(version) || (primary_only && gamma_curve != ImageGammaCurve::Log3G10) {
+			return Err(RedError::UnsupportedGammaCurve { gamma_curve, version, pipeline });
+		}
+		Ok(())
+	}
+
+	/// `Ok` if the color space is available with this color version and image pipeline, see [`ImageColorSpace::is_supported_by`].
+	/// With ColorVersion3 and `ImagePipeline::PrimaryDevelopmentOnly` the output is always REDWideGamutRGB.
+	pub fn check_color_space(color_space: ImageColorSpace, version: ColorVersion, pipeline: ImagePipeline) -> RedResult<()> {
+		let primary_only = version == ColorVersion::Version3 && pipeline == ImagePipeline::PrimaryDevelopmentOnly;
+		if !color_space.is_supported_by(version) || (primary_only && color_space != ImageColorSpace::REDWideGamutRGB) {
+			return Err(RedError::UnsupportedColorSpace { color_space, version, pipeline });
+		}
+		Ok(())
+	}
+
+	/// `Ok` if `min <= value <= max`. NaN is always out of range.
+	pub fn check_range(field: &'static str, value: f32, min: f32, max: f32) -> Result<(), RangeError> {
+		if (min..=max).contains(&value) { return Ok(()); }
+		Err(RangeError { field, value, allowed: AllowedValues::Range { min, max } })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{ ColorVersion::*, ImagePipeline::* };
+
+    #[test]
+    fn gamma_curve_and_color_space() {
+        assert!(ImageProcessingLimits::check_gamma_curve(ImageGammaCurve::REDgamma4, Version2, FullGraded).is_ok());
+        assert!(matches!(ImageProcessingLimits::check_gamma_curve(ImageGammaCurve::REDgamma4, Version3, FullGraded),
+            Err(RedError::UnsupportedGammaCurve { gamma_curve: ImageGammaCurve::REDgamma4, version: Version3, pipeline: FullGraded })));
+        assert!(ImageProcessingLimits::check_gamma_curve(ImageGammaCurve::HybridLogGamma, VersionBC, FullGraded).is_ok());
+        assert!(ImageProcessingLimits::check_color_space(ImageColorSpace::DCIP3D65, Version2, FullGraded).is_err());
+
+        // Primary development only outputs Log3G10 / REDWideGamutRGB
+        assert!(ImageProcessingLimits::check_gamma_curve(ImageGammaCurve::Log3G10, Version3, PrimaryDevelopmentOnly).is_ok());
+        assert!(ImageProcessingLimits::check_gamma_curve(ImageGammaCurve::BT1886, Version3, PrimaryDevelopmentOnly).is_err());
+        assert!(ImageProcessingLimits::check_color_space(ImageColorSpace::REDWideGamutRGB, Version3, PrimaryDevelopmentOnly).is_ok());
+        assert!(matches!(ImageProcessingLimits::check_color_space(ImageColorSpace::Rec709, Version3, PrimaryDevelopmentOnly),
+            Err(RedError::UnsupportedColorSpace { color_space: ImageColorSpace::Rec709, .. })));
+    }
 }
@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
-use crate::enums::*;
+use crate::{ enums::*, ImageProcessingLimits as L, RangeError, RedResult, UserCurve };
+use crate::user_curve::{ check_user_curve, curve_fields };
 use cpp::*;
 
 cpp_class! {
@@ -26,7 +27,7 @@ impl ImageProcessingSettings {
 
 	/// Color version — defaults to latest (V3). Settings out of bounds will be clipped.
 	/// If a lower version is set for a newer clip it will be forced up.
-	pub fn     version(&self)     -> ColorVersion { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).Version; })) } }
+	pub fn     version(&self)     -> RedResult<ColorVersion> { ColorVersion::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).Version; })) }
 	pub fn set_version(&mut self, v: ColorVersion) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).Version = (R3DSDK::ColorVersion)v; }) }
 
 	/// Kelvin color temperature for white balance.
@@ -65,13 +66,13 @@ impl ImageProcessingSettings {
 	pub fn     drx(&self)     -> f32 { cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> f32 as "float" { return (*self).DRX; }) }
 	pub fn set_drx(&mut self, v: f32) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "float"] { (*self).DRX = v; }) }
 
-	/// Image Gamma Curve (limited in Broadcast Color).
-	pub fn     gamma_curve(&self)     -> i32 { cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).GammaCurve; }) }
-	pub fn set_gamma_curve(&mut self, v: i32) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).GammaCurve = (R3DSDK::ImageGammaCurve)v; }) }
+	/// Image Gamma Curve (limited in Broadcast Color). `RedError::InvalidEnumValue` if the SDK holds a value unknown to this crate.
+	pub fn     gamma_curve(&self)     -> RedResult<ImageGammaCurve> { ImageGammaCurve::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).GammaCurve; })) }
+	pub fn set_gamma_curve(&mut self, v: ImageGammaCurve) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).GammaCurve = (R3DSDK::ImageGammaCurve)v; }) }
 
-	/// Image Color Space (limited in Broadcast Color).
-	pub fn     color_space(&self)     -> i32 { cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).ColorSpace; }) }
-	pub fn set_color_space(&mut self, v: i32) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).ColorSpace = (R3DSDK::ImageColorSpace)v; }) }
+	/// Image Color Space (limited in Broadcast Color). `RedError::InvalidEnumValue` if the SDK holds a value unknown to this crate.
+	pub fn     color_space(&self)     -> RedResult<ImageColorSpace> { ImageColorSpace::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).ColorSpace; })) }
+	pub fn set_color_space(&mut self, v: ImageColorSpace) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).ColorSpace = (R3DSDK::ImageColorSpace)v; }) }
 
 	// --------------------------------------------------------------------------------
 	// Custom PDLog (ignored in IPP2/BC)
@@ -138,20 +139,26 @@ impl ImageProcessingSettings {
 		unsafe { std::slice::from_raw_parts_mut(ptr, 10) }
 	}
 
+	/// Luma user curve as control points, see [`UserCurve`]
+	pub fn user_curve_points(&self)       -> UserCurve { UserCurve::from(<[f32; 10]>::try_from(self.user_curve()).unwrap()) }
+	pub fn user_curve_red_points(&self)   -> UserCurve { UserCurve::from(<[f32; 10]>::try_from(self.user_curve_red()).unwrap()) }
+	pub fn user_curve_green_points(&self) -> UserCurve { UserCurve::from(<[f32; 10]>::try_from(self.user_curve_green()).unwrap()) }
+	pub fn user_curve_blue_points(&self)  -> UserCurve { UserCurve::from(<[f32; 10]>::try_from(self.user_curve_blue()).unwrap()) }
+
 	// --------------------------------------------------------------------------------
 	// Premium decode-only (ignored in IPP2/BC where noted)
 	// --------------------------------------------------------------------------------
 
 	/// Detail — only used for full res premium decode; ignored in IPP2/BC.
-	pub fn     detail(&self)     -> ImageDetail { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).Detail; })) } }
+	pub fn     detail(&self)     -> RedResult<ImageDetail> { ImageDetail::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).Detail; })) }
 	pub fn set_detail(&mut self, v: ImageDetail) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).Detail = (R3DSDK::ImageDetail)v; }) }
 
 	/// OLPF Compensation — premium full/half res; ignored in IPP2/BC.
-	pub fn     olpf_compensation(&self)     -> ImageOLPFCompensation { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).OLPFCompensation; })) } }
+	pub fn     olpf_compensation(&self)     -> RedResult<ImageOLPFCompensation> { ImageOLPFCompensation::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).OLPFCompensation; })) }
 	pub fn set_olpf_compensation(&mut self, v: ImageOLPFCompensation) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).OLPFCompensation = (R3DSDK::ImageOLPFCompensation)v; }) }
 
 	/// Denoise — only used for full res premium decode; ignored in IPP2/BC.
-	pub fn     denoise(&self)     -> ImageDenoise { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).Denoise; })) } }
+	pub fn     denoise(&self)     -> RedResult<ImageDenoise> { ImageDenoise::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).Denoise; })) }
 	pub fn set_denoise(&mut self, v: ImageDenoise) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).Denoise = (R3DSDK::ImageDenoise)v; }) }
 
 	/// Dragon Enhanced Blacks (legacy pipe, Dragon+ sensors) — union with ChromaNoiseReduction.
@@ -163,7 +170,7 @@ impl ImageProcessingSettings {
 	pub fn set_chroma_noise_reduction(&mut self, v: bool) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "bool"] { (*self).ChromaNoiseReduction = v; }) }
 
 	/// Flashing Pixel Adjustment — premium full/half res; ignored for N-RAW.
-	pub fn     flashing_pixel_adjustment(&self)     -> FlashingPixelAdjust { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).FlashingPixelAdjustment; })) } }
+	pub fn     flashing_pixel_adjustment(&self)     -> RedResult<FlashingPixelAdjust> { FlashingPixelAdjust::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).FlashingPixelAdjustment; })) }
 	pub fn set_flashing_pixel_adjustment(&mut self, v: FlashingPixelAdjust) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).FlashingPixelAdjustment = (R3DSDK::FlashingPixelAdjust)v; }) }
 
 	// --------------------------------------------------------------------------------
@@ -199,7 +206,7 @@ impl ImageProcessingSettings {
 	// --------------------------------------------------------------------------------
 
 	/// Image Pipeline Mode (IPP2).
-	pub fn     image_pipeline_mode(&self)     -> ImagePipeline { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).ImagePipelineMode; })) } }
+	pub fn     image_pipeline_mode(&self)     -> RedResult<ImagePipeline> { ImagePipeline::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).ImagePipelineMode; })) }
 	pub fn set_image_pipeline_mode(&mut self, v: ImagePipeline) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).ImagePipelineMode = (R3DSDK::ImagePipeline)v; }) }
 
 	/// Exposure Adjust — also available with Broadcast Color.
@@ -229,16 +236,24 @@ impl ImageProcessingSettings {
 	/// 3D LUT handle — NULL if disabled/not set. Value unique per process.
 	pub fn     lut3d(&self)     -> *mut core::ffi::c_void { cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> *mut core::ffi::c_void as "void *" { return (*self).Lut3D; }) }
 
+	/// Path of the 3D LUT — None if no LUT is set. Only the filename for sidecar LUTs.
+	pub fn     lut3d_path(&self) -> Option<String> {
+		let ptr = cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> *const core::ffi::c_char as "const char *" {
+			return (*self).Lut3D ? R3DSDK::Get3DLutPath((*self).Lut3D) : nullptr;
+		});
+		if ptr.is_null() { None } else { Some(unsafe { std::ffi::CStr::from_ptr(ptr) }.to_string_lossy().into_owned()) }
+	}
+
 	/// 3D LUT enabled — defaults to false; only used if a LUT is set.
 	pub fn     lut3d_enabled(&self)     -> bool { cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> bool as "bool" { return (*self).Lut3DEnabled; }) }
 	pub fn set_lut3d_enabled(&mut self, v: bool) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "bool"] { (*self).Lut3DEnabled = v; }) }
 
 	/// Output Tone Map (IPP2).
-	pub fn     output_tone_map(&self)     -> ToneMap { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).OutputToneMap; })) } }
+	pub fn     output_tone_map(&self)     -> RedResult<ToneMap> { ToneMap::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).OutputToneMap; })) }
 	pub fn set_output_tone_map(&mut self, v: ToneMap) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).OutputToneMap = (R3DSDK::ToneMap)v; }) }
 
 	/// Highlight Roll-off — no effect with LOG curve applied.
-	pub fn     highlight_roll_off(&self)     -> RollOff { unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).HighlightRollOff; })) } }
+	pub fn     highlight_roll_off(&self)     -> RedResult<RollOff> { RollOff::try_from(cpp!(unsafe [self as "const R3DSDK::ImageProcessingSettings *"] -> i32 as "int" { return (int)(*self).HighlightRollOff; })) }
 	pub fn set_highlight_roll_off(&mut self, v: RollOff) { cpp!(unsafe [self as "R3DSDK::ImageProcessingSettings *", v as "int"] { (*self).HighlightRollOff = (R3DSDK::RollOff)v; }) }
 
 	/// HDR Peak Nits — HDR2084 only and HighlightRollOff != None.
@@ -255,6 +270,95 @@ impl ImageProcessingSettings {
 			self->CheckBounds();
 		})
 	}
+
+	// --------------------------------------------------------------------------------
+	// Validated setters
+	// --------------------------------------------------------------------------------
+	// Unlike the plain setters and check_bounds, these don't change an out of range value
+	// but leave the setting untouched and return which field and range were violated.
+
+	/// Set the ISO if it's one of `ImageProcessingLimits::iso_list`
+	pub fn try_set_iso(&mut self, v: usize) -> Result<(), RangeError> { L::check_iso(v)?; self.set_iso(v); Ok(()) }
+	/// Set the nearest valid ISO from `ImageProcessingLimits::iso_list` and return it
+	pub fn set_iso_snapped(&mut self, v: usize) -> usize { let v = L::nearest_iso(v); self.set_iso(v); v }
+
+	pub fn try_set_kelvin(&mut self, v: f32)                -> Result<(), RangeError> { L::check_range("kelvin", v, L::kelvin_min(), L::kelvin_max())?; self.set_kelvin(v); Ok(()) }
+	pub fn try_set_tint(&mut self, v: f32)                  -> Result<(), RangeError> { L::check_range("tint", v, L::tint_min(), L::tint_max())?; self.set_tint(v); Ok(()) }
+	pub fn try_set_exposure_compensation(&mut self, v: f32) -> Result<(), RangeError> { L::check_range("exposure_compensation", v, L::exposure_min(), L::exposure_max())?; self.set_exposure_compensation(v); Ok(()) }
+	pub fn try_set_gain_red(&mut self, v: f32)              -> Result<(), RangeError> { L::check_range("gain_red", v, L::gains_min(), L::gains_max())?; self.set_gain_red(v); Ok(()) }
+	pub fn try_set_gain_green(&mut self, v: f32)            -> Result<(), RangeError> { L::check_range("gain_green", v, L::gains_min(), L::gains_max())?; self.set_gain_green(v); Ok(()) }
+	pub fn try_set_gain_blue(&mut self, v: f32)             -> Result<(), RangeError> { L::check_range("gain_blue", v, L::gains_min(), L::gains_max())?; self.set_gain_blue(v); Ok(()) }
+	pub fn try_set_saturation(&mut self, v: f32)            -> Result<(), RangeError> { L::check_range("saturation", v, L::saturation_min(), L::saturation_max())?; self.set_saturation(v); Ok(()) }
+	pub fn try_set_contrast(&mut self, v: f32)              -> Result<(), RangeError> { L::check_range("contrast", v, L::contrast_min(), L::contrast_max())?; self.set_contrast(v); Ok(()) }
+	pub fn try_set_brightness(&mut self, v: f32)            -> Result<(), RangeError> { L::check_range("brightness", v, L::brightness_min(), L::brightness_max())?; self.set_brightness(v); Ok(()) }
+	pub fn try_set_drx(&mut self, v: f32)                   -> Result<(), RangeError> { L::check_range("drx", v, L::drx_min(), L::drx_max())?; self.set_drx(v); Ok(()) }
+	pub fn try_set_shadow(&mut self, v: f32)                -> Result<(), RangeError> { L::check_range("shadow", v, L::shadow_min(), L::shadow_max())?; self.set_shadow(v); Ok(()) }
+	pub fn try_set_flut(&mut self, v: f32)                  -> Result<(), RangeError> { L::check_range("flut", v, L::flut_min(), L::flut_max())?; self.set_flut(v); Ok(()) }
+	pub fn try_set_exposure_adjust(&mut self, v: f32)       -> Result<(), RangeError> { L::check_range("exposure_adjust", v, L::exposure_adjust_min(), L::exposure_adjust_max())?; self.set_exposure_adjust(v); Ok(()) }
+	pub fn try_set_cdl_saturation(&mut self, v: f32)        -> Result<(), RangeError> { L::check_range("cdl_saturation", v, L::cdl_saturation_min(), L::cdl_saturation_max())?; self.set_cdl_saturation(v); Ok(()) }
+	pub fn try_set_custom_pdlog_gamma(&mut self, v: f32)    -> Result<(), RangeError> { L::check_range("custom_pdlog_gamma", v, L::custom_pdlog_gamma_min(), L::custom_pdlog_gamma_max())?; self.set_custom_pdlog_gamma(v); Ok(()) }
+	pub fn try_set_custom_pdlog_black_point(&mut self, v: usize) -> Result<(), RangeError> {
+		L::check_range("custom_pdlog_black_point", v as f32, L::custom_pdlog_black_point_min() as f32, L::custom_pdlog_black_point_max() as f32)?;
+		self.set_custom_pdlog_black_point(v);
+		Ok(())
+	}
+	pub fn try_set_custom_pdlog_white_point(&mut self, v: usize) -> Result<(), RangeError> {
+		L::check_range("custom_pdlog_white_point", v as f32, L::custom_pdlog_white_point_min() as f32, L::custom_pdlog_white_point_max() as f32)?;
+		self.set_custom_pdlog_white_point(v);
+		Ok(())
+	}
+	pub fn try_set_hdr_peak_nits(&mut self, v: u32) -> Result<(), RangeError> {
+		L::check_range("hdr_peak_nits", v as f32, L::hdr_peak_nits_min() as f32, L::hdr_peak_nits_max() as f32)?;
+		self.set_hdr_peak_nits(v);
+		Ok(())
+	}
+
+	/// Set the gamma curve if it's available with the current color version and image pipeline, see `ImageProcessingLimits::check_gamma_curve`.
+	/// Set the version and pipeline first.
+	pub fn try_set_gamma_curve(&mut self, v: ImageGammaCurve) -> RedResult<()> {
+		L::check_gamma_curve(v, self.version()?, self.image_pipeline_mode()?)?;
+		self.set_gamma_curve(v);
+		Ok(())
+	}
+	/// Set the color space if it's available with the current color version and image pipeline, see `ImageProcessingLimits::check_color_space`.
+	/// Set the version and pipeline first.
+	pub fn try_set_color_space(&mut self, v: ImageColorSpace) -> RedResult<()> {
+		L::check_color_space(v, self.version()?, self.image_pipeline_mode()?)?;
+		self.set_color_space(v);
+		Ok(())
+	}
+	/// Check the gamma curve and color space against the color version and image pipeline,
+	/// e.g. after changing the version or pipeline.
+	pub fn check_color_pipeline(&self) -> RedResult<()> {
+		let (version, pipeline) = (self.version()?, self.image_pipeline_mode()?);
+		L::check_gamma_curve(self.gamma_curve()?, version, pipeline)?;
+		L::check_color_space(self.color_space()?, version, pipeline)
+	}
+
+	pub fn try_set_lgg_red(&mut self, v: LiftGammaGain)   -> Result<(), RangeError> { check_lgg(&v, ["lgg_red.lift", "lgg_red.gamma", "lgg_red.gain"])?; *self.lgg_red_mut() = v; Ok(()) }
+	pub fn try_set_lgg_green(&mut self, v: LiftGammaGain) -> Result<(), RangeError> { check_lgg(&v, ["lgg_green.lift", "lgg_green.gamma", "lgg_green.gain"])?; *self.lgg_green_mut() = v; Ok(()) }
+	pub fn try_set_lgg_blue(&mut self, v: LiftGammaGain)  -> Result<(), RangeError> { check_lgg(&v, ["lgg_blue.lift", "lgg_blue.gamma", "lgg_blue.gain"])?; *self.lgg_blue_mut() = v; Ok(()) }
+
+	pub fn try_set_cdl_red(&mut self, v: SlopeOffsetPower)   -> Result<(), RangeError> { check_cdl(&v, ["cdl_red.slope", "cdl_red.offset", "cdl_red.power"])?; *self.cdl_red_mut() = v; Ok(()) }
+	pub fn try_set_cdl_green(&mut self, v: SlopeOffsetPower) -> Result<(), RangeError> { check_cdl(&v, ["cdl_green.slope", "cdl_green.offset", "cdl_green.power"])?; *self.cdl_green_mut() = v; Ok(()) }
+	pub fn try_set_cdl_blue(&mut self, v: SlopeOffsetPower)  -> Result<(), RangeError> { check_cdl(&v, ["cdl_blue.slope", "cdl_blue.offset", "cdl_blue.power"])?; *self.cdl_blue_mut() = v; Ok(()) }
+
+	/// Set the user curve if all points are in 0 -- 1 and the X coordinates are strictly increasing, see [`UserCurve::validate`]
+	pub fn try_set_user_curve(&mut self, v: UserCurve)       -> Result<(), RangeError> { check_user_curve(&v, curve_fields!("user_curve"))?;       self.user_curve_mut().copy_from_slice(&<[f32; 10]>::from(v)); Ok(()) }
+	pub fn try_set_user_curve_red(&mut self, v: UserCurve)   -> Result<(), RangeError> { check_user_curve(&v, curve_fields!("user_curve_red"))?;   self.user_curve_red_mut().copy_from_slice(&<[f32; 10]>::from(v)); Ok(()) }
+	pub fn try_set_user_curve_green(&mut self, v: UserCurve) -> Result<(), RangeError> { check_user_curve(&v, curve_fields!("user_curve_green"))?; self.user_curve_green_mut().copy_from_slice(&<[f32; 10]>::from(v)); Ok(()) }
+	pub fn try_set_user_curve_blue(&mut self, v: UserCurve)  -> Result<(), RangeError> { check_user_curve(&v, curve_fields!("user_curve_blue"))?;  self.user_curve_blue_mut().copy_from_slice(&<[f32; 10]>::from(v)); Ok(()) }
+}
+
+pub(crate) fn check_lgg(v: &LiftGammaGain, fields: [&'static str; 3]) -> Result<(), RangeError> {
+	L::check_range(fields[0], v.lift,  L::lgg_lift_min(),  L::lgg_lift_max())?;
+	L::check_range(fields[1], v.gamma, L::lgg_gamma_min(), L::lgg_gamma_max())?;
+	L::check_range(fields[2], v.gain,  L::lgg_gain_min(),  L::lgg_gain_max())
+}
+pub(crate) fn check_cdl(v: &SlopeOffsetPower, fields: [&'static str; 3]) -> Result<(), RangeError> {
+	L::check_range(fields[0], v.slope,  L::cdl_slope_min(),  L::cdl_slope_max())?;
+	L::check_range(fields[1], v.offset, L::cdl_offset_min(), L::cdl_offset_max())?;
+	L::check_range(fields[2], v.power,  L::cdl_power_min(),  L::cdl_power_max())
 }
 
 cpp_class! {
@@ -267,10 +371,10 @@ cpp_class! {
 
 impl HdrProcessingSettings {
     /// HDRx blending algorithm to use
-    pub fn hdr_blend_algorithm(&self) -> HdrBlendAlgorithm {
-        unsafe { std::mem::transmute(cpp!([self as "const R3DSDK::HdrProcessingSettings *"] -> i32 as "int" {
+    pub fn hdr_blend_algorithm(&self) -> RedResult<HdrBlendAlgorithm> {
+        HdrBlendAlgorithm::try_from(cpp!(unsafe [self as "const R3DSDK::HdrProcessingSettings *"] -> i32 as "int" {
             return (int)(*self).BlendAlgorithm;
-        })) }
+        }))
     }
     /// Set HDRx blending algorithm to use
     pub fn set_hdr_blend_algorithm(&mut self, v: HdrBlendAlgorithm) {
@@ -299,6 +403,12 @@ impl HdrProcessingSettings {
             (*self).Bias = v;
         })
     }
+    /// Set amount of blending, returns an error instead of clipping if it's not in range of -1 -- +1
+    pub fn try_set_bias(&mut self, v: f32) -> Result<(), RangeError> {
+        L::check_range("bias", v, -1.0, 1.0)?;
+        self.set_bias(v);
+        Ok(())
+    }
 
 	/// Check to see if all parameters are within the proper range and clip any that aren't
     pub fn check_bounds(&mut self) {
@@ -307,3 +417,53 @@ impl HdrProcessingSettings {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ AllowedValues, RedError };
+
+    #[test]
+    fn try_set_out_of_range() {
+        let mut s = ImageProcessingSettings::default();
+        let kelvin = s.kelvin();
+        let e = s.try_set_kelvin(L::kelvin_max() + 1.0).unwrap_err();
+        assert_eq!((e.field, e.value), ("kelvin", L::kelvin_max() + 1.0));
+        assert_eq!(e.allowed, AllowedValues::Range { min: L::kelvin_min(), max: L::kelvin_max() });
+        assert!(s.try_set_kelvin(f32::NAN).is_err());
+        assert_eq!(s.kelvin(), kelvin);
+        assert!(s.try_set_kelvin(L::kelvin_min()).is_ok());
+        assert_eq!(s.kelvin(), L::kelvin_min());
+
+        // Only the field out of range is reported and none of them is written
+        let lgg = *s.lgg_red();
+        let e = s.try_set_lgg_red(LiftGammaGain { lift: 0.0, gamma: L::lgg_gamma_max() * 2.0, gain: 1.0 }).unwrap_err();
+        assert_eq!(e.field, "lgg_red.gamma");
+        assert_eq!(*s.lgg_red(), lgg);
+
+        let iso = s.iso();
+        let e = s.try_set_iso(iso + 1).unwrap_err();
+        assert_eq!(e.allowed, AllowedValues::OneOf(L::iso_list().into_iter().map(|x| x as f32).collect()));
+        assert_eq!(s.iso(), iso);
+    }
+
+    #[test]
+    fn try_set_gamma_curve() {
+        let mut s = ImageProcessingSettings::default();
+        s.set_version(ColorVersion::Version3);
+        s.set_image_pipeline_mode(ImagePipeline::FullGraded);
+        s.set_gamma_curve(ImageGammaCurve::BT1886);
+        let e = s.try_set_gamma_curve(ImageGammaCurve::REDgamma4).unwrap_err();
+        assert!(matches!(e, RedError::UnsupportedGammaCurve { gamma_curve: ImageGammaCurve::REDgamma4, version: ColorVersion::Version3, .. }));
+        assert_eq!(s.gamma_curve().unwrap(), ImageGammaCurve::BT1886);
+        assert!(s.try_set_gamma_curve(ImageGammaCurve::Log3G10).is_ok());
+
+        // Changing the pipeline afterwards is caught by check_color_pipeline
+        s.set_color_space(ImageColorSpace::Rec709);
+        assert!(s.check_color_pipeline().is_ok());
+        s.set_image_pipeline_mode(ImagePipeline::PrimaryDevelopmentOnly);
+        assert!(matches!(s.check_color_pipeline(), Err(RedError::UnsupportedColorSpace { .. })));
+        assert!(s.try_set_color_space(ImageColorSpace::REDWideGamutRGB).is_ok());
+        assert!(s.check_color_pipeline().is_ok());
+    }
+}
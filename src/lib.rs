@@ -5,13 +5,30 @@
 
 mod asyncdecoder; pub use asyncdecoder::*;
 mod clip;         pub use clip::*;
+mod cube;         pub use cube::*;
 mod custom_io;    pub use custom_io::*;
 mod debayer;      pub use debayer::*;
+mod decode_request; pub use decode_request::*;
 mod enums;        pub use enums::*;
 mod error;        pub use error::*;
 mod future;       pub use future::*;
+mod grade_preset; pub use grade_preset::*;
+mod grade_timeline; pub use grade_timeline::*;
 mod metadata;     pub use metadata::*;
+mod metrics;      pub use metrics::*;
+mod parallel;     pub use parallel::*;
 mod r3ddecoder;   pub use r3ddecoder::*;
 mod sdk;          pub use sdk::*;
+mod settings_diff; pub use settings_diff::*;
+mod settings_schema; pub use settings_schema::*;
+mod user_curve;   pub use user_curve::*;
+mod version;      pub use version::*;
 mod image_processing_settings; pub use image_processing_settings::*;
 mod image_processing_limits;   pub use image_processing_limits::*;
+
+#[cfg(feature = "cdl")]   mod cdl;
+#[cfg(feature = "cdl")]   pub use cdl::*;
+#[cfg(feature = "tokio")] mod tokio_rt;
+#[cfg(feature = "tokio")] pub use tokio_rt::*;
+#[cfg(feature = "mock")]  mod mock;
+#[cfg(feature = "mock")]  pub use mock::*;
@@ -46,9 +46,9 @@ impl Metadata {
         unsafe {
             let c_key = std::ffi::CString::new(key).unwrap();
             let c_key = c_key.as_ptr();
-            let meta_type: MetadataType = std::mem::transmute(cpp!([self as "const R3DSDK::Metadata*", c_key as "const char*"] -> i32 as "int" {
+            let meta_type = MetadataType::try_from(cpp!([self as "const R3DSDK::Metadata*", c_key as "const char*"] -> i32 as "int" {
                 return (int)self->MetadataItemType(c_key);
-            }));
+            })).unwrap_or(MetadataType::Invalid); // types unknown to this crate are skipped
             match meta_type {
                 MetadataType::Int => {
                     let value: u32 = cpp!([self as "const R3DSDK::Metadata*", c_key as "const char*"] -> u32 as "unsigned int" {
@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Decode instrumentation.
+//
+// With the `metrics` feature, every decode submitted to R3dDecoder, AsyncDecoder, GpuDecoder or
+// the synchronous Clip decode functions is counted in process-wide counters, see `DecodeMetrics`.
+// With the `tracing` feature, every decode job gets a `tracing` span which is entered while the job is submitted
+// and while its completion is processed, and closed when the job finishes.
+// Without these features the hooks compile to nothing.
+
+#[cfg(feature = "metrics")]
+use std::{ collections::BTreeMap, sync::Mutex, sync::atomic::{ AtomicU64, Ordering }, time::{ Duration, Instant } };
+
+/// Which decode path a job went through
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeSource {
+    /// `R3dDecoder::decode`
+    R3dDecoder,
+    /// `AsyncDecoder::decode_for_gpu_sdk`
+    AsyncDecoder,
+    /// `GpuDecoder::decode_for_gpu_sdk`
+    GpuDecoder,
+    /// `Clip::decode_video_frame` and `Clip::video_track_decode_frame`
+    Clip,
+}
+
+/// Tracks a single decode from submission to completion.
+pub(crate) struct JobMetrics {
+    #[cfg(feature = "metrics")]
+    source: DecodeSource,
+    #[cfg(feature = "metrics")]
+    start: Instant,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl JobMetrics {
+    #[allow(unused_variables)]
+    pub(crate) fn start(source: DecodeSource, video_frame_no: usize) -> Self {
+        #[cfg(feature = "metrics")]
+        {
+            let stats = source.stats();
+            stats.submitted.fetch_add(1, Ordering::Relaxed);
+            stats.in_flight.fetch_add(1, Ordering::Relaxed);
+        }
+        Self {
+            #[cfg(feature = "metrics")]
+            source,
+            #[cfg(feature = "metrics")]
+            start: Instant::now(),
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!("r3d_decode", source = ?source, frame = video_frame_no, status = tracing::field::Empty),
+        }
+    }
+
+    /// Enter the job's span on the current thread until the returned guard is dropped.
+    pub(crate) fn enter(&self) -> JobScope {
+        JobScope {
+            #[cfg(feature = "tracing")]
+            _span: self.span.clone().entered(),
+        }
+    }
+
+    /// `result` is the number of bytes decoded on success or the failed status.
+    #[allow(unused_variables)]
+    pub(crate) fn finish<S: std::fmt::Debug>(self, result: Result<usize, S>) {
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(_) => { self.span.record("status", "Ok"); }
+            Err(status) => { self.span.record("status", tracing::field::debug(status)); }
+        }
+        #[cfg(feature = "metrics")]
+        {
+            let stats = self.source.stats();
+            stats.in_flight.fetch_sub(1, Ordering::Relaxed);
+            match result {
+                Ok(bytes) => {
+                    stats.completed.fetch_add(1, Ordering::Relaxed);
+                    stats.bytes_decoded.fetch_add(bytes as u64, Ordering::Relaxed);
+                    stats.latency.record(self.start.elapsed());
+                }
+                Err(status) => {
+                    *stats.failed.lock().unwrap().entry(format!("{status:?}")).or_default() += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Guard returned by [`JobMetrics::enter`]
+pub(crate) struct JobScope {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+#[cfg(feature = "metrics")]
+const LATENCY_BUCKETS: usize = 20;
+
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct SourceStats {
+    submitted: AtomicU64,
+    in_flight: AtomicU64,
+    completed: AtomicU64,
+    failed: Mutex<BTreeMap<String, u64>>,
+    bytes_decoded: AtomicU64,
+    latency: AtomicHistogram,
+}
+
+/// Power of two buckets, from 1 ms up to ~4.4 minutes
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct AtomicHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS],
+    sum_us: AtomicU64,
+    max_us: AtomicU64,
+}
+#[cfg(feature = "metrics")]
+impl AtomicHistogram {
+    fn bucket_bound(i: usize) -> Duration {
+        Duration::from_millis(1 << i)
+    }
+    fn record(&self, latency: Duration) {
+        let us = latency.as_micros() as u64;
+        let i = (0..LATENCY_BUCKETS - 1).find(|&i| latency <= Self::bucket_bound(i)).unwrap_or(LATENCY_BUCKETS - 1);
+        self.buckets[i].fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add(us, Ordering::Relaxed);
+        self.max_us.fetch_max(us, Ordering::Relaxed);
+    }
+    fn snapshot(&self) -> LatencyHistogram {
+        let buckets: Vec<_> = self.buckets.iter().enumerate().map(|(i, x)| {
+            let bound = if i == LATENCY_BUCKETS - 1 { Duration::MAX } else { Self::bucket_bound(i) };
+            (bound, x.load(Ordering::Relaxed))
+        }).collect();
+        LatencyHistogram {
+            count: buckets.iter().map(|x| x.1).sum(),
+            buckets,
+            sum: Duration::from_micros(self.sum_us.load(Ordering::Relaxed)),
+            max: Duration::from_micros(self.max_us.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl DecodeSource {
+    pub const ALL: [DecodeSource; 4] = [DecodeSource::R3dDecoder, DecodeSource::AsyncDecoder, DecodeSource::GpuDecoder, DecodeSource::Clip];
+
+    fn stats(self) -> &'static SourceStats {
+        static STATS: std::sync::OnceLock<[SourceStats; 4]> = std::sync::OnceLock::new();
+        &STATS.get_or_init(Default::default)[self as usize]
+    }
+}
+
+/// Process-wide decode counters, available with the `metrics` feature.
+#[cfg(feature = "metrics")]
+pub struct DecodeMetrics;
+
+#[cfg(feature = "metrics")]
+impl DecodeMetrics {
+    /// Current counters of a single decode path
+    pub fn snapshot(source: DecodeSource) -> DecodeStats {
+        let stats = source.stats();
+        let failed_by_status = stats.failed.lock().unwrap().clone();
+        DecodeStats {
+            submitted:     stats.submitted.load(Ordering::Relaxed),
+            in_flight:     stats.in_flight.load(Ordering::Relaxed),
+            completed:     stats.completed.load(Ordering::Relaxed),
+            failed:        failed_by_status.values().sum(),
+            failed_by_status,
+            bytes_decoded: stats.bytes_decoded.load(Ordering::Relaxed),
+            latency:       stats.latency.snapshot(),
+        }
+    }
+
+    /// Current counters of all decode paths
+    pub fn snapshot_all() -> Vec<(DecodeSource, DecodeStats)> {
+        DecodeSource::ALL.iter().map(|&x| (x, Self::snapshot(x))).collect()
+    }
+
+    /// Reset all counters except the jobs currently in flight
+    pub fn reset() {
+        for source in DecodeSource::ALL {
+            let stats = source.stats();
+            stats.submitted.store(stats.in_flight.load(Ordering::Relaxed), Ordering::Relaxed);
+            stats.completed.store(0, Ordering::Relaxed);
+            stats.bytes_decoded.store(0, Ordering::Relaxed);
+            stats.failed.lock().unwrap().clear();
+            stats.latency.buckets.iter().for_each(|x| x.store(0, Ordering::Relaxed));
+            stats.latency.sum_us.store(0, Ordering::Relaxed);
+            stats.latency.max_us.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Snapshot of the counters of a single decode path
+#[cfg(feature = "metrics")]
+#[derive(Clone, Debug, Default)]
+pub struct DecodeStats {
+    pub submitted: u64,
+    pub in_flight: u64,
+    pub completed: u64,
+    pub failed: u64,
+    /// Failed jobs by `R3DStatus`/`DecodeStatus` name
+    pub failed_by_status: BTreeMap<String, u64>,
+    /// Total size of the output buffers of completed jobs
+    pub bytes_decoded: u64,
+    /// Latency of completed jobs, from submission to completion
+    pub latency: LatencyHistogram,
+}
+
+/// Latency histogram with power of two buckets
+#[cfg(feature = "metrics")]
+#[derive(Clone, Debug, Default)]
+pub struct LatencyHistogram {
+    /// (upper bound, count) for every bucket. The last bucket is unbounded (`Duration::MAX`).
+    pub buckets: Vec<(Duration, u64)>,
+    pub count: u64,
+    pub sum: Duration,
+    pub max: Duration,
+}
+
+#[cfg(feature = "metrics")]
+impl LatencyHistogram {
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 { None } else { Some(self.sum.div_f64(self.count as f64)) }
+    }
+    /// Upper bound of the bucket containing the given percentile (0.0 - 100.0).
+    /// The last bucket reports the maximum latency instead.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let target = ((p.clamp(0.0, 100.0) / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut acc = 0;
+        for &(bound, count) in &self.buckets {
+            acc += count;
+            if acc >= target {
+                return Some(if bound == Duration::MAX { self.max } else { bound.min(self.max) });
+            }
+        }
+        Some(self.max)
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Mock SDK backend, enabled with the `mock` feature.
+//
+// The crate is compiled against `mock/include` and `mock/r3dsdk_mock.cpp` instead of the RED R3D SDK, so it builds
+// and runs without the SDK (e.g. on CI). The public API is unchanged: clips are described with `MockClip`, registered
+// under a path and then opened with `Clip::from_path`. Every decode renders a synthetic test pattern.
+
+use crate::{ IMUSample, Value };
+use core::ffi::c_void;
+use cpp::*;
+use std::time::Duration;
+
+cpp!{{
+    #include "R3DSDKMock.h"
+    #include <cstring>
+}}
+
+/// Test pattern rendered by the decodes of a [`MockClip`].
+///
+/// Exposure (`exposure_compensation` + `exposure_adjust`), the RGB gains and saturation of the image processing
+/// settings are applied to the pattern, the other settings are ignored. The X track of HDRx clips is 3 stops darker.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MockPattern {
+    /// Red = horizontal ramp, green = vertical ramp, blue = frame number / (frame count - 1)
+    Gradient,
+    /// Constant linear RGB color
+    Solid([f32; 3]),
+    /// Black and white squares of the given size in pixels at full resolution, moving one pixel to the right every frame
+    Checkerboard(usize),
+}
+
+/// Synthetic clip served by the mock backend.
+///
+/// Defaults: 1920x1080, 24 frames at 24 fps, one video track, gradient pattern, no metadata and no IMU samples.
+///
+/// ```ignore
+/// MockClip::new("A001_C001.R3D").dimensions(640, 360).frame_count(10).register();
+/// let clip = Clip::from_path("A001_C001.R3D")?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockClip {
+    path: String,
+    width: usize,
+    height: usize,
+    frame_count: usize,
+    video_track_count: usize,
+    framerate: f32,
+    uuid: [u8; 16],
+    metadata: Vec<(String, Value)>,
+    frame_metadata: Vec<(String, Value)>,
+    imu_samples: Vec<IMUSample>,
+    dropped_frames: Vec<usize>,
+    pattern: MockPattern,
+    decode_delay: Duration,
+}
+
+impl MockClip {
+    /// The clip will be served for this exact path, no file is read.
+    pub fn new(path: &str) -> Self {
+        use std::hash::{ Hash, Hasher };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        path.hash(&mut hasher);
+        let hash = hasher.finish().to_le_bytes();
+        let mut uuid = [0u8; 16];
+        uuid[..8].copy_from_slice(&hash);
+        uuid[8..].copy_from_slice(&hash);
+
+        Self {
+            path: path.to_owned(),
+            width: 1920,
+            height: 1080,
+            frame_count: 24,
+            video_track_count: 1,
+            framerate: 24.0,
+            uuid,
+            metadata: Vec::new(),
+            frame_metadata: Vec::new(),
+            imu_samples: Vec::new(),
+            dropped_frames: Vec::new(),
+            pattern: MockPattern::Gradient,
+            decode_delay: Duration::ZERO,
+        }
+    }
+
+    pub fn path(&self) -> &str { &self.path }
+
+    /// Full resolution size of the clip
+    pub fn dimensions(mut self, width: usize, height: usize) -> Self { self.width = width; self.height = height; self }
+    /// A clip with 0 frames fails to load with `LoadStatus::ClipIsEmpty`
+    pub fn frame_count(mut self, v: usize) -> Self { self.frame_count = v; self }
+    /// 2 = HDRx clip
+    pub fn video_track_count(mut self, v: usize) -> Self { self.video_track_count = v.max(1); self }
+    /// Timecode runs at half this rate above 30 fps
+    pub fn framerate(mut self, v: f32) -> Self { self.framerate = v; self }
+    /// Defaults to a value derived from the path
+    pub fn uuid(mut self, v: [u8; 16]) -> Self { self.uuid = v; self }
+    /// Clip metadata item, see [`Clip::metadata`](crate::Clip::metadata)
+    pub fn metadata(mut self, key: &str, value: Value) -> Self { Self::set(&mut self.metadata, key, value); self }
+    /// Metadata item returned for every frame, see [`Clip::metadata_for_frame`](crate::Clip::metadata_for_frame)
+    pub fn frame_metadata(mut self, key: &str, value: Value) -> Self { Self::set(&mut self.frame_metadata, key, value); self }
+    pub fn imu_samples(mut self, v: impl IntoIterator<Item = IMUSample>) -> Self { self.imu_samples = v.into_iter().collect(); self }
+    /// Frames which fail to decode with `IsDroppedFrame` (`ErrorProcessing` in `R3dDecoder`)
+    pub fn dropped_frames(mut self, v: impl IntoIterator<Item = usize>) -> Self { self.dropped_frames = v.into_iter().collect(); self }
+    pub fn pattern(mut self, v: MockPattern) -> Self { self.pattern = v; self }
+    /// Added to every decode, to test timeouts and cancellation
+    pub fn decode_delay(mut self, v: Duration) -> Self { self.decode_delay = v; self }
+
+    fn set(items: &mut Vec<(String, Value)>, key: &str, value: Value) {
+        match items.iter_mut().find(|x| x.0 == key) {
+            Some(item) => item.1 = value,
+            None => items.push((key.to_owned(), value)),
+        }
+    }
+
+    /// Make the clip available to `Clip::from_path` and `Clip::load_from`.
+    /// Registering the same path again replaces the clip, clips which are already open are not affected.
+    pub fn register(&self) {
+        let desc = cpp!(unsafe [] -> *mut c_void as "R3DSDK::Mock::ClipDescription *" {
+            return new R3DSDK::Mock::ClipDescription();
+        });
+
+        let (width, height, frame_count, track_count, framerate) = (self.width, self.height, self.frame_count, self.video_track_count, self.framerate);
+        let uuid = self.uuid.as_ptr();
+        let delay_ms = self.decode_delay.as_millis().min(u32::MAX as u128) as u32;
+        cpp!(unsafe [desc as "R3DSDK::Mock::ClipDescription *", width as "size_t", height as "size_t", frame_count as "size_t",
+                     track_count as "size_t", framerate as "float", uuid as "const uint8_t *", delay_ms as "unsigned int"] {
+            desc->width = width;
+            desc->height = height;
+            desc->frameCount = frame_count;
+            desc->trackCount = track_count;
+            desc->framerate = framerate;
+            memcpy(desc->uuid, uuid, sizeof(desc->uuid));
+            desc->decodeDelayMs = delay_ms;
+        });
+
+        let (pattern, color, checker_size) = match self.pattern {
+            MockPattern::Gradient        => (0, [0.0; 3], 0),
+            MockPattern::Solid(color)    => (1, color, 0),
+            MockPattern::Checkerboard(n) => (2, [0.0; 3], n),
+        };
+        let color = color.as_ptr();
+        cpp!(unsafe [desc as "R3DSDK::Mock::ClipDescription *", pattern as "int", color as "const float *", checker_size as "size_t"] {
+            desc->pattern = (R3DSDK::Mock::FramePattern)pattern;
+            if (pattern == R3DSDK::Mock::PatternSolid) {
+                memcpy(desc->color, color, sizeof(desc->color));
+            }
+            if (pattern == R3DSDK::Mock::PatternCheckerboard) {
+                desc->checkerSize = checker_size;
+            }
+        });
+
+        for (per_frame, items) in [(false, &self.metadata), (true, &self.frame_metadata)] {
+            for (key, value) in items {
+                let c_key = std::ffi::CString::new(key.as_str()).unwrap();
+                let c_key = c_key.as_ptr();
+                let (kind, int_value, float_value, string_value) = match value {
+                    Value::Int(v)    => (1, *v, 0.0, String::new()),
+                    Value::String(v) => (2, 0, 0.0, v.clone()),
+                    Value::Float(v)  => (3, 0, *v, String::new()),
+                };
+                let c_string = std::ffi::CString::new(string_value).unwrap_or_default();
+                let c_string = c_string.as_ptr();
+                cpp!(unsafe [desc as "R3DSDK::Mock::ClipDescription *", per_frame as "bool", c_key as "const char *", kind as "int",
+                             int_value as "unsigned int", float_value as "float", c_string as "const char *"] {
+                    R3DSDK::Mock::MetadataItem item;
+                    item.key = c_key;
+                    item.type = (R3DSDK::MetadataType)kind;
+                    item.intValue = int_value;
+                    item.floatValue = float_value;
+                    item.stringValue = c_string;
+                    (per_frame ? desc->frameMetadata : desc->metadata).Set(item);
+                });
+            }
+        }
+
+        let (imu_ptr, imu_len) = (self.imu_samples.as_ptr(), self.imu_samples.len());
+        let (dropped_ptr, dropped_len) = (self.dropped_frames.as_ptr(), self.dropped_frames.len());
+        cpp!(unsafe [desc as "R3DSDK::Mock::ClipDescription *", imu_ptr as "const R3DSDK::IMUSample *", imu_len as "size_t",
+                     dropped_ptr as "const size_t *", dropped_len as "size_t"] {
+            desc->imuSamples.assign(imu_ptr, imu_ptr + imu_len);
+            desc->droppedFrames.assign(dropped_ptr, dropped_ptr + dropped_len);
+        });
+
+        let c_path = std::ffi::CString::new(self.path.as_str()).unwrap();
+        let c_path = c_path.as_ptr();
+        cpp!(unsafe [desc as "R3DSDK::Mock::ClipDescription *", c_path as "const char *"] {
+            R3DSDK::Mock::RegisterClip(c_path, *desc);
+            delete desc;
+        });
+    }
+
+    /// Remove a registered clip. Returns `false` if no clip was registered for the path.
+    pub fn unregister(path: &str) -> bool {
+        let c_path = std::ffi::CString::new(path).unwrap();
+        let c_path = c_path.as_ptr();
+        cpp!(unsafe [c_path as "const char *"] -> bool as "bool" {
+            return R3DSDK::Mock::UnregisterClip(c_path);
+        })
+    }
+}
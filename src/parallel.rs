@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use crate::{ RedResult, RedError, Clip, SharedClip, AlignedBuffer, ImageProcessingSettings, GradeTimeline };
+use crate::enums::*;
+use std::collections::BTreeMap;
+use std::ops::{ Deref, Range };
+use std::sync::{ Condvar, Mutex, mpsc };
+use std::sync::atomic::{ AtomicBool, AtomicUsize, Ordering };
+
+/// Result of a single frame decoded by [`ParallelDecode`].
+pub type DecodedFrame = (usize, RedResult<AlignedBuffer>);
+
+/// Decode a range of frames with the synchronous DecodeVideoFrame() path on a pool of worker threads.
+///
+/// Created with [`Clip::decode_frames_parallel`] or [`SharedClip::decode_frames_parallel`].
+/// By default frames are delivered in the order they finish decoding and the amount of decoded
+/// frames waiting to be consumed is not limited.
+pub struct ParallelDecode<C> {
+    clip: C,
+    frames: Range<usize>,
+    mode: VideoDecodeMode,
+    pixel_type: VideoPixelType,
+    settings: Option<ImageProcessingSettings>,
+    timeline: Option<GradeTimeline>,
+    threads: usize,
+    max_buffered: Option<usize>,
+    in_order: bool,
+}
+
+impl Clip {
+    /// Decode `frames` on `threads` worker threads (0 = number of available CPU cores).
+    /// Every frame is decoded into a newly allocated buffer which is handed to the consumer, see [`ParallelDecode`] for delivery options.
+    pub fn decode_frames_parallel(&self, frames: Range<usize>, mode: VideoDecodeMode, pixel_type: VideoPixelType, settings: Option<&ImageProcessingSettings>, threads: usize) -> ParallelDecode<&Clip> {
+        ParallelDecode::new(self, frames, mode, pixel_type, settings, threads)
+    }
+}
+
+impl SharedClip {
+    /// Same as [`Clip::decode_frames_parallel`], but the workers keep a reference to the clip,
+    /// so the decode can run in the background with [`ParallelDecode::spawn`].
+    pub fn decode_frames_parallel(&self, frames: Range<usize>, mode: VideoDecodeMode, pixel_type: VideoPixelType, settings: Option<&ImageProcessingSettings>, threads: usize) -> ParallelDecode<SharedClip> {
+        ParallelDecode::new(self.clone(), frames, mode, pixel_type, settings, threads)
+    }
+}
+
+impl<C: Deref<Target = Clip>> ParallelDecode<C> {
+    fn new(clip: C, frames: Range<usize>, mode: VideoDecodeMode, pixel_type: VideoPixelType, settings: Option<&ImageProcessingSettings>, threads: usize) -> Self {
+        let threads = if threads == 0 {
+            std::thread::available_parallelism().map(|x| x.get()).unwrap_or(1)
+        } else {
+            threads
+        };
+        Self {
+            clip,
+            frames,
+            mode,
+            pixel_type,
+            settings: settings.cloned(),
+            timeline: None,
+            threads,
+            max_buffered: None,
+            in_order: false,
+        }
+    }
+
+    /// Limit the number of frames being decoded, waiting for delivery or in the callback to `n`.
+    /// Workers wait for the consumer when the limit is reached, so at most `n` output buffers are alive
+    /// (plus the ones the callback keeps).
+    pub fn max_buffered_frames(mut self, n: usize) -> Self {
+        self.max_buffered = Some(n.max(1));
+        self
+    }
+
+    /// Deliver the frames in ascending frame order instead of the order they finish decoding.
+    pub fn in_order(mut self, in_order: bool) -> Self {
+        self.in_order = in_order;
+        self
+    }
+
+    /// Grade every frame with the settings interpolated from `timeline` instead of the static settings.
+    /// HDRx blending is enabled for frames whose keyframes have HDRx settings.
+    pub fn grade_timeline(mut self, timeline: &GradeTimeline) -> Self {
+        self.timeline = Some(timeline.clone());
+        self
+    }
+
+    /// Run the decode and call `callback` on the current thread for every frame.
+    /// Return `false` from the callback to stop decoding, frames which are already being decoded are discarded.
+    /// Returns when all frames were delivered or the decode was stopped.
+    pub fn for_each<F: FnMut(usize, RedResult<AlignedBuffer>) -> bool>(self, mut callback: F) {
+        let clip: &Clip = &self.clip;
+        let next_frame = AtomicUsize::new(self.frames.start);
+        let cancelled = AtomicBool::new(false);
+        let permits = Permits::new(self.max_buffered);
+        let threads = self.threads.min(self.frames.len()).max(1);
+
+        std::thread::scope(|s| {
+            let (tx, rx) = mpsc::channel::<DecodedFrame>();
+            for _ in 0..threads {
+                let tx = tx.clone();
+                let (next_frame, cancelled, permits) = (&next_frame, &cancelled, &permits);
+                let (frames, mode, pixel_type, settings, timeline) = (&self.frames, self.mode, self.pixel_type, self.settings.as_ref(), self.timeline.as_ref());
+                s.spawn(move || {
+                    // Acquire the permit before picking the frame number, so the lowest
+                    // undelivered frame always has a permit and in-order delivery can't deadlock.
+                    while permits.acquire(cancelled) {
+                        let frame_no = next_frame.fetch_add(1, Ordering::SeqCst);
+                        if frame_no >= frames.end {
+                            permits.release(1);
+                            break;
+                        }
+                        let grade = timeline.and_then(|x| x.grade_at(frame_no));
+                        let (frame_settings, hdr_settings) = match &grade {
+                            Some(grade) => (Some(grade.to_settings()), grade.to_hdr_settings()),
+                            None => (settings.copied(), None),
+                        };
+                        let result = clip.decode_video_frame(frame_no, mode, pixel_type, frame_settings.as_ref(), hdr_settings.as_ref(), None)
+                            .and_then(|buf| buf.ok_or(RedError::OutputBufferInvalid));
+                        if tx.send((frame_no, result)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(tx);
+
+            let mut pending = BTreeMap::new();
+            let mut expected = self.frames.start;
+            for (frame_no, result) in rx.iter() {
+                let keep_going = if self.in_order {
+                    pending.insert(frame_no, result);
+                    let mut keep_going = true;
+                    while keep_going && let Some(result) = pending.remove(&expected) {
+                        keep_going = callback(expected, result);
+                        permits.release(1);
+                        expected += 1;
+                    }
+                    keep_going
+                } else {
+                    // Released once the callback returned, so the frame counts towards the limit while the callback runs
+                    let keep_going = callback(frame_no, result);
+                    permits.release(1);
+                    keep_going
+                };
+                if !keep_going {
+                    cancelled.store(true, Ordering::SeqCst);
+                    permits.wake_all();
+                    break;
+                }
+            }
+            // `rx` is dropped here, so workers stop after their current frame.
+        });
+    }
+}
+
+impl ParallelDecode<SharedClip> {
+    /// Run the decode in the background and return a channel receiving the decoded frames.
+    /// Dropping the receiver stops the decode. When [`max_buffered_frames`](Self::max_buffered_frames)
+    /// is set, a frame only stops counting towards the limit once it has been received.
+    pub fn spawn(self) -> mpsc::Receiver<DecodedFrame> {
+        let (tx, rx) = if self.max_buffered.is_some() {
+            let (tx, rx) = mpsc::sync_channel(0);
+            (Sender::Sync(tx), rx)
+        } else {
+            let (tx, rx) = mpsc::channel();
+            (Sender::Async(tx), rx)
+        };
+        std::thread::spawn(move || {
+            self.for_each(|frame_no, result| tx.send((frame_no, result)));
+        });
+        rx
+    }
+}
+
+enum Sender {
+    Sync(mpsc::SyncSender<DecodedFrame>),
+    Async(mpsc::Sender<DecodedFrame>),
+}
+impl Sender {
+    fn send(&self, v: DecodedFrame) -> bool {
+        match self {
+            Sender::Sync(tx) => tx.send(v).is_ok(),
+            Sender::Async(tx) => tx.send(v).is_ok(),
+        }
+    }
+}
+
+/// Counting semaphore limiting the number of frames in flight.
+struct Permits {
+    available: Mutex<Option<usize>>,
+    cond: Condvar,
+}
+impl Permits {
+    fn new(max: Option<usize>) -> Self {
+        Self { available: Mutex::new(max), cond: Condvar::new() }
+    }
+    /// Returns `false` if the decode was cancelled while waiting.
+    fn acquire(&self, cancelled: &AtomicBool) -> bool {
+        let mut available = self.available.lock().unwrap();
+        loop {
+            if cancelled.load(Ordering::SeqCst) {
+                return false;
+            }
+            match available.as_mut() {
+                None => return true,
+                Some(n) if *n > 0 => { *n -= 1; return true; }
+                Some(_) => { available = self.cond.wait(available).unwrap(); }
+            }
+        }
+    }
+    fn release(&self, count: usize) {
+        if let Some(n) = self.available.lock().unwrap().as_mut() {
+            *n += count;
+            self.cond.notify_all();
+        }
+    }
+    fn wake_all(&self) {
+        let _lock = self.available.lock().unwrap();
+        self.cond.notify_all();
+    }
+}
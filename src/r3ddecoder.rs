@@ -1,29 +1,52 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
-use crate::{ RedResult, RedError,  clip::AlignedBuffer, enums::*, future::*, metadata::* };
+use crate::{ RedResult, RedError, ErrorContext, Sdk, DecodeFailure, metrics::*, AsyncDecoder, clip::AlignedBuffer, enums::*, future::*, metadata::*, ImageProcessingSettings, HdrProcessingSettings, GradeTimeline };
 use core::ffi::c_void;
-use std::sync::{ Arc, atomic::Ordering };
+use std::sync::Arc;
+use std::time::Duration;
 use cpp::*;
 
 cpp! {{
     #include "R3DSDKDecoder.h"
+    #include <cstdint>
+    #if defined(_WIN32)
+        #ifndef NOMINMAX
+            #define NOMINMAX
+        #endif
+        #ifndef WIN32_LEAN_AND_MEAN
+            #define WIN32_LEAN_AND_MEAN
+        #endif
+        #include <windows.h>
+    #elif defined(__APPLE__)
+        #include <sys/sysctl.h>
+    #else
+        #include <unistd.h>
+    #endif
 }}
 
 pub struct R3dDecoder {
-    ptr: *mut core::ffi::c_void
+    ptr: *mut core::ffi::c_void,
+    timeout: Option<Duration>,
+    tracker: Arc<JobTracker>,
+    _sdk: Sdk,
 }
+// SAFETY: R3DDecoder::decode() is thread-safe, the decoder only holds an opaque pointer to the SDK object.
+unsafe impl Send for R3dDecoder {}
+unsafe impl Sync for R3dDecoder {}
+
 impl R3dDecoder {
     pub fn new(options: &R3dDecoderOptions) -> RedResult<Self> {
+        let sdk = Sdk::current()?;
         unsafe {
             let mut ptr = std::ptr::null_mut();
             let ptrptr = &mut ptr;
             let options_ptr = options.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!([options_ptr as "R3DSDK::R3DDecoderOptions *", ptrptr as "R3DSDK::R3DDecoder **"] -> i32 as "int" { return R3DSDK::R3DDecoder::CreateDecoder(options_ptr, ptrptr); }));
+            let status = R3DStatus::from(cpp!([options_ptr as "R3DSDK::R3DDecoderOptions *", ptrptr as "R3DSDK::R3DDecoder **"] -> i32 as "int" { return R3DSDK::R3DDecoder::CreateDecoder(options_ptr, ptrptr); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
-                Ok(Self { ptr })
+                Ok(Self { ptr, timeout: None, tracker: Arc::default(), _sdk: sdk })
             }
         }
     }
@@ -32,41 +55,84 @@ impl R3dDecoder {
         self.ptr
     }
 
+    /// Default timeout applied to every future returned by this decoder, see [`CallbackFuture::with_timeout`].
+    pub fn set_default_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+    pub fn default_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     /// Asynchronously decode a single frame as specified in the job.
     /// The decode will be scheduled and the function will immediately return a future.
     ///
     /// You should await the returned future to get the status of the decode operation.
     /// The future gives you back ownership of the job you passed in, so you can re-use it if needed.
-    pub fn decode(&self, job: R3dDecodeJob) -> RedResult<CallbackFuture<R3dDecodeJob>> {
+    /// The job is also returned on failure, as part of [`DecodeFailure`].
+    pub fn decode(&self, job: R3dDecodeJob) -> Result<CallbackFuture<R3dDecodeJob>, DecodeFailure<R3dDecodeJob>> {
         unsafe {
             let ptr = self.ptr;
             let job_ptr = job.ptr;
+            self.tracker.release_retired();
 
-            let state = Arc::new(State::new(job));
-            let state_ptr = Arc::as_ptr(&state) as *mut c_void;
+            let metrics = JobMetrics::start(DecodeSource::R3dDecoder, job.video_frame_no());
+            let _scope = metrics.enter();
+            let state = Arc::new(State::new(job, metrics, self.tracker.clone()));
+            // The callback owns one reference, so the state outlives the future if it's dropped before completion.
+            let state_ptr = Arc::into_raw(state.clone()) as *mut c_void;
 
-            let callback_ptr = decode_callback as extern "C" fn(*mut c_void, R3DStatus);
+            let callback_ptr = decode_callback as extern "C" fn(*mut c_void, i32);
+            self.tracker.add(job_ptr);
 
-            let status: R3DStatus = std::mem::transmute(cpp!([ptr as "R3DSDK::R3DDecoder *", job_ptr as "R3DSDK::R3DDecodeJob *", state_ptr as "void *", callback_ptr as "void *"] -> i32 as "int" {
+            let status = R3DStatus::from(cpp!([ptr as "R3DSDK::R3DDecoder *", job_ptr as "R3DSDK::R3DDecodeJob *", state_ptr as "void *", callback_ptr as "void *"] -> i32 as "int" {
                 job_ptr->callback = (R3DSDK::R3DDecodeJob::DecodeCallback)callback_ptr;
                 job_ptr->privateData = state_ptr;
                 return ptr->decode(job_ptr);
             }));
             if status != R3DStatus::Ok {
-                Err(RedError::from(status))
+                // The job was not scheduled, so the callback will never be called
+                drop(Arc::from_raw(state_ptr as *const State<R3dDecodeJob>));
+                self.tracker.remove(job_ptr);
+                state.finish_metrics(Err(status));
+                let job = state.job.lock().unwrap().take();
+                let error = RedError::from(status).with_context(R3dDecodeJob::error_context(job.as_ref()).status(status.code()));
+                Err(DecodeFailure { job, error })
             } else {
-                Ok(CallbackFuture { state })
+                Ok(CallbackFuture::new(state, self.timeout))
             }
         }
     }
+
+    /// Release the decoder once all submitted jobs have finished.
+    ///
+    /// The SDK can't cancel jobs already submitted to the R3DDecoder, so the returned future resolves
+    /// after the callbacks of all outstanding jobs fired (resolving their futures), then the decoder is released.
+    /// Dropping the decoder does the same, but blocks the current thread while waiting.
+    ///
+    /// The wait is limited to the [default timeout](Self::set_default_timeout), or 30 seconds without one. If some callbacks
+    /// still didn't fire by then (e.g. a hung decode), the future resolves anyway and the native decoder is leaked, see `Drop`.
+    pub fn shutdown(self) -> impl Future<Output = ()> + Send {
+        let (tracker, timeout) = (self.tracker.clone(), self.timeout);
+        DecoderShutdown::new(self, tracker, timeout)
+    }
 }
 
 impl Drop for R3dDecoder {
+    /// Waits for the callbacks of all submitted jobs, at most for the [default timeout](Self::set_default_timeout)
+    /// (or 30 seconds without one) counted from the drop or from [`shutdown`](Self::shutdown).
+    /// If jobs are still pending after that, `ReleaseDecoder` is skipped and the native decoder and the SDK are leaked
+    /// with a warning, so a late callback can never touch freed memory.
     fn drop(&mut self) {
+        // Make sure no callback can fire after the decoder is released
+        if !self.tracker.wait_release(self.timeout, "R3dDecoder") {
+            std::mem::forget(self._sdk.clone());
+            return;
+        }
         let ptr = self.ptr;
         cpp!(unsafe [ptr as "R3DSDK::R3DDecoder *"] {
             R3DSDK::R3DDecoder::ReleaseDecoder(ptr);
         });
+        self.tracker.release_retired();
     }
 }
 
@@ -78,18 +144,25 @@ impl Drop for R3dDecoder {
 /// The defaults the SDK chooses are usually core count - 1 for threads, and min(device memory * 0.75, concurrent GPU Frames * 1GB) for GPUs, for host memory the value is by default set to concurrentImageCount * 512MB,
 /// note if the device of the host actually runs out of memory the out of memory status will be returned from either the callback or the decode call, based upon when it occurs.  Memory pools do not pre-allocate the memory.
 pub struct R3dDecoderOptions {
-    ptr: *mut core::ffi::c_void
+    ptr: *mut core::ffi::c_void,
+    config: R3dDecoderConfig,
+    _sdk: Sdk,
 }
+// SAFETY: all mutation goes through &mut self, the SDK object is not tied to the creating thread.
+unsafe impl Send for R3dDecoderOptions {}
+unsafe impl Sync for R3dDecoderOptions {}
+
 impl R3dDecoderOptions {
     pub fn new() -> RedResult<Self> {
+        let sdk = Sdk::current()?;
         unsafe {
             let mut ptr = std::ptr::null_mut();
             let ptrptr = &mut ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!([ptrptr as "R3DSDK::R3DDecoderOptions **"] -> i32 as "int" { return R3DSDK::R3DDecoderOptions::CreateOptions(ptrptr); }));
+            let status = R3DStatus::from(cpp!([ptrptr as "R3DSDK::R3DDecoderOptions **"] -> i32 as "int" { return R3DSDK::R3DDecoderOptions::CreateOptions(ptrptr); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
-                Ok(Self { ptr })
+                Ok(Self { ptr, config: R3dDecoderConfig::default(), _sdk: sdk })
             }
         }
     }
@@ -107,10 +180,11 @@ impl R3dDecoderOptions {
         unsafe {
             let self_ptr = self.ptr;
             let c_ptr = c_path.as_ptr();
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", c_ptr as "const char *"] -> i32 as "int" { return self_ptr->setScratchFolder(std::string(c_ptr)); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", c_ptr as "const char *"] -> i32 as "int" { return self_ptr->setScratchFolder(std::string(c_ptr)); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.scratch_folder = Some(path.to_owned());
                 Ok(())
             }
         }
@@ -123,10 +197,11 @@ impl R3dDecoderOptions {
     pub fn set_decompression_thread_count(&mut self, count: usize) -> RedResult<()> {
         unsafe {
             let self_ptr = self.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", count as "size_t"] -> i32 as "int" { return self_ptr->setDecompressionThreadCount(count); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", count as "size_t"] -> i32 as "int" { return self_ptr->setDecompressionThreadCount(count); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.decompression_thread_count = Some(count);
                 Ok(())
             }
         }
@@ -139,10 +214,11 @@ impl R3dDecoderOptions {
     pub fn set_concurrent_image_count(&mut self, count: usize) -> RedResult<()> {
         unsafe {
             let self_ptr = self.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", count as "size_t"] -> i32 as "int" { return self_ptr->setConcurrentImageCount(count); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", count as "size_t"] -> i32 as "int" { return self_ptr->setConcurrentImageCount(count); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.concurrent_image_count = Some(count);
                 Ok(())
             }
         }
@@ -152,10 +228,11 @@ impl R3dDecoderOptions {
     pub fn set_memory_pool_size(&mut self, size_mbs: usize) -> RedResult<()> {
         unsafe {
             let self_ptr = self.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", size_mbs as "size_t"] -> i32 as "int" { return self_ptr->setMemoryPoolSize(size_mbs); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", size_mbs as "size_t"] -> i32 as "int" { return self_ptr->setMemoryPoolSize(size_mbs); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.memory_pool_size = Some(size_mbs);
                 Ok(())
             }
         }
@@ -167,10 +244,11 @@ impl R3dDecoderOptions {
 	pub fn set_gpu_memory_pool_size(&mut self, size_mbs: usize) -> RedResult<()> {
         unsafe {
             let self_ptr = self.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", size_mbs as "size_t"] -> i32 as "int" { return self_ptr->setGPUMemoryPoolSize(size_mbs); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", size_mbs as "size_t"] -> i32 as "int" { return self_ptr->setGPUMemoryPoolSize(size_mbs); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.gpu_memory_pool_size = Some(size_mbs);
                 Ok(())
             }
         }
@@ -187,10 +265,11 @@ impl R3dDecoderOptions {
     pub fn set_gpu_concurrent_frame_count(&mut self, count: usize) -> RedResult<()> {
         unsafe {
             let self_ptr = self.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", count as "size_t"] -> i32 as "int" { return self_ptr->setGPUConcurrentFrameCount(count); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", count as "size_t"] -> i32 as "int" { return self_ptr->setGPUConcurrentFrameCount(count); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.gpu_concurrent_frame_count = Some(count);
                 Ok(())
             }
         }
@@ -202,7 +281,7 @@ impl R3dDecoderOptions {
             let mut list = Vec::<OpenCLDeviceInfo>::new();
             let list_ref = &mut list;
             //let list_ptr = list.as_mut_ptr();
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [list_ref as "void*"] -> i32 as "int" {
+            let status = R3DStatus::from(cpp!([list_ref as "void*"] -> i32 as "int" {
                 std::vector<R3DSDK::OpenCLDeviceInfo> list;
                 auto status = R3DSDK::R3DDecoderOptions::GetOpenCLDeviceList(list);
                 for (const auto &item : list) {
@@ -225,7 +304,7 @@ impl R3dDecoderOptions {
         unsafe {
             let mut list = Vec::<CudaDeviceInfo>::new();
             let list_ref = &mut list;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [list_ref as "void*"] -> i32 as "int" {
+            let status = R3DStatus::from(cpp!([list_ref as "void*"] -> i32 as "int" {
                 std::vector<R3DSDK::CudaDeviceInfo> list;
                 auto status = R3DSDK::R3DDecoderOptions::GetCudaDeviceList(list);
                 for (const auto &item : list) {
@@ -248,10 +327,11 @@ impl R3dDecoderOptions {
     pub fn use_opencl_device(&mut self, device: &OpenCLDeviceInfo) -> RedResult<()> {
         unsafe {
             let self_ptr = self.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", device as "const R3DSDK::OpenCLDeviceInfo *"] -> i32 as "int" { return self_ptr->useDevice(*device); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", device as "const R3DSDK::OpenCLDeviceInfo *"] -> i32 as "int" { return self_ptr->useDevice(*device); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.devices.push(DecoderDevice::OpenCL { platform_name: device.platform_name(), name: device.name() });
                 Ok(())
             }
         }
@@ -261,15 +341,125 @@ impl R3dDecoderOptions {
     pub fn use_cuda_device(&mut self, device: &CudaDeviceInfo) -> RedResult<()> {
         unsafe {
             let self_ptr = self.ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!(unsafe [self_ptr as "R3DSDK::R3DDecoderOptions *", device as "const R3DSDK::CudaDeviceInfo *"] -> i32 as "int" { return self_ptr->useDevice(*device); }));
+            let status = R3DStatus::from(cpp!([self_ptr as "R3DSDK::R3DDecoderOptions *", device as "const R3DSDK::CudaDeviceInfo *"] -> i32 as "int" { return self_ptr->useDevice(*device); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
+                self.config.devices.push(DecoderDevice::Cuda { pci_bus_id: device.pci_bus_id(), name: device.name() });
                 Ok(())
             }
         }
     }
 
+    /// Options tuned for interactive playback: few frames in flight for low latency,
+    /// host memory pool sized for them and limited to a quarter of the available system memory (of the total physical memory on macOS).
+    pub fn for_playback() -> RedResult<Self> {
+        let threads = AsyncDecoder::threads_available().max(1);
+        Self::preset(threads, threads.min(4), 512, 1024, 4, 2)
+    }
+
+    /// Options tuned for batch transcoding throughput: one frame in flight per thread,
+    /// host memory pool limited to half of the available system memory (of the total physical memory on macOS).
+    pub fn for_batch_transcode() -> RedResult<Self> {
+        let threads = AsyncDecoder::threads_available().max(1);
+        Self::preset(threads, threads, 1024, 4096, 2, 3)
+    }
+
+    fn preset(threads: usize, concurrent_images: usize, mb_per_image: usize, min_pool_mb: usize, memory_divisor: usize, gpu_frames: usize) -> RedResult<Self> {
+        let mut pool_mb = (concurrent_images * mb_per_image).max(min_pool_mb);
+        if let Some(available) = available_system_memory() {
+            // Never go below the SDK minimum of 1024 MB
+            pool_mb = pool_mb.min((available / 1024 / 1024) as usize / memory_divisor).max(1024);
+        }
+        let mut options = Self::new()?;
+        options.set_decompression_thread_count(threads)?;
+        options.set_concurrent_image_count(concurrent_images)?;
+        options.set_memory_pool_size(pool_mb)?;
+        options.set_gpu_concurrent_frame_count(gpu_frames)?;
+        Ok(options)
+    }
+
+    /// The configuration set on this instance so far. Values which were never set use the SDK defaults.
+    pub fn config(&self) -> &R3dDecoderConfig {
+        &self.config
+    }
+    pub fn scratch_folder(&self) -> Option<&str> {
+        self.config.scratch_folder.as_deref()
+    }
+    pub fn decompression_thread_count(&self) -> Option<usize> {
+        self.config.decompression_thread_count
+    }
+    pub fn concurrent_image_count(&self) -> Option<usize> {
+        self.config.concurrent_image_count
+    }
+    pub fn memory_pool_size(&self) -> Option<usize> {
+        self.config.memory_pool_size
+    }
+    pub fn gpu_memory_pool_size(&self) -> Option<usize> {
+        self.config.gpu_memory_pool_size
+    }
+    pub fn gpu_concurrent_frame_count(&self) -> Option<usize> {
+        self.config.gpu_concurrent_frame_count
+    }
+    /// Devices added with use_cuda_device() or use_opencl_device()
+    pub fn devices(&self) -> &[DecoderDevice] {
+        &self.config.devices
+    }
+
+}
+
+impl std::fmt::Debug for R3dDecoderOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.config.fmt(f)
+    }
+}
+
+/// Rust-side record of the values set on [`R3dDecoderOptions`], as the SDK doesn't allow reading them back.
+/// `None` means the value was not set and the SDK default is used. Memory pool sizes are in MB.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct R3dDecoderConfig {
+    pub scratch_folder: Option<String>,
+    pub decompression_thread_count: Option<usize>,
+    pub concurrent_image_count: Option<usize>,
+    pub memory_pool_size: Option<usize>,
+    pub gpu_memory_pool_size: Option<usize>,
+    pub gpu_concurrent_frame_count: Option<usize>,
+    pub devices: Vec<DecoderDevice>,
+}
+
+/// GPU device added to [`R3dDecoderOptions`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecoderDevice {
+    Cuda { pci_bus_id: i32, name: String },
+    OpenCL { platform_name: String, name: String },
+}
+
+/// Physical memory available without swapping in bytes, None if it can't be determined.
+/// On Linux this is `MemAvailable`, which includes the page cache that can be reclaimed.
+/// macOS has no equivalent, so this is the total physical memory there.
+fn available_system_memory() -> Option<u64> {
+    if cfg!(target_os = "linux")
+        && let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo")
+        && let Some(kb) = meminfo.lines().find_map(|x| x.strip_prefix("MemAvailable:")?.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()) {
+        return Some(kb * 1024);
+    }
+    // Other platforms, or Linux kernels older than 3.14 without MemAvailable (where only the free pages are counted)
+    let bytes = cpp!(unsafe [] -> u64 as "uint64_t" {
+    #if defined(_WIN32)
+        MEMORYSTATUSEX status;
+        status.dwLength = sizeof(status);
+        return GlobalMemoryStatusEx(&status) ? (uint64_t)status.ullAvailPhys : 0;
+    #elif defined(__APPLE__)
+        uint64_t size = 0;
+        size_t len = sizeof(size);
+        return sysctlbyname("hw.memsize", &size, &len, nullptr, 0) == 0 ? size : 0;
+    #else
+        long pages = sysconf(_SC_AVPHYS_PAGES);
+        long page_size = sysconf(_SC_PAGE_SIZE);
+        return (pages > 0 && page_size > 0) ? (uint64_t)pages * (uint64_t)page_size : 0;
+    #endif
+    });
+    if bytes > 0 { Some(bytes) } else { None }
 }
 
 cpp_class! {
@@ -359,24 +549,40 @@ impl Drop for R3dDecoderOptions {
 pub struct R3dDecodeJob {
     ptr: *mut core::ffi::c_void,
     internal_buffer: Option<AlignedBuffer>,
+    image_settings: Option<Box<ImageProcessingSettings>>,
+    hdr_settings: Option<Box<HdrProcessingSettings>>,
     metadata_allocated: bool,
+    clip_path: Option<String>,
 }
+// SAFETY: the job is exclusively owned (by the caller or by the pending future), setters take &mut self.
+unsafe impl Send for R3dDecodeJob {}
+unsafe impl Sync for R3dDecodeJob {}
+
 impl R3dDecodeJob {
     pub fn new() -> RedResult<Self> {
         unsafe {
             let mut ptr = std::ptr::null_mut();
             let ptrptr = &mut ptr;
-            let status: R3DStatus = std::mem::transmute(cpp!([ptrptr as "R3DSDK::R3DDecodeJob **"] -> i32 as "int" { return R3DSDK::R3DDecoder::CreateDecodeJob(ptrptr); }));
+            let status = R3DStatus::from(cpp!([ptrptr as "R3DSDK::R3DDecodeJob **"] -> i32 as "int" { return R3DSDK::R3DDecoder::CreateDecodeJob(ptrptr); }));
             if status != R3DStatus::Ok {
                 Err(RedError::from(status))
             } else {
-                Ok(Self { ptr, internal_buffer: None, metadata_allocated: false })
+                Ok(Self { ptr, internal_buffer: None, image_settings: None, hdr_settings: None, metadata_allocated: false, clip_path: None })
             }
         }
     }
     pub fn as_mut_ptr(&self) -> *mut core::ffi::c_void {
         self.ptr
     }
+
+    /// Error context for a failed decode of `job`, the job is `None` if it was lost
+    fn error_context(job: Option<&Self>) -> ErrorContext {
+        let context = ErrorContext::new("R3dDecoder::decode");
+        match job {
+            Some(job) => context.clip_path(job.clip_path.as_deref()).video_frame_no(job.video_frame_no()).video_track_no(job.video_track_no()),
+            None => context,
+        }
+    }
 }
 impl Drop for R3dDecodeJob {
     fn drop(&mut self) {
@@ -398,7 +604,8 @@ impl R3dDecodeJob {
         let clip_ptr = clip.as_mut_ptr();
 		cpp!(unsafe [self_ptr as "R3DSDK::R3DDecodeJob *", clip_ptr as "R3DSDK::Clip *"] {
 			self_ptr->clip = clip_ptr;
-		})
+		});
+        self.clip_path = clip.path().map(str::to_owned);
     }
     /// 0 = main (A) track, 1 = EPIC/Scarlet-X higlight protection track 2 (X track)
 	/// ignored when doing HDRx blending
@@ -406,6 +613,10 @@ impl R3dDecodeJob {
         let self_ptr = self.ptr;
         cpp!(unsafe [self_ptr as "R3DSDK::R3DDecodeJob *", v as "size_t"] { self_ptr->videoTrackNo = v; })
     }
+    pub fn video_track_no(&self) -> usize {
+        let self_ptr = self.ptr;
+        cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> usize as "size_t" { return self_ptr->videoTrackNo; })
+    }
     pub fn set_video_frame_no(&mut self, v: usize) {
         let self_ptr = self.ptr;
         cpp!(unsafe [self_ptr as "R3DSDK::R3DDecodeJob *", v as "size_t"] { self_ptr->videoFrameNo = v; })
@@ -413,6 +624,10 @@ impl R3dDecodeJob {
     pub fn video_frame_no(&self) -> usize {
         let self_ptr = self.ptr;
         cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> usize as "size_t" { return self_ptr->videoFrameNo; })
+    }
+    pub fn output_buffer_size(&self) -> usize {
+        let self_ptr = self.ptr;
+        cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> usize as "size_t" { return self_ptr->outputBufferSize; })
     }
 	// item is the pointer to the original item as submitted in the Decode() call
 	// typedef void (*DecodeCallback)(R3DDecodeJob * item, R3DStatus decodeStatus);
@@ -421,10 +636,11 @@ impl R3dDecodeJob {
 
 
 	/// Resolution/speed to decode the image at. This will also
-	/// influence how much memory is needed for the output buffer
-	pub fn mode(&self) -> VideoDecodeMode {
+	/// influence how much memory is needed for the output buffer.
+	/// `RedError::InvalidEnumValue` if the mode wasn't set yet.
+	pub fn mode(&self) -> RedResult<VideoDecodeMode> {
         let self_ptr = self.ptr;
-		unsafe { std::mem::transmute(cpp!([self_ptr as "const R3DSDK::R3DDecodeJob *"] -> i32 as "int" { return (int)self_ptr->mode; })) }
+		VideoDecodeMode::try_from(cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> i32 as "int" { return (int)self_ptr->mode; }))
 	}
 	/// Resolution/speed to decode the image at. This will also
 	/// influence how much memory is needed for the output buffer
@@ -435,9 +651,9 @@ impl R3dDecodeJob {
 	}
 	/// Which pixel type to decode the image in. See the VideoPixelType enum for the available options. To get the image in the
 	/// original pixel type supported by earlier SDK's set PixelType_16Bit_RGB_Planar
-	pub fn pixel_type(&self) -> VideoPixelType {
+	pub fn pixel_type(&self) -> RedResult<VideoPixelType> {
         let self_ptr = self.ptr;
-		unsafe { std::mem::transmute(cpp!([self_ptr as "const R3DSDK::R3DDecodeJob *"] -> i32 as "int" { return (int)self_ptr->pixelType; })) }
+		VideoPixelType::try_from(cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> i32 as "int" { return (int)self_ptr->pixelType; }))
 	}
 
 	/// Which pixel type to decode the image in. See the VideoPixelType
@@ -468,20 +684,83 @@ impl R3dDecodeJob {
 	/// Allocate internal buffer for the output image.
     /// Always aligned to 1024 bytes.
 	pub fn allocate_internal_buffer(&mut self, clip: &crate::clip::Clip) -> RedResult<()> {
-        self.internal_buffer = Some(clip.allocate_aligned_buffer(&self.mode(), &self.pixel_type(), 1024)?);
+        self.internal_buffer = Some(clip.allocate_aligned_buffer(&self.mode()?, &self.pixel_type()?, 1024)?);
         let buf = self.internal_buffer.as_ref().unwrap();
         self.set_output_buffer(buf.ptr, buf.len());
         Ok(())
 	}
 
 	/// Image processing settings to apply to the decode. This cannot be NULL.
-	pub fn set_image_processing(&mut self, v: &crate::image_processing_settings::ImageProcessingSettings) {
+	pub fn set_image_processing(&mut self, v: &ImageProcessingSettings) {
 		let ptr = v as *const _;
         let self_ptr = self.ptr;
 		cpp!(unsafe [self_ptr as "R3DSDK::R3DDecodeJob *", ptr as "R3DSDK::ImageProcessingSettings *"] {
 			self_ptr->imageProcessingSettings = ptr;
 		})
 	}
+	/// Same as set_image_processing(), but the job keeps its own copy of the settings,
+	/// so they don't have to outlive the job.
+	pub fn set_image_processing_owned(&mut self, v: ImageProcessingSettings) {
+		let settings = Box::new(v);
+		self.set_image_processing(&settings);
+		self.image_settings = Some(settings);
+	}
+
+	/// HDRx processing settings to apply to the decode. This parameter is optional.
+	/// When set, both tracks are blended and the video track number is ignored.
+	/// The clip must be set before calling this function, returns NotAnHDRxClip if the clip is not an HDRx clip.
+	pub fn set_hdr_processing(&mut self, v: &HdrProcessingSettings) -> RedResult<()> {
+		let self_ptr = self.ptr;
+		let track_count = cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> usize as "size_t" {
+			return self_ptr->clip ? self_ptr->clip->VideoTrackCount() : 0;
+		});
+		match track_count {
+			0 => return Err(RedError::InvalidJobParameterClip),
+			1 => return Err(RedError::NotAnHDRxClip),
+			_ => { }
+		}
+		let ptr = v as *const _;
+		cpp!(unsafe [self_ptr as "R3DSDK::R3DDecodeJob *", ptr as "R3DSDK::HdrProcessingSettings *"] {
+			self_ptr->hdrProcessingSettings = ptr;
+		});
+		Ok(())
+	}
+	/// Same as set_hdr_processing(), but the job keeps its own copy of the settings,
+	/// so they don't have to outlive the job.
+	pub fn set_hdr_processing_owned(&mut self, v: HdrProcessingSettings) -> RedResult<()> {
+		let settings = Box::new(v);
+		self.set_hdr_processing(&settings)?;
+		self.hdr_settings = Some(settings);
+		Ok(())
+	}
+	/// Image processing settings for the frame set with set_video_frame_no(), interpolated from a keyframed grade.
+	/// The job keeps its own copy of the settings. If the grade has HDRx settings, HDRx blending is enabled
+	/// as with set_hdr_processing_owned(), so the clip must be set first, otherwise it's disabled as with clear_hdr_processing().
+	/// Returns InvalidParameter if the timeline has no keyframes.
+	pub fn set_grade_timeline(&mut self, timeline: &GradeTimeline) -> RedResult<()> {
+		let grade = timeline.grade_at(self.video_frame_no()).ok_or(RedError::InvalidParameter)?;
+		match grade.to_hdr_settings() {
+			Some(hdr_settings) => self.set_hdr_processing_owned(hdr_settings)?,
+			None => self.clear_hdr_processing(),
+		}
+		self.set_image_processing_owned(grade.to_settings());
+		Ok(())
+	}
+	/// Disable HDRx blending and decode the track set with set_video_track_no() again.
+	pub fn clear_hdr_processing(&mut self) {
+		let self_ptr = self.ptr;
+		cpp!(unsafe [self_ptr as "R3DSDK::R3DDecodeJob *"] {
+			self_ptr->hdrProcessingSettings = nullptr;
+		});
+		self.hdr_settings = None;
+	}
+	/// Returns true if HDRx blending is enabled for this job.
+	pub fn hdr_processing_enabled(&self) -> bool {
+		let self_ptr = self.ptr;
+		cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> bool as "bool" {
+			return self_ptr->hdrProcessingSettings != nullptr;
+		})
+	}
 
 	/// Allocate internal metadata object to receive per-frame metadata.
     /// Call this before submitting the job if you want to receive metadata.
@@ -499,7 +778,7 @@ impl R3dDecodeJob {
     /// Optionally return the decoded frame's per-frame metadata
     pub fn metadata(&self) -> RedResult<&Metadata> {
         if !self.metadata_allocated {
-            return Err(RedError::Other("Metadata was not requested in the job".to_string()));
+            return Err(RedError::FrameMetadataNotRequested);
         }
         let self_ptr = self.ptr;
         let ptr = cpp!(unsafe [self_ptr as "const R3DSDK::R3DDecodeJob *"] -> *const core::ffi::c_void as "void *" {
@@ -509,7 +788,8 @@ impl R3dDecodeJob {
     }
 }
 
-extern "C" fn decode_callback(job: *mut c_void /* R3DDecodeJob * */, decode_status: R3DStatus) {
+extern "C" fn decode_callback(job: *mut c_void /* R3DDecodeJob * */, decode_status: i32) {
+    let decode_status = R3DStatus::from(decode_status);
     if job.is_null() {
         log::error!("Job pointer is null in callback.");
         return;
@@ -522,19 +802,24 @@ extern "C" fn decode_callback(job: *mut c_void /* R3DDecodeJob * */, decode_stat
         return;
     }
 
-    // Safety: `ud` points to the State inside an Arc held by the Future.
-    let state: &mut State<R3dDecodeJob> = unsafe { &mut *(ud as *mut State<R3dDecodeJob>) };
+    // Safety: `ud` is the reference created with Arc::into_raw when the job was submitted.
+    let state: Arc<State<R3dDecodeJob>> = unsafe { Arc::from_raw(ud as *const State<R3dDecodeJob>) };
+    let tracker = state.tracker.clone();
+    let scope = state.enter_span();
 
     // Store the result and signal completion
-    {
-        let mut lock = state.result.lock().unwrap();
-        let org_job = state.job.take().unwrap();
-        if decode_status == R3DStatus::Ok {
-            *lock = Some(Ok(org_job));
-        } else {
-            *lock = Some(Err(RedError::from(decode_status)));
-        }
-    }
-    state.done.store(true, Ordering::Release);
-    state.waker.wake();
+    let org_job = state.job.lock().unwrap().take().unwrap();
+    tracker.detach(org_job.as_mut_ptr());
+    let late = if decode_status == R3DStatus::Ok {
+        state.finish_metrics(Ok::<_, R3DStatus>(org_job.output_buffer_size()));
+        state.complete(Ok(org_job))
+    } else {
+        state.finish_metrics(Err(decode_status));
+        let error = RedError::from(decode_status).with_context(R3dDecodeJob::error_context(Some(&org_job)).status(decode_status.code()));
+        state.complete(Err(DecodeFailure { job: Some(org_job), error }))
+    };
+    drop(scope);
+    // Releasing the job here would call ReleaseDecodeJob inside its own callback. Finishing the job
+    // allows the decoder and then the SDK to be released, so this must be the very last step.
+    tracker.finish(Box::new((state, late)));
 }
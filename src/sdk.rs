@@ -1,47 +1,139 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // Copyright © 2025 Adrian <adrian.eddy at gmail>
 
-use crate::{ RedResult, enums::* };
+use crate::{ RedResult, RedError, SdkVersion, enums::* };
+use std::path::{ Path, PathBuf };
+use std::sync::Mutex;
 use cpp::*;
 
 cpp! {{
     #include "R3DSDK.h"
 }}
 
-pub struct Sdk;
+/// Number of live `Sdk` guards and the components the SDK was initialized with
+static STATE: Mutex<(usize, InitializeFlags)> = Mutex::new((0, InitializeFlags::None));
+
+/// Guard keeping the SDK initialized.
+///
+/// The SDK is initialized by the first [`Sdk::initialize`] call and finalized when the last guard is dropped.
+/// Every [`Clip`](crate::Clip), decoder and GPU debayer wrapper holds its own guard, so the SDK stays loaded while they're alive,
+/// and creating them before the SDK is initialized returns `RedError::SdkNotInitialized`.
+/// Independent components in the same process can each call `Sdk::initialize` and keep their own guard.
+pub struct Sdk {
+    _private: (),
+}
 
 impl Sdk {
+    /// Environment variable with additional directories to search for the RED dynamic libraries,
+    /// separated like `PATH` (`;` on Windows, `:` elsewhere).
+    pub const LIBRARY_PATH_ENV: &'static str = "R3DSDK_LIBRARY_PATH";
+
+    /// File name of the main RED dynamic library, the directory containing it is passed to the SDK.
+    pub const LIBRARY_NAME: &'static str = if cfg!(target_os = "windows") { "REDR3D-x64.dll" }
+                                           else if cfg!(target_os = "macos") { "REDR3D.dylib" }
+                                           else { "REDR3D-x64.so" };
+
     /// This must be called one time before calling any other functions or
     /// constructing any classes. Do not call this for each single thread.
     /// Static initializers must have run before calling this.
-    /// path must point to the folder where the dynamic libraries can be found, it cannot be an empty string.
+    ///
+    /// The RED dynamic libraries are located at runtime, see [`library_search_paths`](Self::library_search_paths).
+    /// `path` is searched first and can be an empty string to use only the other locations.
+    /// Returns `RedError::R3DSDKLibraryNotFound` if the libraries are not present in any of them,
+    /// so the application can run without RED support installed.
+    ///
+    /// If the SDK is already initialized, another guard is returned and `path` is ignored.
+    /// The loaded components can't change until the last guard is dropped, so this fails with `RedError::SdkAlreadyInitialized`
+    /// if `flags` request a component the SDK wasn't initialized with.
     pub fn initialize(path: &str, flags: InitializeFlags) -> RedResult<Self> {
-        let c_path = std::ffi::CString::new(path).unwrap();
+        let mut state = STATE.lock().unwrap();
+        if state.0 > 0 {
+            // Already initialized, the loaded components can't be changed until the SDK is finalized
+            let loaded = state.1 | InitializeFlags::DelayGpuCompile;
+            if !loaded.contains(flags) {
+                return Err(RedError::SdkAlreadyInitialized(state.1));
+            }
+            state.0 += 1;
+            return Ok(Sdk { _private: () });
+        }
+
+        let dir = if cfg!(feature = "mock") {
+            // The mock backend doesn't load any libraries
+            PathBuf::from(if path.is_empty() { "." } else { path })
+        } else {
+            Self::find_library_dir(path)?
+        };
+        log::debug!("Initializing R3D SDK from {}", dir.display());
+
+        let c_path = std::ffi::CString::new(dir.to_str().ok_or(RedError::InvalidPath)?).map_err(|_| RedError::InvalidPath)?;
         unsafe {
             let c_ptr = c_path.as_ptr();
-            let flags = flags.bits();
-            let status: InitializeStatus = std::mem::transmute(cpp!(unsafe [c_ptr as "const char *", flags as "unsigned int"] -> i32 as "int" {
-                return (int)R3DSDK::InitializeSdk(c_ptr, flags);
+            let bits = flags.bits();
+            let status = InitializeStatus::from(cpp!([c_ptr as "const char *", bits as "unsigned int"] -> i32 as "int" {
+                return (int)R3DSDK::InitializeSdk(c_ptr, bits);
             }));
             if status == InitializeStatus::Ok {
-                Ok(Sdk {})
+                *state = (1, flags);
+                for mismatch in SdkVersion::current().mismatches() {
+                    log::warn!("{mismatch}");
+                }
+                Ok(Sdk { _private: () })
             } else {
+                // The version string is only useful while the libraries are still loaded
+                log::error!("R3D SDK initialization failed: {status:?}\n{}", SdkVersion::current());
+                // Same as the SDK samples, release whatever was loaded so initialization can be tried again
+                cpp!(unsafe [] { R3DSDK::FinalizeSdk(); });
                 Err(status.into())
             }
         }
     }
 
+    /// Another guard for the already initialized SDK, or `RedError::SdkNotInitialized` if there are no live guards.
+    pub fn current() -> RedResult<Self> {
+        let mut state = STATE.lock().unwrap();
+        if state.0 == 0 {
+            return Err(RedError::SdkNotInitialized);
+        }
+        state.0 += 1;
+        Ok(Sdk { _private: () })
+    }
+
+    pub fn is_initialized() -> bool {
+        STATE.lock().unwrap().0 > 0
+    }
+
+    /// Components the SDK was initialized with, `None` if it's not initialized.
+    pub fn initialized_flags() -> Option<InitializeFlags> {
+        let state = STATE.lock().unwrap();
+        if state.0 > 0 { Some(state.1) } else { None }
+    }
+
+    /// Directories searched for the RED dynamic libraries, in order:
+    /// 1) `path` passed to [`initialize`](Self::initialize), if not empty
+    /// 2) every entry of the `R3DSDK_LIBRARY_PATH` environment variable
+    /// 3) the directory of the executable (and `../Frameworks` next to it on macOS)
+    pub fn library_search_paths(path: &str) -> Vec<PathBuf> {
+        search_paths(path, std::env::var_os(Self::LIBRARY_PATH_ENV))
+    }
+
+    /// First directory from [`library_search_paths`](Self::library_search_paths) where [`LIBRARY_NAME`](Self::LIBRARY_NAME) exists and can be loaded.
+    /// Returns `R3DSDKLibraryNotFound` if there's none, or `InvalidR3DSDKLibrary` if the library was found but failed to load (wrong architecture, missing dependencies).
+    pub fn find_library_dir(path: &str) -> RedResult<PathBuf> {
+        find_library_in(Self::library_search_paths(path))
+    }
+
     /// Returns version information for the static/API version as well as any dynamic
     /// libraries succesfully loaded. Should be called after InitializedSdk(), but will
     /// return useful information even if initialization failed. If possible log the
     /// output of this function to a log file to help with customer issue debugging.
+    /// See [`SdkVersion::current`] for the parsed version numbers.
     pub fn version() -> String {
         unsafe {
             let ver = cpp!(unsafe [] -> *const core::ffi::c_char as "const char *" {
                 return R3DSDK::GetSdkVersion();
             });
             let c_str = std::ffi::CStr::from_ptr(ver);
-            c_str.to_str().unwrap().to_string()
+            c_str.to_string_lossy().into_owned()
         }
     }
 
@@ -62,9 +154,59 @@ impl Sdk {
     }*/
 }
 
+/// [`Sdk::library_search_paths`] with the value of the `R3DSDK_LIBRARY_PATH` environment variable
+fn search_paths(path: &str, env: Option<std::ffi::OsString>) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if !path.is_empty() {
+        paths.push(PathBuf::from(path));
+    }
+    if let Some(env) = env {
+        paths.extend(std::env::split_paths(&env).filter(|x| !x.as_os_str().is_empty()));
+    }
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|x| x.parent().map(Path::to_path_buf)) {
+        if cfg!(target_os = "macos") {
+            paths.push(exe_dir.join("../Frameworks"));
+        }
+        paths.push(exe_dir);
+    }
+    paths
+}
+
+/// [`Sdk::find_library_dir`] in the given directories
+fn find_library_in(dirs: impl IntoIterator<Item = PathBuf>) -> RedResult<PathBuf> {
+    let mut error = RedError::R3DSDKLibraryNotFound;
+    for dir in dirs {
+        let lib_path = dir.join(Sdk::LIBRARY_NAME);
+        if !lib_path.is_file() {
+            continue;
+        }
+        // Make sure the library is loadable before handing it over to the SDK. It's unloaded right away and the SDK loads it again.
+        match unsafe { libloading::Library::new(&lib_path) } {
+            Ok(_) => return Ok(dir),
+            Err(e) => {
+                log::warn!("Failed to load {}: {e}", lib_path.display());
+                error = RedError::InvalidR3DSDKLibrary;
+            }
+        }
+    }
+    Err(error)
+}
+
+impl Clone for Sdk {
+    fn clone(&self) -> Self {
+        STATE.lock().unwrap().0 += 1;
+        Sdk { _private: () }
+    }
+}
+
 impl Drop for Sdk {
     fn drop(&mut self) {
-        cpp!(unsafe []  { R3DSDK::FinalizeSdk(); })
+        let mut state = STATE.lock().unwrap();
+        state.0 -= 1;
+        if state.0 == 0 {
+            state.1 = InitializeFlags::None;
+            cpp!(unsafe []  { R3DSDK::FinalizeSdk(); })
+        }
     }
 }
 
@@ -133,3 +275,53 @@ bool CreateRWGLog3G10ToOutputTransformLuts(float * output3DLut, int edgeLength,
                                            ImageColorSpace colorSpace, ImageGammaCurve gammaCurve, ToneMap outputToneMap, RollOff highlightRollOff, unsigned int hdrPeakNits);
 
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty directory in the system temp dir, removed on drop
+    struct TempDir(PathBuf);
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("r3d-rs-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn search_order() {
+        let env = std::env::join_paths(["/env/a", "", "/env/b"]).unwrap();
+        let paths = search_paths("/explicit", Some(env));
+        assert_eq!(paths[..3], [PathBuf::from("/explicit"), PathBuf::from("/env/a"), PathBuf::from("/env/b")]);
+        // The executable's directory is searched last
+        let exe_dir = std::env::current_exe().unwrap().parent().unwrap().to_path_buf();
+        assert_eq!(paths.last(), Some(&exe_dir));
+
+        let paths = search_paths("", None);
+        assert_eq!(paths.len(), if cfg!(target_os = "macos") { 2 } else { 1 });
+    }
+
+    #[test]
+    fn library_not_found() {
+        let empty = TempDir::new("empty");
+        assert!(matches!(find_library_in([empty.0.clone(), empty.0.join("missing")]), Err(RedError::R3DSDKLibraryNotFound)));
+    }
+
+    #[test]
+    fn invalid_library() {
+        let (empty, invalid) = (TempDir::new("no-library"), TempDir::new("invalid-library"));
+        std::fs::write(invalid.0.join(Sdk::LIBRARY_NAME), b"not a library").unwrap();
+        assert!(matches!(find_library_in([empty.0.clone(), invalid.0.clone()]), Err(RedError::InvalidR3DSDKLibrary)));
+        // A directory named like the library is skipped
+        std::fs::create_dir(empty.0.join(Sdk::LIBRARY_NAME)).unwrap();
+        assert!(matches!(find_library_in([empty.0.clone()]), Err(RedError::R3DSDKLibraryNotFound)));
+    }
+}
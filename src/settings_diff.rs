@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use std::fmt;
+use crate::{ Clip, ImageProcessingSettings, ImageProcessingLimits as L, RedError, RedResult, enums::* };
+
+/// Value of a single image processing setting, as reported by [`ImageProcessingSettings::diff`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SettingValue {
+    Bool(bool),
+    Integer(u64),
+    Float(f32),
+    /// Enum variant name (e.g. `Log3G10`, or `Unknown(12)` for values unknown to this crate) and its display label.
+    /// The label is taken from `ImageProcessingLimits::*_labels()` if the SDK has labels for the setting, otherwise it's the variant name.
+    Enum { variant: String, label: String },
+    /// User curve, 5 (x, y) points
+    Curve(Vec<f32>),
+    /// 3D LUT path
+    Path(Option<String>),
+}
+
+/// Single setting which differs between two [`ImageProcessingSettings`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SettingChange {
+    /// Name of the setting, same as the getter (e.g. `kelvin`, `cdl_red.slope`)
+    pub field: &'static str,
+    pub old: SettingValue,
+    pub new: SettingValue,
+}
+
+impl From<bool>  for SettingValue { fn from(v: bool)  -> Self { Self::Bool(v) } }
+impl From<u32>   for SettingValue { fn from(v: u32)   -> Self { Self::Integer(v as u64) } }
+impl From<usize> for SettingValue { fn from(v: usize) -> Self { Self::Integer(v as u64) } }
+impl From<f32>   for SettingValue { fn from(v: f32)   -> Self { Self::Float(v) } }
+impl From<&[f32]> for SettingValue { fn from(v: &[f32]) -> Self { Self::Curve(v.to_vec()) } }
+impl From<Option<String>> for SettingValue { fn from(v: Option<String>) -> Self { Self::Path(v) } }
+
+impl SettingValue {
+    fn from_enum<T: Copy + PartialEq + fmt::Debug>(v: RedResult<T>, labels: &[(T, String)]) -> Self {
+        let variant = match &v {
+            Ok(v) => format!("{v:?}"),
+            Err(RedError::InvalidEnumValue { value, .. }) => format!("Unknown({value})"),
+            Err(e) => e.to_string(),
+        };
+        let label = v.ok()
+            .and_then(|v| labels.iter().find(|(x, _)| *x == v))
+            .map_or_else(|| variant.clone(), |(_, label)| label.clone());
+        Self::Enum { variant, label }
+    }
+}
+
+impl fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(v)            => f.write_str(if *v { "on" } else { "off" }),
+            Self::Integer(v)         => write!(f, "{v}"),
+            Self::Float(v)           => write!(f, "{v}"),
+            Self::Enum { label, .. } => f.write_str(label),
+            Self::Curve(v)           => write!(f, "{}", v.chunks(2).map(|p| format!("({}, {})", p[0], p.get(1).unwrap_or(&0.0))).collect::<Vec<_>>().join(" ")),
+            Self::Path(Some(v))      => f.write_str(v),
+            Self::Path(None)         => f.write_str("none"),
+        }
+    }
+}
+
+impl fmt::Display for SettingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.old, self.new)
+    }
+}
+
+impl ImageProcessingSettings {
+    /// List every setting which differs between `self` (old) and `other` (new), in the order of the SDK structure.
+    /// `deb` and `chroma_noise_reduction` share storage in the SDK, the change is reported under the name used by the new color version.
+    /// The 3D LUT is compared by its path.
+    pub fn diff(&self, other: &ImageProcessingSettings) -> Vec<SettingChange> {
+        let mut changes = Vec::new();
+        let mut push = |field: &'static str, old: SettingValue, new: SettingValue| {
+            if old != new {
+                changes.push(SettingChange { field, old, new });
+            }
+        };
+        macro_rules! cmp {
+            ($($field:literal => $get:ident),* $(,)?) => { $( push($field, self.$get().into(), other.$get().into()); )* };
+        }
+        macro_rules! cmp_enum {
+            ($($field:literal => $get:ident, $labels:expr),* $(,)?) => { $( {
+                let labels = $labels;
+                push($field, SettingValue::from_enum(self.$get(), &labels), SettingValue::from_enum(other.$get(), &labels));
+            } )* };
+        }
+        macro_rules! cmp_struct {
+            ($($field:literal => $get:ident . $member:ident),* $(,)?) => { $( push($field, self.$get().$member.into(), other.$get().$member.into()); )* };
+        }
+
+        cmp!("brightness" => brightness, "iso" => iso);
+        cmp_enum!("version" => version, []);
+        cmp!(
+            "kelvin"                => kelvin,
+            "tint"                  => tint,
+            "exposure_compensation" => exposure_compensation,
+            "gain_red"              => gain_red,
+            "gain_green"            => gain_green,
+            "gain_blue"             => gain_blue,
+            "saturation"            => saturation,
+            "contrast"              => contrast,
+            "drx"                   => drx,
+        );
+        cmp_enum!(
+            "gamma_curve" => gamma_curve, L::gamma_curve_options(),
+            "color_space" => color_space, L::color_space_options(),
+        );
+        cmp!(
+            "custom_pdlog_black_point" => custom_pdlog_black_point,
+            "custom_pdlog_white_point" => custom_pdlog_white_point,
+            "custom_pdlog_gamma"       => custom_pdlog_gamma,
+            "user_curve"               => user_curve,
+            "user_curve_red"           => user_curve_red,
+            "user_curve_green"         => user_curve_green,
+            "user_curve_blue"          => user_curve_blue,
+        );
+        cmp_enum!(
+            "detail"            => detail,            [],
+            "olpf_compensation" => olpf_compensation, [],
+            "denoise"           => denoise,           [],
+        );
+        let deb = if matches!(other.version(), Ok(ColorVersion::Version1 | ColorVersion::Version2)) { "deb" } else { "chroma_noise_reduction" };
+        push(deb, self.deb().into(), other.deb().into());
+        cmp_enum!("flashing_pixel_adjustment" => flashing_pixel_adjustment, []);
+        cmp!("shadow" => shadow, "flut" => flut);
+        cmp_struct!(
+            "lgg_red.lift"   => lgg_red.lift,   "lgg_red.gamma"   => lgg_red.gamma,   "lgg_red.gain"   => lgg_red.gain,
+            "lgg_green.lift" => lgg_green.lift, "lgg_green.gamma" => lgg_green.gamma, "lgg_green.gain" => lgg_green.gain,
+            "lgg_blue.lift"  => lgg_blue.lift,  "lgg_blue.gamma"  => lgg_blue.gamma,  "lgg_blue.gain"  => lgg_blue.gain,
+        );
+        cmp_enum!("image_pipeline_mode" => image_pipeline_mode, L::image_pipeline_mode_options());
+        cmp!("exposure_adjust" => exposure_adjust, "cdl_saturation" => cdl_saturation);
+        cmp_struct!(
+            "cdl_red.slope"   => cdl_red.slope,   "cdl_red.offset"   => cdl_red.offset,   "cdl_red.power"   => cdl_red.power,
+            "cdl_green.slope" => cdl_green.slope, "cdl_green.offset" => cdl_green.offset, "cdl_green.power" => cdl_green.power,
+            "cdl_blue.slope"  => cdl_blue.slope,  "cdl_blue.offset"  => cdl_blue.offset,  "cdl_blue.power"  => cdl_blue.power,
+        );
+        cmp!("cdl_enabled" => cdl_enabled, "lut3d_path" => lut3d_path, "lut3d_enabled" => lut3d_enabled);
+        cmp_enum!(
+            "output_tone_map"    => output_tone_map,    L::output_tone_map_options(),
+            "highlight_roll_off" => highlight_roll_off, L::highlight_roll_off_options(),
+        );
+        cmp!("hdr_peak_nits" => hdr_peak_nits, "maskline_adjust" => maskline_adjust);
+
+        changes
+    }
+
+    /// List every setting changed relative to the clip defaults ([`Clip::default_image_processing_settings`], i.e. the camera look or the RMD sidecar).
+    pub fn diff_from_clip_default(&self, clip: &Clip) -> Vec<SettingChange> {
+        clip.default_image_processing_settings().diff(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(v: ImageGammaCurve) -> String {
+        L::gamma_curve_options().into_iter().find(|(x, _)| *x == v).unwrap().1
+    }
+
+    #[test]
+    fn diff_values() {
+        let old = ImageProcessingSettings::default();
+        assert!(old.diff(&old.clone()).is_empty());
+
+        let mut new = old.clone();
+        new.set_kelvin(3200.0);
+        new.set_gamma_curve(ImageGammaCurve::HDR2084);
+        new.set_detail(ImageDetail::Low);
+        let changes = old.diff(&new);
+        assert_eq!(changes.iter().map(|x| x.field).collect::<Vec<_>>(), ["kelvin", "gamma_curve", "detail"]);
+
+        assert_eq!(changes[0].old, SettingValue::Float(old.kelvin()));
+        assert_eq!(changes[0].new, SettingValue::Float(3200.0));
+        assert_eq!(changes[0].to_string(), format!("kelvin: {} -> 3200", old.kelvin()));
+
+        // Labels come from the SDK, settings without labels use the variant name
+        let gamma_curve = old.gamma_curve().unwrap();
+        assert_eq!(changes[1].old, SettingValue::Enum { variant: format!("{gamma_curve:?}"), label: label(gamma_curve) });
+        assert_eq!(changes[1].new, SettingValue::Enum { variant: "HDR2084".into(), label: label(ImageGammaCurve::HDR2084) });
+        assert_eq!(changes[2].new, SettingValue::Enum { variant: "Low".into(), label: "Low".into() });
+    }
+
+    #[test]
+    fn diff_deb_and_chroma_noise_reduction() {
+        let mut old = ImageProcessingSettings::default();
+        old.set_version(ColorVersion::Version2);
+        let mut new = old.clone();
+        new.set_deb(true);
+        let changes = old.diff(&new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].field, &changes[0].new), ("deb", &SettingValue::Bool(true)));
+
+        // Named after the color version of the new settings
+        new.set_version(ColorVersion::Version3);
+        let changes = old.diff(&new);
+        assert_eq!(changes.iter().map(|x| x.field).collect::<Vec<_>>(), ["version", "chroma_noise_reduction"]);
+        assert_eq!(changes[0].new, SettingValue::Enum { variant: "Version3".into(), label: "Version3".into() });
+    }
+}
@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use crate::{ ImageProcessingLimits, ImageProcessingLimits as L, ImageProcessingSettings, UserCurve, enums::* };
+
+/// Value type, range and default of a control in [`ControlSchema`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum ControlType {
+    /// Inclusive range
+    Float { min: f32, max: f32, default: f32 },
+    /// Inclusive range
+    Integer { min: u64, max: u64, default: u64 },
+    /// One of the listed values, e.g. the ISO
+    IntegerList { values: Vec<u64>, default: u64 },
+    Bool { default: bool },
+    /// `default` is the variant name, `None` if the SDK default is unknown to this crate
+    Enum { options: Vec<EnumOption>, default: Option<String> },
+    /// 5-point curve, see [`UserCurve`]
+    Curve { default: UserCurve },
+}
+
+/// Option of an enum control
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EnumOption {
+    /// Raw SDK value
+    pub value: i32,
+    /// Variant name, e.g. `Log3G10`
+    pub variant: String,
+    /// Label from `ImageProcessingLimits::*_options()`, or the variant name if the SDK has no labels for the setting
+    pub label: String,
+    /// Color versions the option can be used with, e.g. the legacy gamma curves are not available with ColorVersion3
+    pub color_versions: Vec<ColorVersion>,
+}
+
+/// Description of a single image processing control, see [`ImageProcessingLimits::schema`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ControlSchema {
+    /// Name of the setting, same as the getter and [`SettingChange::field`](crate::SettingChange::field) (e.g. `kelvin`, `cdl_red.slope`)
+    pub name: &'static str,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub control: ControlType,
+    /// Color versions which use the setting, it's ignored with the other ones
+    pub color_versions: Vec<ColorVersion>,
+    /// Image pipeline modes which use the setting with ColorVersion3, empty if the setting is not used with ColorVersion3
+    pub pipelines: Vec<ImagePipeline>,
+}
+
+const ALL: &[ColorVersion] = &[ColorVersion::Version1, ColorVersion::Version2, ColorVersion::Version3, ColorVersion::VersionBC];
+const LEGACY: &[ColorVersion] = &[ColorVersion::Version1, ColorVersion::Version2];
+const NOT_BC: &[ColorVersion] = &[ColorVersion::Version1, ColorVersion::Version2, ColorVersion::Version3];
+const IPP2: &[ColorVersion] = &[ColorVersion::Version3];
+const IPP2_BC: &[ColorVersion] = &[ColorVersion::Version3, ColorVersion::VersionBC];
+
+fn float(min: f32, max: f32, default: f32) -> ControlType {
+    ControlType::Float { min, max, default }
+}
+fn integer(min: impl Into<u64>, max: impl Into<u64>, default: impl Into<u64>) -> ControlType {
+    ControlType::Integer { min: min.into(), max: max.into(), default: default.into() }
+}
+
+impl ImageProcessingLimits {
+    /// Description of every control of [`ImageProcessingSettings`] in the order of the SDK structure: name, type, range, default,
+    /// enum options with labels and which color versions and image pipelines use it. With the `serde` feature it can be serialized,
+    /// e.g. to JSON for generating a grading UI.
+    ///
+    /// Ranges and defaults come from the `ImageProcessingLimits` functions; settings without SDK limits (the enums
+    /// without labels, the user curves and the toggles) use the defaults of `ImageProcessingSettings::default()`.
+    /// The 3D LUT path is not included.
+    pub fn schema() -> Vec<ControlSchema> {
+        let defaults = ImageProcessingSettings::default();
+        let mut out = Vec::new();
+        // `primary_development`: also used with `ImagePipeline::PrimaryDevelopmentOnly`, which only keeps the primary development controls
+        let mut push = |name: &'static str, control: ControlType, color_versions: &[ColorVersion], primary_development: bool| {
+            let pipelines = match (color_versions.contains(&ColorVersion::Version3), primary_development) {
+                (false, _)    => vec![],
+                (true, true)  => vec![ImagePipeline::PrimaryDevelopmentOnly, ImagePipeline::FullGraded],
+                (true, false) => vec![ImagePipeline::FullGraded],
+            };
+            out.push(ControlSchema { name, control, color_versions: color_versions.to_vec(), pipelines });
+        };
+        macro_rules! options {
+            ($list:expr, $default:expr, $versions:expr) => {
+                ControlType::Enum {
+                    options: $list.into_iter().map(|(v, label)| EnumOption { value: v as i32, variant: format!("{v:?}"), label, color_versions: $versions(v) }).collect(),
+                    default: $default.ok().map(|v| format!("{v:?}")),
+                }
+            };
+        }
+        macro_rules! variants {
+            ($($v:expr),*) => { vec![$(($v, format!("{:?}", $v))),*] };
+        }
+
+        push("brightness", float(L::brightness_min(), L::brightness_max(), L::brightness_default()), NOT_BC, false);
+        push("iso", ControlType::IntegerList { values: L::iso_list().into_iter().map(|x| x as u64).collect(), default: L::iso_default() as u64 }, ALL, true);
+        push("version", options!(variants!(ColorVersion::Version1, ColorVersion::Version2, ColorVersion::Version3, ColorVersion::VersionBC), defaults.version(), |_| ALL.to_vec()), ALL, true);
+        push("kelvin", float(L::kelvin_min(), L::kelvin_max(), L::kelvin_default()), ALL, true);
+        push("tint", float(L::tint_min(), L::tint_max(), L::tint_default()), ALL, true);
+        push("exposure_compensation", float(L::exposure_min(), L::exposure_max(), L::exposure_default()), ALL, false);
+        for name in ["gain_red", "gain_green", "gain_blue"] {
+            push(name, float(L::gains_min(), L::gains_max(), L::gains_default()), LEGACY, false);
+        }
+        push("saturation", float(L::saturation_min(), L::saturation_max(), L::saturation_default()), LEGACY, false);
+        push("contrast", float(L::contrast_min(), L::contrast_max(), L::contrast_default()), LEGACY, false);
+        push("drx", float(L::drx_min(), L::drx_max(), L::drx_default()), LEGACY, false);
+        push("gamma_curve", options!(L::gamma_curve_options(), L::gamma_curve_default(), |v: ImageGammaCurve| ALL.iter().copied().filter(|x| v.is_supported_by(*x)).collect()), ALL, false);
+        push("color_space", options!(L::color_space_options(), L::color_space_default(), |v: ImageColorSpace| ALL.iter().copied().filter(|x| v.is_supported_by(*x)).collect()), ALL, false);
+        push("custom_pdlog_black_point", integer(L::custom_pdlog_black_point_min() as u64, L::custom_pdlog_black_point_max() as u64, L::custom_pdlog_black_point_default() as u64), LEGACY, false);
+        push("custom_pdlog_white_point", integer(L::custom_pdlog_white_point_min() as u64, L::custom_pdlog_white_point_max() as u64, L::custom_pdlog_white_point_default() as u64), LEGACY, false);
+        push("custom_pdlog_gamma", float(L::custom_pdlog_gamma_min(), L::custom_pdlog_gamma_max(), L::custom_pdlog_gamma_default()), LEGACY, false);
+        push("user_curve",       ControlType::Curve { default: defaults.user_curve_points() },       ALL,    false);
+        push("user_curve_red",   ControlType::Curve { default: defaults.user_curve_red_points() },   NOT_BC, false);
+        push("user_curve_green", ControlType::Curve { default: defaults.user_curve_green_points() }, NOT_BC, false);
+        push("user_curve_blue",  ControlType::Curve { default: defaults.user_curve_blue_points() },  NOT_BC, false);
+        push("detail", options!(variants!(ImageDetail::Low, ImageDetail::Medium, ImageDetail::High), defaults.detail(), |_| LEGACY.to_vec()), LEGACY, false);
+        push("olpf_compensation", options!(variants!(ImageOLPFCompensation::Off, ImageOLPFCompensation::Low, ImageOLPFCompensation::Medium, ImageOLPFCompensation::High),
+            defaults.olpf_compensation(), |_| LEGACY.to_vec()), LEGACY, false);
+        push("denoise", options!(variants!(ImageDenoise::Off, ImageDenoise::Minimum, ImageDenoise::Milder, ImageDenoise::Mild, ImageDenoise::Medium, ImageDenoise::Strong, ImageDenoise::Maximum),
+            defaults.denoise(), |_| LEGACY.to_vec()), LEGACY, false);
+        // Same storage in the SDK, the name depends on the color version
+        push("deb", ControlType::Bool { default: defaults.deb() }, LEGACY, false);
+        push("chroma_noise_reduction", ControlType::Bool { default: defaults.chroma_noise_reduction() }, IPP2_BC, true);
+        push("flashing_pixel_adjustment", options!(variants!(FlashingPixelAdjust::Off, FlashingPixelAdjust::Mild, FlashingPixelAdjust::Medium, FlashingPixelAdjust::Strong),
+            defaults.flashing_pixel_adjustment(), |_| ALL.to_vec()), ALL, true);
+        push("shadow", float(L::shadow_min(), L::shadow_max(), L::shadow_default()), &[ColorVersion::Version2], false);
+        push("flut", float(L::flut_min(), L::flut_max(), L::flut_default()), &[ColorVersion::Version2], false);
+        for names in [["lgg_red.lift", "lgg_red.gamma", "lgg_red.gain"], ["lgg_green.lift", "lgg_green.gamma", "lgg_green.gain"], ["lgg_blue.lift", "lgg_blue.gamma", "lgg_blue.gain"]] {
+            push(names[0], float(L::lgg_lift_min(),  L::lgg_lift_max(),  L::lgg_lift_default()),  LEGACY, false);
+            push(names[1], float(L::lgg_gamma_min(), L::lgg_gamma_max(), L::lgg_gamma_default()), LEGACY, false);
+            push(names[2], float(L::lgg_gain_min(),  L::lgg_gain_max(),  L::lgg_gain_default()),  LEGACY, false);
+        }
+        push("image_pipeline_mode", options!(L::image_pipeline_mode_options(), L::image_pipeline_mode_default(), |_| IPP2.to_vec()), IPP2, true);
+        push("exposure_adjust", float(L::exposure_adjust_min(), L::exposure_adjust_max(), L::exposure_adjust_default()), IPP2_BC, true);
+        push("cdl_saturation", float(L::cdl_saturation_min(), L::cdl_saturation_max(), L::cdl_saturation_default()), IPP2, false);
+        for names in [["cdl_red.slope", "cdl_red.offset", "cdl_red.power"], ["cdl_green.slope", "cdl_green.offset", "cdl_green.power"], ["cdl_blue.slope", "cdl_blue.offset", "cdl_blue.power"]] {
+            push(names[0], float(L::cdl_slope_min(),  L::cdl_slope_max(),  L::cdl_slope_default()),  IPP2, false);
+            push(names[1], float(L::cdl_offset_min(), L::cdl_offset_max(), L::cdl_offset_default()), IPP2, false);
+            push(names[2], float(L::cdl_power_min(),  L::cdl_power_max(),  L::cdl_power_default()),  IPP2, false);
+        }
+        push("cdl_enabled", ControlType::Bool { default: defaults.cdl_enabled() }, IPP2, false);
+        push("lut3d_enabled", ControlType::Bool { default: defaults.lut3d_enabled() }, IPP2, false);
+        push("output_tone_map", options!(L::output_tone_map_options(), L::output_tone_map_default(), |_| IPP2.to_vec()), IPP2, false);
+        push("highlight_roll_off", options!(L::highlight_roll_off_options(), L::highlight_roll_off_default(), |_| IPP2.to_vec()), IPP2, false);
+        push("hdr_peak_nits", integer(L::hdr_peak_nits_min(), L::hdr_peak_nits_max(), L::hdr_peak_nits_default()), IPP2, false);
+        push("maskline_adjust", ControlType::Bool { default: defaults.maskline_adjust() }, ALL, true);
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    // Every setting changed from the defaults
+    fn changed(version: ColorVersion) -> ImageProcessingSettings {
+        let mut s = ImageProcessingSettings::default();
+        s.set_brightness(0.5);
+        s.set_iso(1600);
+        s.set_version(version);
+        s.set_kelvin(3200.0);
+        s.set_tint(10.0);
+        s.set_exposure_compensation(1.0);
+        s.set_gain_red(1.5);
+        s.set_gain_green(1.5);
+        s.set_gain_blue(1.5);
+        s.set_saturation(1.5);
+        s.set_contrast(0.5);
+        s.set_drx(0.5);
+        s.set_gamma_curve(ImageGammaCurve::Log3G10);
+        s.set_color_space(ImageColorSpace::REDWideGamutRGB);
+        s.set_custom_pdlog_black_point(10);
+        s.set_custom_pdlog_white_point(1000);
+        s.set_custom_pdlog_gamma(1.5);
+        s.user_curve_mut()[5] = 0.6;
+        s.user_curve_red_mut()[5] = 0.6;
+        s.user_curve_green_mut()[5] = 0.6;
+        s.user_curve_blue_mut()[5] = 0.6;
+        s.set_detail(ImageDetail::Low);
+        s.set_olpf_compensation(ImageOLPFCompensation::High);
+        s.set_denoise(ImageDenoise::Maximum);
+        s.set_deb(true);
+        s.set_flashing_pixel_adjustment(FlashingPixelAdjust::Strong);
+        s.set_shadow(0.1);
+        s.set_flut(1.0);
+        let lgg = LiftGammaGain { lift: 0.1, gamma: 1.5, gain: 1.5 };
+        (*s.lgg_red_mut(), *s.lgg_green_mut(), *s.lgg_blue_mut()) = (lgg, lgg, lgg);
+        s.set_image_pipeline_mode(ImagePipeline::PrimaryDevelopmentOnly);
+        s.set_exposure_adjust(0.5);
+        s.set_cdl_saturation(1.5);
+        let cdl = SlopeOffsetPower { slope: 1.5, offset: 0.1, power: 1.5 };
+        (*s.cdl_red_mut(), *s.cdl_green_mut(), *s.cdl_blue_mut()) = (cdl, cdl, cdl);
+        s.set_cdl_enabled(true);
+        s.set_lut3d_enabled(true);
+        s.set_output_tone_map(ToneMap::High);
+        s.set_highlight_roll_off(RollOff::Hard);
+        s.set_hdr_peak_nits(4000);
+        s.set_maskline_adjust(true);
+        s
+    }
+
+    #[test]
+    fn schema_names_match_diff() {
+        let defaults = ImageProcessingSettings::default();
+        let mut fields: BTreeSet<&str> = defaults.diff(&changed(ColorVersion::Version2)).into_iter().map(|x| x.field).collect();
+        fields.extend(defaults.diff(&changed(ColorVersion::Version3)).into_iter().map(|x| x.field));
+        let schema = ImageProcessingLimits::schema();
+        let names: BTreeSet<&str> = schema.iter().map(|x| x.name).collect();
+        assert_eq!(names.len(), schema.len());
+        assert_eq!(names, fields);
+    }
+
+    #[test]
+    fn gamma_curve_color_versions() {
+        let schema = ImageProcessingLimits::schema();
+        let Some(ControlType::Enum { options, default }) = schema.iter().find(|x| x.name == "gamma_curve").map(|x| &x.control) else { panic!("gamma_curve is not an enum") };
+        assert_eq!(default.as_deref(), Some(format!("{:?}", L::gamma_curve_default().unwrap()).as_str()));
+        let versions = |v: ImageGammaCurve| &options.iter().find(|x| x.value == v as i32).unwrap().color_versions;
+        use ColorVersion::*;
+        assert_eq!(versions(ImageGammaCurve::REDgamma4), &[Version1, Version2]);
+        assert_eq!(versions(ImageGammaCurve::REDlogFilm), &[Version2, Version3]);
+        assert_eq!(versions(ImageGammaCurve::Log3G10), &[Version1, Version2, Version3]);
+        assert_eq!(versions(ImageGammaCurve::HybridLogGamma), &[Version3, VersionBC]);
+        assert_eq!(versions(ImageGammaCurve::BT1886), &[Version1, Version2, Version3, VersionBC]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_control() {
+        let schema = ImageProcessingLimits::schema();
+        let control = schema.iter().find(|x| x.name == "image_pipeline_mode").unwrap();
+        assert_eq!(serde_json::to_string(control).unwrap(), concat!(
+            r#"{"name":"image_pipeline_mode","type":"enum","options":["#,
+            r#"{"value":1,"variant":"FullGraded","label":"Full Graded Pipeline","color_versions":["Version3"]},"#,
+            r#"{"value":0,"variant":"PrimaryDevelopmentOnly","label":"Primary Development Only","color_versions":["Version3"]}],"#,
+            r#""default":"FullGraded","color_versions":["Version3"],"pipelines":["PrimaryDevelopmentOnly","FullGraded"]}"#
+        ));
+    }
+}
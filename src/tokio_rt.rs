@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Tokio integration, enabled with the `tokio` feature.
+//
+// The synchronous SDK calls (opening a clip, software decoding, CRC checks, IMU decoding) block for a long
+// time, so these wrappers run them with `tokio::task::spawn_blocking` instead of on the async worker threads.
+// The futures returned by the GPU decoders (`CallbackFuture`) don't block and can be awaited directly.
+
+use crate::{ RedResult, RedError, DecodeResult, Clip, SharedClip, AlignedBuffer, ImageProcessingSettings, HdrProcessingSettings, IMUSample };
+use crate::{ R3dDecoder, R3dDecodeJob, AsyncDecoder, GpuDecoder, AsyncDecompressJob };
+use crate::enums::*;
+use std::sync::Arc;
+use tokio::sync::{ Semaphore, OwnedSemaphorePermit };
+
+async fn spawn_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> RedResult<T> {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(v) => Ok(v),
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => Err(RedError::Cancelled),
+    }
+}
+
+impl SharedClip {
+    /// Load the clip on the blocking thread pool, see [`Clip::from_path`].
+    pub async fn from_path_async(path: &str) -> RedResult<Self> {
+        let path = path.to_owned();
+        spawn_blocking(move || Clip::from_path(&path)).await?.map(Self::new)
+    }
+
+    /// Decode the given video frame on the blocking thread pool, see [`Clip::decode_video_frame`].
+    /// The output buffer is always allocated internally.
+    pub async fn decode_video_frame_async(
+        &self,
+        video_frame_no: usize,
+        mode: VideoDecodeMode,
+        pixel_type: VideoPixelType,
+        image_settings: Option<&ImageProcessingSettings>,
+        hdr_settings: Option<&HdrProcessingSettings>,
+    ) -> RedResult<AlignedBuffer> {
+        let clip = self.clone();
+        let image_settings = image_settings.cloned();
+        let hdr_settings = hdr_settings.cloned();
+        spawn_blocking(move || {
+            clip.decode_video_frame(video_frame_no, mode, pixel_type, image_settings.as_ref(), hdr_settings.as_ref(), None)
+                .and_then(|buf| buf.ok_or(RedError::OutputBufferInvalid))
+        }).await?
+    }
+
+    /// Check the frame CRC on the blocking thread pool, see [`Clip::check_frame`].
+    pub async fn check_frame_async(&self, video_frame_no: usize) -> RedResult<()> {
+        let clip = self.clone();
+        spawn_blocking(move || clip.check_frame(video_frame_no)).await?
+    }
+
+    /// Decode IMU samples on the blocking thread pool, see [`Clip::imu_samples`].
+    pub async fn imu_samples_async<T: std::ops::RangeBounds<u64> + Send + 'static>(&self, range: T) -> RedResult<Vec<IMUSample>> {
+        let clip = self.clone();
+        spawn_blocking(move || clip.imu_samples(range)).await
+    }
+}
+
+/// Limits the number of concurrent decodes using a `tokio::sync::Semaphore`.
+///
+/// The limiter is cheap to clone, all clones share the same limit. Tasks wait asynchronously
+/// for a free slot before the decode is started, so no worker thread is blocked while waiting.
+#[derive(Clone)]
+pub struct DecodeLimiter {
+    semaphore: Arc<Semaphore>,
+}
+
+impl DecodeLimiter {
+    pub fn new(max_concurrent_decodes: usize) -> Self {
+        Self { semaphore: Arc::new(Semaphore::new(max_concurrent_decodes)) }
+    }
+
+    /// Number of decodes that can be started right now.
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    /// Wait for a free slot. The slot is released when the returned permit is dropped.
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.semaphore.clone().acquire_owned().await.expect("semaphore is never closed")
+    }
+
+    /// Run `f` once a slot is available and keep the slot until the returned future completes.
+    pub async fn run<T, F: Future<Output = T>>(&self, f: impl FnOnce() -> F) -> T {
+        let _permit = self.acquire().await;
+        f().await
+    }
+
+    /// Synchronous software decode, see [`SharedClip::decode_video_frame_async`].
+    pub async fn decode_video_frame(
+        &self,
+        clip: &SharedClip,
+        video_frame_no: usize,
+        mode: VideoDecodeMode,
+        pixel_type: VideoPixelType,
+        image_settings: Option<&ImageProcessingSettings>,
+        hdr_settings: Option<&HdrProcessingSettings>,
+    ) -> RedResult<AlignedBuffer> {
+        let _permit = self.acquire().await;
+        clip.decode_video_frame_async(video_frame_no, mode, pixel_type, image_settings, hdr_settings).await
+    }
+
+    /// Submit the job to the decoder once a slot is available, see [`R3dDecoder::decode`].
+    pub async fn decode(&self, decoder: &R3dDecoder, job: R3dDecodeJob) -> DecodeResult<R3dDecodeJob> {
+        let _permit = self.acquire().await;
+        decoder.decode(job)?.await
+    }
+
+    /// Submit the job to the decoder once a slot is available, see [`AsyncDecoder::decode_for_gpu_sdk`].
+    pub async fn decode_for_gpu_sdk(&self, decoder: &AsyncDecoder, job: AsyncDecompressJob) -> DecodeResult<AsyncDecompressJob> {
+        let _permit = self.acquire().await;
+        decoder.decode_for_gpu_sdk(job)?.await
+    }
+
+    /// Submit the job to the decoder once a slot is available, see [`GpuDecoder::decode_for_gpu_sdk`].
+    pub async fn gpu_decode_for_gpu_sdk(&self, decoder: &GpuDecoder, job: AsyncDecompressJob) -> DecodeResult<AsyncDecompressJob> {
+        let _permit = self.acquire().await;
+        decoder.decode_for_gpu_sdk(job)?.await
+    }
+}
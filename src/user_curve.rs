@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use crate::{ ImageProcessingLimits as L, RangeError, AllowedValues };
+
+/// Control point of a [`UserCurve`], both coordinates in 0 -- 1
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurvePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// 5-point user curve, stored by the SDK as 10 floats (`UserCurve`, `UserCurveRed`, `UserCurveGreen` and `UserCurveBlue`).
+///
+/// The X coordinates must be strictly increasing (black.x < low.x < mid.x < high.x < white.x), the SDK ignores curves which aren't.
+/// Use [`validate`](Self::validate) or the `ImageProcessingSettings::try_set_user_curve*` setters to catch them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserCurve {
+    pub black: CurvePoint,
+    pub low: CurvePoint,
+    pub mid: CurvePoint,
+    pub high: CurvePoint,
+    pub white: CurvePoint,
+}
+
+impl Default for UserCurve {
+    /// Straight line, (0, 0) (0.25, 0.25) (0.5, 0.5) (0.75, 0.75) (1, 1)
+    fn default() -> Self {
+        Self::from([0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1.0, 1.0])
+    }
+}
+
+impl From<[f32; 10]> for UserCurve {
+    fn from(v: [f32; 10]) -> Self {
+        let p = |i: usize| CurvePoint { x: v[i * 2], y: v[i * 2 + 1] };
+        Self { black: p(0), low: p(1), mid: p(2), high: p(3), white: p(4) }
+    }
+}
+impl From<UserCurve> for [f32; 10] {
+    fn from(c: UserCurve) -> Self {
+        let [a, b, c, d, e] = c.points();
+        [a.x, a.y, b.x, b.y, c.x, c.y, d.x, d.y, e.x, e.y]
+    }
+}
+
+/// Setting names of the 10 values of a curve for [`RangeError`], e.g. `user_curve_red.mid.x`
+macro_rules! curve_fields {
+    ($name:literal) => {
+        [
+            concat!($name, ".black.x"), concat!($name, ".black.y"),
+            concat!($name, ".low.x"),   concat!($name, ".low.y"),
+            concat!($name, ".mid.x"),   concat!($name, ".mid.y"),
+            concat!($name, ".high.x"),  concat!($name, ".high.y"),
+            concat!($name, ".white.x"), concat!($name, ".white.y"),
+        ]
+    };
+}
+pub(crate) use curve_fields;
+
+impl UserCurve {
+    /// Control points from black to white
+    pub fn points(&self) -> [CurvePoint; 5] {
+        [self.black, self.low, self.mid, self.high, self.white]
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.points().iter().all(|p| p.x == p.y)
+    }
+
+    /// Check that all coordinates are in 0 -- 1 and the X coordinates are strictly increasing.
+    /// An X coordinate which isn't is reported with the range between its neighbours.
+    pub fn validate(&self) -> Result<(), RangeError> {
+        check_user_curve(self, curve_fields!("user_curve"))
+    }
+
+    /// Y of the curve at `x`, for drawing the curve in a UI.
+    ///
+    /// Natural cubic spline through the five points, flat outside black.x -- white.x and clamped to 0 -- 1.
+    /// The SDK doesn't document how it interpolates the curve, so this is an approximation of what it applies.
+    /// The curve must be [valid](Self::validate).
+    pub fn evaluate(&self, x: f32) -> f32 {
+        let p = self.points();
+        if x <= p[0].x { return p[0].y; }
+        if x >= p[4].x { return p[4].y; }
+
+        // Second derivatives at the points, zero at both ends, tridiagonal system solved with the Thomas algorithm
+        let h: [f32; 4] = std::array::from_fn(|i| p[i + 1].x - p[i].x);
+        let slope: [f32; 4] = std::array::from_fn(|i| (p[i + 1].y - p[i].y) / h[i]);
+        let mut m = [0.0f32; 5];
+        let mut diag = [0.0f32; 5];
+        let mut rhs = [0.0f32; 5];
+        for i in 1..4 {
+            diag[i] = 2.0 * (h[i - 1] + h[i]);
+            rhs[i] = 6.0 * (slope[i] - slope[i - 1]);
+            if i > 1 {
+                let w = h[i - 1] / diag[i - 1];
+                diag[i] -= w * h[i - 1];
+                rhs[i] -= w * rhs[i - 1];
+            }
+        }
+        for i in (1..4).rev() {
+            m[i] = (rhs[i] - h[i] * m[i + 1]) / diag[i];
+        }
+
+        let i = (0..4).rfind(|&i| x >= p[i].x).unwrap_or(0);
+        let (a, b) = ((p[i + 1].x - x) / h[i], (x - p[i].x) / h[i]);
+        let y = a * p[i].y + b * p[i + 1].y + ((a * a * a - a) * m[i] + (b * b * b - b) * m[i + 1]) * h[i] * h[i] / 6.0;
+        y.clamp(0.0, 1.0)
+    }
+}
+
+pub(crate) fn check_user_curve(v: &UserCurve, fields: [&'static str; 10]) -> Result<(), RangeError> {
+    let p = v.points();
+    for (i, point) in p.iter().enumerate() {
+        L::check_range(fields[i * 2],     point.x, 0.0, 1.0)?;
+        L::check_range(fields[i * 2 + 1], point.y, 0.0, 1.0)?;
+    }
+    for i in 1..5 {
+        if p[i].x <= p[i - 1].x {
+            let max = p.get(i + 1).map_or(1.0, |next| next.x);
+            return Err(RangeError { field: fields[i * 2], value: p[i].x, allowed: AllowedValues::Range { min: p[i - 1].x, max } });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(x: [f32; 5], y: [f32; 5]) -> UserCurve {
+        UserCurve::from([x[0], y[0], x[1], y[1], x[2], y[2], x[3], y[3], x[4], y[4]])
+    }
+
+    #[test]
+    fn validate() {
+        assert!(UserCurve::default().validate().is_ok());
+        assert!(UserCurve::default().is_identity());
+
+        let e = curve([0.0, 0.25, 1.5, 0.75, 1.0], [0.0, 0.25, 0.5, 0.75, 1.0]).validate().unwrap_err();
+        assert_eq!((e.field, e.value), ("user_curve.mid.x", 1.5));
+        let e = curve([0.0, 0.25, 0.5, 0.75, 1.0], [0.0, 0.25, -0.1, 0.75, 1.0]).validate().unwrap_err();
+        assert_eq!(e.field, "user_curve.mid.y");
+
+        // Not increasing: the allowed range is between the neighbours
+        let e = curve([0.0, 0.5, 0.4, 0.75, 1.0], [0.0, 0.25, 0.5, 0.75, 1.0]).validate().unwrap_err();
+        assert_eq!(e.field, "user_curve.mid.x");
+        assert_eq!(e.allowed, AllowedValues::Range { min: 0.5, max: 0.75 });
+        let e = curve([0.0, 0.25, 0.5, 0.75, 0.75], [0.0, 0.25, 0.5, 0.75, 1.0]).validate().unwrap_err();
+        assert_eq!(e.field, "user_curve.white.x");
+        assert_eq!(e.allowed, AllowedValues::Range { min: 0.75, max: 1.0 });
+    }
+
+    #[test]
+    fn evaluate_identity() {
+        let c = UserCurve::default();
+        for i in 0..=100 {
+            let x = i as f32 / 100.0;
+            assert!((c.evaluate(x) - x).abs() < 1e-6, "{x}");
+        }
+    }
+
+    #[test]
+    fn evaluate_points_and_clamping() {
+        let c = curve([0.1, 0.25, 0.5, 0.75, 0.9], [0.05, 0.3, 0.6, 0.95, 1.0]);
+        for p in c.points() {
+            assert!((c.evaluate(p.x) - p.y).abs() < 1e-6, "{p:?}");
+        }
+        // Flat outside the end points
+        assert_eq!(c.evaluate(0.0), 0.05);
+        assert_eq!(c.evaluate(0.95), 1.0);
+
+        // The spline undershoots between low and mid and overshoots between high and white, the result is clamped
+        let c = curve([0.0, 0.25, 0.5, 0.75, 1.0], [0.0, 0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(c.evaluate(0.375), 0.0);
+        assert_eq!(c.evaluate(0.875), 1.0);
+        assert!((0..=100).map(|i| c.evaluate(i as f32 / 100.0)).all(|y| (0.0..=1.0).contains(&y)));
+    }
+}
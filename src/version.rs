@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+use crate::{ RedResult, RedError, Sdk };
+
+/// major.minor.patch version number
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parse `9.1.0`, `v9.1.0` or `9.1`. Anything after the digits of the patch number (build number, `b`, `-beta` etc.) is ignored.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.strip_prefix(['v', 'V']).unwrap_or(s).trim_end_matches('.');
+        let mut parts = s.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = match parts.next() {
+            Some(x) => {
+                let digits = x.find(|c: char| !c.is_ascii_digit()).unwrap_or(x.len());
+                x[..digits].parse().ok()?
+            }
+            None => 0,
+        };
+        Some(Self { major, minor, patch })
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// One of the RED dynamic libraries loaded by `Sdk::initialize`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SdkLibrary {
+    /// REDR3D, the main decoding library
+    R3DSDK,
+    RedCuda,
+    RedOpenCL,
+    RedMetal,
+    R3DDecoder,
+}
+
+impl SdkLibrary {
+    pub const ALL: [SdkLibrary; 5] = [SdkLibrary::R3DSDK, SdkLibrary::RedCuda, SdkLibrary::RedOpenCL, SdkLibrary::RedMetal, SdkLibrary::R3DDecoder];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::R3DSDK     => "REDR3D",
+            Self::RedCuda    => "REDCuda",
+            Self::RedOpenCL  => "REDOpenCL",
+            Self::RedMetal   => "REDMetal",
+            Self::R3DDecoder => "R3DDecoder",
+        }
+    }
+
+    /// Names this library is reported under in the SDK version string
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Self::R3DSDK     => &["REDR3D"],
+            Self::RedCuda    => &["REDCuda"],
+            Self::RedOpenCL  => &["REDOpenCL", "REDCL"],
+            Self::RedMetal   => &["REDMetal"],
+            Self::R3DDecoder => &["R3DDecoder", "REDDecoder"],
+        }
+    }
+}
+
+/// Loaded library with a different version than the headers the crate was built with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionMismatch {
+    /// `None` for the static library / API version
+    pub library: Option<SdkLibrary>,
+    pub found: Version,
+    pub expected: Version,
+}
+
+impl std::fmt::Display for VersionMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.library.map_or("R3D SDK API", |x| x.name());
+        write!(f, "{name} {} doesn't match the SDK headers {}", self.found, self.expected)
+    }
+}
+
+/// Version information parsed from [`Sdk::version`].
+///
+/// The format of the version string is not specified by the SDK, so every field is optional:
+/// a library is `None` if it's not loaded or its version couldn't be found in the string.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SdkVersion {
+    /// Version of the static library / API
+    pub api: Option<Version>,
+    /// (library, version) for every dynamic library found in the version string
+    pub libraries: Vec<(SdkLibrary, Version)>,
+    /// The unparsed version string
+    pub raw: String,
+}
+
+impl SdkVersion {
+    /// Version of the SDK headers the crate is built against
+    pub const HEADERS: Version = Version::new(9, 1, 0);
+
+    /// Version information of the SDK in this process. The dynamic libraries are only reported after `Sdk::initialize`.
+    pub fn current() -> Self {
+        Self::parse(&Sdk::version())
+    }
+
+    pub fn parse(raw: &str) -> Self {
+        let tokens: Vec<&str> = raw.split(|c: char| !c.is_ascii_alphanumeric() && c != '.')
+                                   .map(|x| x.trim_matches('.'))
+                                   .filter(|x| !x.is_empty())
+                                   .collect();
+        // First version number within a few tokens after the name (skips "v", "version" etc.)
+        let version_after = |i: usize| tokens.iter().skip(i + 1).take(3).find_map(|x| Version::parse(x));
+
+        let mut api = None;
+        let mut libraries = Vec::new();
+        for (i, token) in tokens.iter().enumerate() {
+            if api.is_none() && (token.eq_ignore_ascii_case("R3DSDK") || (token.eq_ignore_ascii_case("R3D") && tokens.get(i + 1).is_some_and(|x| x.eq_ignore_ascii_case("SDK")))) {
+                api = version_after(i);
+            }
+            for library in SdkLibrary::ALL {
+                if library.aliases().iter().any(|x| token.eq_ignore_ascii_case(x))
+                    && !libraries.iter().any(|(x, _)| *x == library)
+                    && let Some(version) = version_after(i) {
+                    libraries.push((library, version));
+                }
+            }
+        }
+
+        Self { api, libraries, raw: raw.to_owned() }
+    }
+
+    pub fn library(&self, library: SdkLibrary) -> Option<Version> {
+        self.libraries.iter().find(|(x, _)| *x == library).map(|(_, v)| *v)
+    }
+
+    /// API version and loaded libraries which don't match [`HEADERS`](Self::HEADERS).
+    pub fn mismatches(&self) -> Vec<VersionMismatch> {
+        let api = self.api.map(|x| (None, x));
+        api.into_iter().chain(self.libraries.iter().map(|&(library, version)| (Some(library), version)))
+            .filter(|(_, found)| *found != Self::HEADERS)
+            .map(|(library, found)| VersionMismatch { library, found, expected: Self::HEADERS })
+            .collect()
+    }
+
+    /// `RedError::LibraryVersionMismatch` if any of [`mismatches`](Self::mismatches) is found
+    pub fn check(&self) -> RedResult<()> {
+        if self.mismatches().is_empty() { Ok(()) } else { Err(RedError::LibraryVersionMismatch) }
+    }
+}
+
+/// One `name: version` line per component, suitable for logs and support tickets
+impl std::fmt::Display for SdkVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.api {
+            Some(v) => writeln!(f, "R3D SDK: {v}")?,
+            None    => writeln!(f, "R3D SDK: unknown")?,
+        }
+        writeln!(f, "Headers: {}", Self::HEADERS)?;
+        for library in SdkLibrary::ALL {
+            match self.library(library) {
+                Some(v) => writeln!(f, "{}: {v}", library.name())?,
+                None    => writeln!(f, "{}: not loaded", library.name())?,
+            }
+        }
+        write!(f, "Raw: {}", self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(Version::parse("9.1.0"), Some(Version::new(9, 1, 0)));
+        assert_eq!(Version::parse("v9.1"), Some(Version::new(9, 1, 0)));
+        assert_eq!(Version::parse("9.1.0.1234"), Some(Version::new(9, 1, 0)));
+        assert_eq!(Version::parse("9.1.2b"), Some(Version::new(9, 1, 2)));
+        assert_eq!(Version::parse("8.6.0-beta1"), Some(Version::new(8, 6, 0)));
+        assert_eq!(Version::parse("9"), None);
+        assert_eq!(Version::parse("9.x"), None);
+        assert_eq!(Version::parse("9.1.b"), None);
+    }
+
+    #[test]
+    fn parse_sdk_version() {
+        let v = SdkVersion::parse("R3DSDK 9.1.0 [Release 64-bit] REDR3D v9.1.0, REDCuda v9.1.0, REDOpenCL v9.1.0");
+        assert_eq!(v.api, Some(SdkVersion::HEADERS));
+        assert_eq!(v.library(SdkLibrary::R3DSDK), Some(SdkVersion::HEADERS));
+        assert_eq!(v.library(SdkLibrary::RedCuda), Some(SdkVersion::HEADERS));
+        assert_eq!(v.library(SdkLibrary::RedOpenCL), Some(SdkVersion::HEADERS));
+        assert_eq!(v.library(SdkLibrary::RedMetal), None);
+        assert!(v.check().is_ok());
+
+        let v = SdkVersion::parse("R3D SDK v9.1.0 (mock backend), REDR3D v9.1.0 (mock), R3DDecoder v9.1.0 (mock)");
+        assert_eq!(v.api, Some(SdkVersion::HEADERS));
+        assert_eq!(v.library(SdkLibrary::R3DDecoder), Some(SdkVersion::HEADERS));
+        assert!(v.mismatches().is_empty());
+    }
+
+    #[test]
+    fn sdk_version_mismatches() {
+        let v = SdkVersion::parse("R3DSDK 8.5.1 [Release] REDR3D version 8.5.1, REDCL 9.1.0.1234; REDCuda: v9.1.0b");
+        assert_eq!(v.api, Some(Version::new(8, 5, 1)));
+        assert_eq!(v.library(SdkLibrary::RedOpenCL), Some(SdkVersion::HEADERS));
+        assert_eq!(v.library(SdkLibrary::RedCuda), Some(SdkVersion::HEADERS));
+        let mismatches = v.mismatches();
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0], VersionMismatch { library: None, found: Version::new(8, 5, 1), expected: SdkVersion::HEADERS });
+        assert_eq!(mismatches[1].library, Some(SdkLibrary::R3DSDK));
+        assert!(matches!(v.check(), Err(RedError::LibraryVersionMismatch)));
+
+        let v = SdkVersion::parse("unknown");
+        assert_eq!(v, SdkVersion { api: None, libraries: vec![], raw: "unknown".into() });
+        assert!(v.check().is_ok());
+    }
+}
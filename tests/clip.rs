@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#![cfg(feature = "mock")]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+
+#[test]
+fn clip_load_and_decode() {
+    let _sdk = sdk();
+    MockClip::new("decode.R3D").dimensions(64, 32).frame_count(5).dropped_frames([3]).register();
+    assert!(Clip::from_path("missing.R3D").is_err());
+
+    let clip = Clip::from_path("decode.R3D").unwrap();
+    assert_eq!(clip.status(), LoadStatus::ClipLoaded);
+    assert_eq!((clip.width(), clip.height(), clip.video_frame_count()), (64, 32, 5));
+
+    let full = clip.decode_video_frame(4, VideoDecodeMode::FullResPremium, VideoPixelType::Rgb16bitInterleaved, None, None, None).unwrap().unwrap();
+    assert_eq!(full.len(), 64 * 32 * 3 * 2);
+    let half = clip.decode_video_frame(4, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, None, None).unwrap().unwrap();
+    assert_eq!(half.len(), 32 * 16 * 3 * 2);
+    // Gradient pattern: blue is the frame number
+    let first = clip.decode_video_frame(0, VideoDecodeMode::FullResPremium, VideoPixelType::Rgb16bitInterleaved, None, None, None).unwrap().unwrap();
+    assert!(full.as_slice::<u16>()[2] > first.as_slice::<u16>()[2]);
+
+    // Decoding into a caller provided buffer
+    let mut buffer = AlignedBuffer::new(full.len(), 16).unwrap();
+    assert!(clip.decode_video_frame(4, VideoDecodeMode::FullResPremium, VideoPixelType::Rgb16bitInterleaved, None, None, Some(&mut buffer)).unwrap().is_none());
+    assert_eq!(buffer.as_slice::<u16>(), full.as_slice::<u16>());
+
+    let e = clip.decode_video_frame(3, VideoDecodeMode::FullResPremium, VideoPixelType::Rgb16bitInterleaved, None, None, None).err().unwrap();
+    assert!(matches!(e.root(), RedError::IsDroppedFrame));
+    let e = clip.decode_video_frame(5, VideoDecodeMode::FullResPremium, VideoPixelType::Rgb16bitInterleaved, None, None, None).err().unwrap();
+    assert!(matches!(e.root(), RedError::RequestOutOfRange));
+}
+
+#[test]
+fn diff_from_clip_default() {
+    let _sdk = sdk();
+    MockClip::new("diff.R3D").dimensions(64, 32).frame_count(1).register();
+    let clip = Clip::from_path("diff.R3D").unwrap();
+
+    let mut settings = clip.default_image_processing_settings();
+    assert!(settings.diff_from_clip_default(&clip).is_empty());
+    settings.set_exposure_adjust(0.5);
+    settings.set_chroma_noise_reduction(true);
+    let changes = settings.diff_from_clip_default(&clip);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].to_string(), "chroma_noise_reduction: off -> on");
+    assert_eq!((changes[1].field, &changes[1].new), ("exposure_adjust", &SettingValue::Float(0.5)));
+}
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Not every test file uses every helper
+#![allow(dead_code)]
+
+use r3d_rs::*;
+use std::time::Duration;
+
+/// Decode delay of the slow mock clips
+pub const DELAY: Duration = Duration::from_millis(100);
+
+/// Every test uses the same components, the SDK stays initialized while any of them runs
+pub fn sdk() -> Sdk {
+    Sdk::initialize("", InitializeFlags::R3DDecoder).unwrap()
+}
+
+pub fn r3d_decoder() -> R3dDecoder {
+    let mut options = R3dDecoderOptions::new().unwrap();
+    let devices = R3dDecoderOptions::opencl_device_list().unwrap();
+    options.use_opencl_device(&devices[0]).unwrap();
+    R3dDecoder::new(&options).unwrap()
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#![cfg(feature = "mock")]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+
+#[test]
+fn r3d_decoder_failure() {
+    let _sdk = sdk();
+    MockClip::new("failure.R3D").dimensions(64, 32).frame_count(4).dropped_frames([2]).register();
+    let clip = Clip::from_path("failure.R3D").unwrap();
+    let decoder = r3d_decoder();
+
+    let job = DecodeRequest::new(&clip, 1).frame_metadata(true).build().unwrap();
+    let job = pollster::block_on(decoder.decode(job).unwrap()).unwrap();
+    assert_eq!(job.video_frame_no(), 1);
+    assert!(job.metadata().is_ok());
+
+    // The job is handed back on failure
+    let job = DecodeRequest::new(&clip, 2).build().unwrap();
+    let failure = pollster::block_on(decoder.decode(job).unwrap()).err().unwrap();
+    assert!(matches!(failure.error.root(), RedError::ErrorProcessing));
+    assert_eq!(failure.job.unwrap().video_frame_no(), 2);
+
+    // Rejected at submission, the callback never fires
+    let mut job = R3dDecodeJob::new().unwrap();
+    job.set_clip(&clip);
+    job.set_video_frame_no(1);
+    let failure = decoder.decode(job).err().unwrap();
+    assert!(failure.job.is_some());
+}
+
+#[test]
+fn async_decoder_failure() {
+    let _sdk = sdk();
+    MockClip::new("failure_async.R3D").dimensions(64, 32).frame_count(4).dropped_frames([3]).register();
+    let clip = Clip::from_path("failure_async.R3D").unwrap();
+    let decoder = AsyncDecoder::new(2).unwrap();
+
+    let job = DecodeRequest::new(&clip, 1).build_async().unwrap();
+    let size = AsyncDecoder::size_buffer_needed(&job);
+    let job = pollster::block_on(decoder.decode_for_gpu_sdk(job).unwrap()).unwrap();
+    assert_eq!((job.video_frame_no(), job.output_buffer_size()), (1, size));
+
+    let failure = pollster::block_on(decoder.decode_for_gpu_sdk(DecodeRequest::new(&clip, 3).build_async().unwrap()).unwrap()).err().unwrap();
+    assert!(matches!(failure.error.root(), RedError::IsDroppedFrame));
+    assert_eq!(failure.job.unwrap().video_frame_no(), 3);
+
+    // No output buffer, rejected at submission
+    let mut job = AsyncDecompressJob::new();
+    job.set_clip(&clip);
+    let failure = decoder.decode_for_gpu_sdk(job).err().unwrap();
+    assert!(matches!(failure.error.root(), RedError::OutputBufferInvalid));
+    assert!(failure.job.is_some());
+
+    pollster::block_on(decoder.close());
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#![cfg(feature = "mock")]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+
+#[test]
+fn decode_request_validation() {
+    let _sdk = sdk();
+    MockClip::new("request.R3D").dimensions(64, 32).frame_count(4).register();
+    MockClip::new("request_hdrx.R3D").dimensions(64, 32).frame_count(4).video_track_count(2).register();
+    let clip = Clip::from_path("request.R3D").unwrap();
+    let hdrx = Clip::from_path("request_hdrx.R3D").unwrap();
+    let root = |r: RedResult<()>| r.unwrap_err().root().clone();
+
+    assert!(DecodeRequest::new(&clip, 3).validate().is_ok());
+    assert!(matches!(root(DecodeRequest::new(&clip, 4).validate()), RedError::RequestOutOfRange));
+    assert!(matches!(root(DecodeRequest::new(&clip, 0).video_track_no(1).validate()), RedError::RequestOutOfRange));
+    let hdr = HdrProcessingSettings::default();
+    assert!(matches!(root(DecodeRequest::new(&clip, 0).hdr_processing(&hdr).validate()), RedError::NotAnHDRxClip));
+    assert!(DecodeRequest::new(&hdrx, 0).hdr_processing(&hdr).validate().is_ok());
+    assert!(DecodeRequest::new(&hdrx, 0).video_track_no(1).decode().is_ok());
+
+    // Only R3DDecoder rejects BGRA and ColorVersion1, `validate` leaves them to the decode path
+    let mut v1 = clip.default_image_processing_settings();
+    v1.set_version(ColorVersion::Version1);
+    assert!(matches!(DecodeRequest::new(&clip, 0).image_processing(&v1).build().err().unwrap().root(), RedError::InvalidJobParameterColorVersion1));
+    assert!(DecodeRequest::new(&clip, 0).image_processing(&v1).validate().is_ok());
+    let bgra = DecodeRequest::new(&clip, 0).pixel_type(VideoPixelType::Bgra8bitInterleaved);
+    assert!(matches!(bgra.build().err().unwrap().root(), RedError::InvalidJobParameterPixelType));
+    assert_eq!(bgra.decode().unwrap().len(), 64 * 32 * 4);
+    let job = DecodeRequest::new(&clip, 2).mode(VideoDecodeMode::HalfResGood).build().unwrap();
+    assert_eq!((job.video_frame_no(), job.output_buffer_size()), (2, 32 * 16 * 3 * 2));
+
+    // AsyncDecompressJob only decompresses, processing parameters are refused instead of ignored
+    let settings = clip.default_image_processing_settings();
+    let mut timeline = GradeTimeline::new();
+    timeline.insert(0, GradePreset::from_settings(&settings).unwrap(), Interpolation::Linear);
+    assert!(DecodeRequest::new(&clip, 1).build_async().is_ok());
+    for request in [
+        DecodeRequest::new(&clip, 1).image_processing(&settings),
+        DecodeRequest::new(&clip, 1).pixel_type(VideoPixelType::Rgb16bitInterleaved),
+        DecodeRequest::new(&clip, 1).grade_timeline(&timeline),
+        DecodeRequest::new(&hdrx, 1).hdr_processing(&hdr),
+    ] {
+        assert!(matches!(request.build_async().err().unwrap().root(), RedError::ParameterUnsupported));
+    }
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#![cfg(feature = "mock")]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+use std::time::{ Duration, Instant };
+
+#[test]
+fn parallel_decode_order() {
+    let _sdk = sdk();
+    MockClip::new("parallel.R3D").dimensions(64, 32).frame_count(12).dropped_frames([5]).register();
+    let clip = Clip::from_path("parallel.R3D").unwrap();
+
+    let mut frames = Vec::new();
+    clip.decode_frames_parallel(2..12, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, 4)
+        .in_order(true)
+        .for_each(|frame_no, result| {
+            assert_eq!(result.is_err(), frame_no == 5);
+            frames.push(frame_no);
+            true
+        });
+    assert_eq!(frames, (2..12).collect::<Vec<_>>());
+
+    let mut frames = Vec::new();
+    clip.decode_frames_parallel(0..12, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, 3)
+        .for_each(|frame_no, _| { frames.push(frame_no); true });
+    frames.sort();
+    assert_eq!(frames, (0..12).collect::<Vec<_>>());
+
+    // Stopping from the callback
+    let mut count = 0;
+    clip.decode_frames_parallel(0..12, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, 2)
+        .in_order(true)
+        .for_each(|frame_no, _| { assert_eq!(frame_no, count); count += 1; count < 3 });
+    assert_eq!(count, 3);
+
+    let shared = SharedClip::new(clip);
+    let rx = shared.decode_frames_parallel(0..12, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, 4).in_order(true).max_buffered_frames(2).spawn();
+    let frames: Vec<_> = rx.iter().map(|(frame_no, result)| { assert_eq!(result.is_err(), frame_no == 5); frame_no }).collect();
+    assert_eq!(frames, (0..12).collect::<Vec<_>>());
+}
+
+#[test]
+fn parallel_decode_max_buffered_frames() {
+    let _sdk = sdk();
+    let delay = DELAY / 4;
+    MockClip::new("parallel_slow.R3D").dimensions(64, 32).frame_count(8).decode_delay(delay).register();
+    let clip = Clip::from_path("parallel_slow.R3D").unwrap();
+    let decode = |max_buffered: usize, callback_time: Duration| {
+        let start = Instant::now();
+        let mut count = 0;
+        clip.decode_frames_parallel(0..8, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, 4)
+            .max_buffered_frames(max_buffered)
+            .for_each(|_, result| { assert!(result.is_ok()); std::thread::sleep(callback_time); count += 1; true });
+        assert_eq!(count, 8);
+        start.elapsed()
+    };
+    // One frame at a time: the decodes can't overlap
+    assert!(decode(1, Duration::ZERO) >= delay * 8);
+    assert!(decode(2, Duration::ZERO) >= delay * 4);
+    // The frame in the callback counts towards the limit, so the next decode only starts once it returned
+    assert!(decode(1, delay) >= delay * 16);
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Runs in its own process, so dropping the last guard finalizes the SDK.
+#![cfg(feature = "mock")]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+
+#[test]
+fn drop_decoders_and_sdk_while_decoding() {
+    let sdk = sdk();
+    MockClip::new("release.R3D").dimensions(64, 32).frame_count(4).decode_delay(DELAY).register();
+    let clip = Clip::from_path("release.R3D").unwrap();
+    let decoder = r3d_decoder();
+    let async_decoder = AsyncDecoder::new(2).unwrap();
+    let futures: Vec<_> = (0..2).map(|i| decoder.decode(DecodeRequest::new(&clip, i).build().unwrap()).unwrap()).collect();
+    let async_futures: Vec<_> = (0..2).map(|i| async_decoder.decode_for_gpu_sdk(DecodeRequest::new(&clip, i).build_async().unwrap()).unwrap()).collect();
+
+    // The mock aborts if the SDK is finalized while a callback is still running
+    drop((async_decoder, decoder));
+    drop((sdk, clip));
+    assert!(!Sdk::is_initialized());
+
+    for future in futures {
+        assert!(pollster::block_on(future).is_ok());
+    }
+    for future in async_futures {
+        assert!(matches!(pollster::block_on(future).err().unwrap().error.root(), RedError::Cancelled));
+    }
+}
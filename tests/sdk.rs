@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+// Runs in its own process, as the SDK state is global and the other tests keep it initialized.
+#![cfg(feature = "mock")]
+
+use r3d_rs::*;
+
+#[test]
+fn initialize_refcount() {
+    MockClip::new("sdk_refcount.R3D").dimensions(32, 16).frame_count(2).register();
+
+    assert!(!Sdk::is_initialized());
+    assert_eq!(Sdk::initialized_flags(), None);
+    assert!(matches!(Sdk::current(), Err(RedError::SdkNotInitialized)));
+    assert!(matches!(Clip::from_path("sdk_refcount.R3D").err().unwrap().root(), RedError::SdkNotInitialized));
+    let version = SdkVersion::current();
+    assert_eq!(version.api, Some(SdkVersion::HEADERS));
+    assert!(version.libraries.is_empty());
+
+    let sdk = Sdk::initialize("", InitializeFlags::R3DDecoder).unwrap();
+    assert_eq!(Sdk::initialized_flags(), Some(InitializeFlags::R3DDecoder));
+    assert_eq!(SdkVersion::current().library(SdkLibrary::R3DDecoder), Some(SdkVersion::HEADERS));
+    assert!(SdkVersion::current().check().is_ok());
+
+    // Already loaded components can be requested again, others only after the SDK was finalized
+    let second = Sdk::initialize("", InitializeFlags::None).unwrap();
+    assert!(matches!(Sdk::initialize("", InitializeFlags::Cuda), Err(RedError::SdkAlreadyInitialized(flags)) if flags == InitializeFlags::R3DDecoder));
+    let current = Sdk::current().unwrap();
+
+    // Clips and decoders keep the SDK initialized
+    let clip = Clip::from_path("sdk_refcount.R3D").unwrap();
+    let decoder = AsyncDecoder::new(1).unwrap();
+    drop((sdk, second, current));
+    assert!(Sdk::is_initialized());
+    drop(clip);
+    assert!(Sdk::is_initialized());
+    drop(decoder);
+    assert!(!Sdk::is_initialized());
+    assert!(matches!(Sdk::current(), Err(RedError::SdkNotInitialized)));
+
+    let sdk = Sdk::initialize("", InitializeFlags::Cuda).unwrap();
+    assert_eq!(Sdk::initialized_flags(), Some(InitializeFlags::Cuda));
+    assert!(matches!(R3dDecoderOptions::new().and_then(|x| R3dDecoder::new(&x)), Err(_)));
+    drop(sdk);
+    assert!(!Sdk::is_initialized());
+    assert!(MockClip::unregister("sdk_refcount.R3D"));
+}
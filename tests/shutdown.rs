@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#![cfg(feature = "mock")]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+use std::time::Instant;
+
+#[test]
+fn r3d_decoder_shutdown() {
+    let _sdk = sdk();
+    MockClip::new("r3d_decoder_shutdown.R3D").dimensions(64, 32).frame_count(4).decode_delay(DELAY).register();
+    let clip = Clip::from_path("r3d_decoder_shutdown.R3D").unwrap();
+    let decoder = r3d_decoder();
+
+    let futures: Vec<_> = (0..4).map(|i| decoder.decode(DecodeRequest::new(&clip, i).build().unwrap()).unwrap()).collect();
+    // Waits for the submitted jobs before releasing the decoder
+    pollster::block_on(decoder.shutdown());
+    for (i, future) in futures.into_iter().enumerate() {
+        assert_eq!(pollster::block_on(future).unwrap().video_frame_no(), i);
+    }
+}
+
+#[test]
+fn async_decoder_close_aborts_pending() {
+    let _sdk = sdk();
+    MockClip::new("async_decoder_close.R3D").dimensions(64, 32).frame_count(4).decode_delay(DELAY).register();
+    let clip = Clip::from_path("async_decoder_close.R3D").unwrap();
+    let decoder = AsyncDecoder::new(1).unwrap();
+
+    let futures: Vec<_> = (0..4).map(|i| decoder.decode_for_gpu_sdk(DecodeRequest::new(&clip, i).build_async().unwrap()).unwrap()).collect();
+    pollster::block_on(decoder.close());
+    for future in futures {
+        let failure = pollster::block_on(future).err().unwrap();
+        assert!(matches!(failure.error.root(), RedError::Cancelled));
+        assert!(failure.job.is_some());
+    }
+}
+
+#[test]
+fn shutdown_leaks_after_deadline() {
+    let _sdk = sdk();
+    MockClip::new("shutdown_hung.R3D").dimensions(64, 32).frame_count(4).decode_delay(DELAY * 5).register();
+    let clip = Clip::from_path("shutdown_hung.R3D").unwrap();
+    let mut decoder = r3d_decoder();
+    decoder.set_default_timeout(Some(DELAY / 2));
+
+    let future = decoder.decode(DecodeRequest::new(&clip, 0).build().unwrap()).unwrap();
+    // Gives up waiting for the job at the default timeout and leaks the decoder
+    let start = Instant::now();
+    pollster::block_on(decoder.shutdown());
+    assert!(start.elapsed() < DELAY * 3);
+    assert!(matches!(pollster::block_on(future).err().unwrap().error, RedError::Timeout));
+    // The leaked job still points to the clip
+    std::mem::forget(clip);
+}
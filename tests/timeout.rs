@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#![cfg(feature = "mock")]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+use std::time::{ Duration, Instant };
+
+#[test]
+fn r3d_decoder_timeout() {
+    let _sdk = sdk();
+    MockClip::new("timeout.R3D").dimensions(64, 32).frame_count(4).register();
+    MockClip::new("timeout_slow.R3D").dimensions(64, 32).frame_count(4).decode_delay(DELAY * 3).register();
+    let clip = Clip::from_path("timeout.R3D").unwrap();
+    let slow = Clip::from_path("timeout_slow.R3D").unwrap();
+    let mut decoder = r3d_decoder();
+
+    decoder.set_default_timeout(Some(DELAY / 4));
+    let start = Instant::now();
+    let failure = pollster::block_on(decoder.decode(DecodeRequest::new(&slow, 0).build().unwrap()).unwrap()).err().unwrap();
+    assert!(matches!(failure.error, RedError::Timeout));
+    assert!(failure.job.is_none());
+    assert!(start.elapsed() < DELAY * 3);
+
+    // `with_timeout` adds a deadline, it can't extend the default timeout
+    decoder.set_default_timeout(None);
+    let future = decoder.decode(DecodeRequest::new(&clip, 0).build().unwrap()).unwrap().with_timeout(Duration::from_secs(10));
+    assert!(pollster::block_on(future).is_ok());
+    decoder.set_default_timeout(Some(DELAY / 4));
+    let future = decoder.decode(DecodeRequest::new(&slow, 1).build().unwrap()).unwrap().with_timeout(Duration::from_secs(10));
+    assert!(matches!(pollster::block_on(future).err().unwrap().error, RedError::Timeout));
+
+    // The timed out jobs are still running when the decoder is dropped, it's leaked after the default timeout
+    // and the jobs keep pointing to the clip
+    std::mem::forget(slow);
+}
+
+#[test]
+fn timed_out_job_released_outside_callback() {
+    let _sdk = sdk();
+    MockClip::new("timeout_release.R3D").dimensions(64, 32).frame_count(4).decode_delay(DELAY).register();
+    let clip = Clip::from_path("timeout_release.R3D").unwrap();
+
+    // The mock aborts if a job or its metadata is released inside the job's callback
+    let mut decoder = r3d_decoder();
+    decoder.set_default_timeout(Some(DELAY / 4));
+    let future = decoder.decode(DecodeRequest::new(&clip, 0).frame_metadata(true).build().unwrap()).unwrap();
+    assert!(matches!(pollster::block_on(future).err().unwrap().error, RedError::Timeout));
+    std::thread::sleep(DELAY * 2);
+    decoder.set_default_timeout(None);
+    assert!(pollster::block_on(decoder.decode(DecodeRequest::new(&clip, 1).build().unwrap()).unwrap()).is_ok());
+
+    // Dropping the future doesn't release the job on the callback thread either
+    drop(decoder.decode(DecodeRequest::new(&clip, 2).frame_metadata(true).build().unwrap()).unwrap());
+    pollster::block_on(decoder.shutdown());
+
+    let decoder = AsyncDecoder::new(1).unwrap();
+    let future = decoder.decode_for_gpu_sdk(DecodeRequest::new(&clip, 0).frame_metadata(true).build_async().unwrap()).unwrap();
+    assert!(matches!(pollster::block_on(future.with_timeout(DELAY / 4)).err().unwrap().error, RedError::Timeout));
+    drop(decoder.decode_for_gpu_sdk(DecodeRequest::new(&clip, 1).frame_metadata(true).build_async().unwrap()).unwrap());
+    pollster::block_on(decoder.close());
+}
+
+#[test]
+fn gpu_decoder_timeout_aborts_job() {
+    let _sdk = sdk();
+    MockClip::new("timeout_gpu.R3D").dimensions(64, 32).frame_count(4).decode_delay(DELAY).register();
+    let clip = Clip::from_path("timeout_gpu.R3D").unwrap();
+    assert!(GpuDecoder::decode_supported_for_clip(&clip));
+    let decoder = GpuDecoder::new().unwrap();
+
+    let future = decoder.decode_for_gpu_sdk(DecodeRequest::new(&clip, 0).build_async().unwrap()).unwrap();
+    let failure = pollster::block_on(future.with_timeout(DELAY / 4)).err().unwrap();
+    assert!(matches!(failure.error, RedError::Timeout));
+    assert!(failure.job.is_none());
+
+    // The job was aborted, so its callback reports it as cancelled
+    pollster::block_on(decoder.close());
+    #[cfg(feature = "metrics")]
+    assert_eq!(DecodeMetrics::snapshot(DecodeSource::GpuDecoder).failed_by_status.get("Cancelled"), Some(&1));
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// Copyright © 2025 Adrian <adrian.eddy at gmail>
+
+#![cfg(all(feature = "mock", feature = "tokio"))]
+
+mod common;
+
+use common::*;
+use r3d_rs::*;
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::time::Instant;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn async_wrappers() {
+    let _sdk = sdk();
+    MockClip::new("tokio.R3D").dimensions(64, 32).frame_count(4).dropped_frames([2]).register();
+    assert!(SharedClip::from_path_async("tokio_missing.R3D").await.is_err());
+    let clip = SharedClip::from_path_async("tokio.R3D").await.unwrap();
+    assert_eq!(clip.video_frame_count(), 4);
+
+    let frame = clip.decode_video_frame_async(1, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, None).await.unwrap();
+    assert_eq!(frame.len(), 32 * 16 * 3 * 2);
+    let e = clip.decode_video_frame_async(2, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, None).await.err().unwrap();
+    assert!(matches!(e.root(), RedError::IsDroppedFrame));
+    let e = clip.decode_video_frame_async(4, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, None).await.err().unwrap();
+    assert!(matches!(e.root(), RedError::RequestOutOfRange));
+    assert!(clip.check_frame_async(1).await.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn limiter_decoders() {
+    let _sdk = sdk();
+    MockClip::new("tokio_decoders.R3D").dimensions(64, 32).frame_count(4).dropped_frames([3]).register();
+    let clip = Clip::from_path("tokio_decoders.R3D").unwrap();
+    let limiter = DecodeLimiter::new(1);
+
+    let decoder = r3d_decoder();
+    let job = limiter.decode(&decoder, DecodeRequest::new(&clip, 1).build().unwrap()).await.unwrap();
+    assert_eq!(job.video_frame_no(), 1);
+    let failure = limiter.decode(&decoder, DecodeRequest::new(&clip, 3).build().unwrap()).await.err().unwrap();
+    assert!(failure.job.is_some());
+
+    let decoder = AsyncDecoder::new(1).unwrap();
+    let job = limiter.decode_for_gpu_sdk(&decoder, DecodeRequest::new(&clip, 2).build_async().unwrap()).await.unwrap();
+    assert_eq!(job.video_frame_no(), 2);
+    assert_eq!(limiter.available(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn limiter_concurrency() {
+    let limiter = DecodeLimiter::new(2);
+    let running = Arc::new(AtomicUsize::new(0));
+    let max_running = Arc::new(AtomicUsize::new(0));
+    let tasks: Vec<_> = (0..8).map(|_| {
+        let (limiter, running, max_running) = (limiter.clone(), running.clone(), max_running.clone());
+        tokio::spawn(async move {
+            limiter.run(|| async {
+                max_running.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                tokio::time::sleep(DELAY / 10).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            }).await
+        })
+    }).collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+    assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    assert_eq!(limiter.available(), 2);
+
+    // The permit is held until dropped
+    let permit = limiter.acquire().await;
+    assert_eq!(limiter.available(), 1);
+    drop(permit);
+    assert_eq!(limiter.available(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn limiter_software_decode() {
+    let _sdk = sdk();
+    MockClip::new("tokio_slow.R3D").dimensions(64, 32).frame_count(6).decode_delay(DELAY / 2).register();
+    let clip = SharedClip::from_path_async("tokio_slow.R3D").await.unwrap();
+    let limiter = DecodeLimiter::new(2);
+
+    let start = Instant::now();
+    let tasks: Vec<_> = (0..6).map(|i| {
+        let (limiter, clip) = (limiter.clone(), clip.clone());
+        tokio::spawn(async move {
+            limiter.decode_video_frame(&clip, i, VideoDecodeMode::HalfResGood, VideoPixelType::Rgb16bitInterleaved, None, None).await
+        })
+    }).collect();
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+    // Two decodes at a time
+    assert!(start.elapsed() >= DELAY / 2 * 3);
+}